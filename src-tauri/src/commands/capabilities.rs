@@ -0,0 +1,175 @@
+// src-tauri/src/commands/capabilities.rs
+
+use super::engine::EngineManager;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Manager, Wry};
+
+// Capabilities are compiled into the binary, so the bundled file is what the build enforces.
+const DEFAULT_CAPABILITY: &str = include_str!("../../capabilities/default.json");
+const WRITE_PROBE_FILE: &str = ".indextts-hub-access-test";
+
+#[derive(Debug, Deserialize)]
+struct CapabilityFile {
+    #[serde(default)]
+    permissions: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FeatureCapability {
+    pub feature: String,
+    pub plugin: String,
+    pub plugin_registered: bool,
+    pub permissions: Vec<String>,
+    pub available: bool,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DirAccess {
+    pub path: String,
+    pub exists: bool,
+    pub readable: bool,
+    pub writable: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CapabilityReport {
+    pub features: Vec<FeatureCapability>,
+    pub dirs: Vec<DirAccess>,
+}
+
+/// Permission identifiers granted to the main window. Entries may be plain strings or
+/// `{ "identifier": ..., "allow": [...] }` objects.
+fn granted_permissions() -> Vec<String> {
+    serde_json::from_str::<CapabilityFile>(DEFAULT_CAPABILITY)
+        .map(|file| {
+            file.permissions
+                .iter()
+                .filter_map(|permission| match permission {
+                    serde_json::Value::String(id) => Some(id.clone()),
+                    other => other
+                        .get("identifier")
+                        .and_then(|id| id.as_str())
+                        .map(str::to_string),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn feature(
+    name: &str,
+    plugin: &str,
+    plugin_registered: bool,
+    granted: &[String],
+    note_when_missing: &str,
+) -> FeatureCapability {
+    let prefix = format!("{}:", plugin);
+    let permissions: Vec<String> = granted
+        .iter()
+        .filter(|id| id.starts_with(&prefix))
+        .cloned()
+        .collect();
+    let available = plugin_registered && !permissions.is_empty();
+    let note = if available {
+        None
+    } else if !plugin_registered {
+        Some(format!(
+            "The {} plugin is not registered in this build. {}",
+            plugin, note_when_missing
+        ))
+    } else {
+        Some(format!(
+            "The {} plugin is registered but no {}:* permission is granted to the main window.",
+            plugin, plugin
+        ))
+    };
+    FeatureCapability {
+        feature: name.to_string(),
+        plugin: plugin.to_string(),
+        plugin_registered,
+        permissions,
+        available,
+        note,
+    }
+}
+
+fn dir_access(path: &str) -> DirAccess {
+    let dir = Path::new(path);
+    let exists = dir.is_dir();
+    let readable = exists && fs::read_dir(dir).is_ok();
+    let writable = exists && {
+        let probe = dir.join(WRITE_PROBE_FILE);
+        let written = fs::write(&probe, b"ok").is_ok();
+        let _ = fs::remove_file(&probe);
+        written
+    };
+    DirAccess {
+        path: path.to_string(),
+        exists,
+        readable,
+        writable,
+    }
+}
+
+/// Reports which plugin-backed features the running build can actually use and whether
+/// the given directories (default: the configured install and model dirs) are accessible.
+#[tauri::command]
+pub fn check_capabilities(
+    app_handle: AppHandle,
+    dirs: Option<Vec<String>>,
+) -> Result<CapabilityReport, String> {
+    let granted = granted_permissions();
+    let features = vec![
+        feature(
+            "opener",
+            "opener",
+            app_handle
+                .try_state::<tauri_plugin_opener::Opener<Wry>>()
+                .is_some(),
+            &granted,
+            "Opening folders and links from the UI will not work.",
+        ),
+        feature(
+            "dialog",
+            "dialog",
+            app_handle
+                .try_state::<tauri_plugin_dialog::Dialog<Wry>>()
+                .is_some(),
+            &granted,
+            "Folder pickers will not open.",
+        ),
+        feature(
+            "shell_open",
+            "shell",
+            app_handle
+                .try_state::<tauri_plugin_shell::Shell<Wry>>()
+                .is_some(),
+            &granted,
+            "Opening URLs through the shell will not work.",
+        ),
+        feature(
+            "notifications",
+            "notification",
+            false,
+            &granted,
+            "deploy-finished and server-event are still emitted; show them with the web Notification API.",
+        ),
+    ];
+
+    let dirs = dirs.unwrap_or_else(|| {
+        let engine = app_handle.state::<EngineManager>();
+        let config = engine.lock();
+        [config.install_dir.clone(), config.model_dir.clone()]
+            .into_iter()
+            .filter(|dir| !dir.is_empty())
+            .collect()
+    });
+
+    Ok(CapabilityReport {
+        features,
+        dirs: dirs.iter().map(|dir| dir_access(dir)).collect(),
+    })
+}
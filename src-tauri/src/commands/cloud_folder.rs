@@ -0,0 +1,163 @@
+// src-tauri/src/commands/cloud_folder.rs
+
+use serde::Serialize;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+// Path components that mark a synced folder, matched case-insensitively as a prefix
+// ("OneDrive - Contoso", "GoogleDrive-me@example.com").
+const PROVIDER_DIR_PREFIXES: [(&str, &str); 9] = [
+    ("onedrive", "OneDrive"),
+    ("dropbox", "Dropbox"),
+    ("icloud drive", "iCloud Drive"),
+    ("iclouddrive", "iCloud Drive"),
+    ("google drive", "Google Drive"),
+    ("googledrive", "Google Drive"),
+    ("box sync", "Box"),
+    ("nutstore", "Nutstore"),
+    ("baidunetdisk", "Baidu Netdisk"),
+];
+// Files or folders the sync clients keep at the root of a synced folder.
+const PROVIDER_MARKERS: [(&str, &str); 4] = [
+    (".dropbox", "Dropbox"),
+    (".dropbox.cache", "Dropbox"),
+    (".tmp.drivedownload", "Google Drive"),
+    (".nutstore", "Nutstore"),
+];
+// Environment variables the OneDrive client sets to its folders on Windows.
+const ONEDRIVE_ENV_VARS: [&str; 3] = ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CloudFolder {
+    pub provider: String,
+    // The synced folder that contains the path.
+    pub root: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NormalizedPath {
+    pub path: String,
+    pub cloud_folder: Option<CloudFolder>,
+    pub warning: Option<String>,
+}
+
+fn cloud_folder(provider: &str, root: &Path) -> CloudFolder {
+    CloudFolder {
+        provider: provider.to_string(),
+        root: root.display().to_string(),
+    }
+}
+
+/// Detects whether `path` lies in a OneDrive, Dropbox, iCloud, Google Drive or similar
+/// synced folder, by known locations, folder names and sync-client marker files.
+pub fn detect_cloud_folder(path: &Path) -> Option<CloudFolder> {
+    for var in ONEDRIVE_ENV_VARS {
+        if let Some(root) = env::var_os(var).filter(|root| !root.is_empty()) {
+            let root = PathBuf::from(root);
+            if path.starts_with(&root) {
+                return Some(cloud_folder("OneDrive", &root));
+            }
+        }
+    }
+
+    let mut prefix = PathBuf::new();
+    let mut previous: Option<String> = None;
+    for component in path.components() {
+        prefix.push(component);
+        let Component::Normal(name) = component else {
+            continue;
+        };
+        let name = name.to_string_lossy().to_lowercase();
+        // macOS: ~/Library/Mobile Documents (iCloud) and ~/Library/CloudStorage/<Provider>-...
+        match previous.as_deref() {
+            Some("library") if name == "mobile documents" => {
+                return Some(cloud_folder("iCloud Drive", &prefix));
+            }
+            Some("cloudstorage") => {
+                let provider = PROVIDER_DIR_PREFIXES
+                    .iter()
+                    .find(|(dir, _)| name.starts_with(dir))
+                    .map_or("Cloud storage", |(_, provider)| provider);
+                return Some(cloud_folder(provider, &prefix));
+            }
+            _ => {}
+        }
+        if let Some((_, provider)) = PROVIDER_DIR_PREFIXES
+            .iter()
+            .find(|(dir, _)| name.starts_with(dir))
+        {
+            return Some(cloud_folder(provider, &prefix));
+        }
+        if let Some((_, provider)) = PROVIDER_MARKERS
+            .iter()
+            .find(|(marker, _)| prefix.join(marker).exists())
+        {
+            return Some(cloud_folder(provider, &prefix));
+        }
+        previous = Some(name);
+    }
+    None
+}
+
+pub fn cloud_folder_warning(cloud: &CloudFolder) -> String {
+    format!(
+        "This folder is synced by {} ({}). Sync locks files during the environment setup, uploads gigabytes of models and can corrupt the virtual environment. Deploy to a local folder outside it instead.",
+        cloud.provider, cloud.root
+    )
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+    match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            PathBuf::from(home).join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Lexically cleans `.` and `..` without touching the disk, since the folder may not exist yet.
+fn clean(path: &Path) -> PathBuf {
+    let mut cleaned = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                cleaned.pop();
+            }
+            other => cleaned.push(other),
+        }
+    }
+    cleaned
+}
+
+/// Turns a user-entered folder into an absolute path (expanding `~`) and warns when it
+/// is inside a cloud-synced folder.
+#[tauri::command]
+pub fn normalize_path(path: String) -> Result<NormalizedPath, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is empty.".to_string());
+    }
+    let expanded = expand_home(trimmed);
+    let absolute = if expanded.is_absolute() {
+        expanded
+    } else {
+        env::current_dir()
+            .map_err(|e| format!("Failed to resolve current directory: {}", e))?
+            .join(expanded)
+    };
+    let normalized = clean(&absolute);
+    let cloud_folder = detect_cloud_folder(&normalized);
+    Ok(NormalizedPath {
+        path: normalized.display().to_string(),
+        warning: cloud_folder.as_ref().map(cloud_folder_warning),
+        cloud_folder,
+    })
+}
+
+/// Reports the cloud sync provider whose folder contains `path`, if any.
+#[tauri::command]
+pub fn check_cloud_folder(path: String) -> Result<Option<CloudFolder>, String> {
+    Ok(detect_cloud_folder(&clean(&expand_home(path.trim()))))
+}
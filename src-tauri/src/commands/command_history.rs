@@ -0,0 +1,114 @@
+// src-tauri/src/commands/command_history.rs
+
+use super::deployments::unix_timestamp;
+use super::log_snapshot::{redact_args, redact_line};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+use tokio::process::Command;
+
+/// The resolved command line of the last run of each step.
+#[derive(Default)]
+pub struct CommandHistory(Mutex<HashMap<String, RecordedCommand>>);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RecordedCommand {
+    pub step: String,
+    pub program: String,
+    pub args: Vec<String>,
+    // Variables set explicitly on the command, on top of the inherited environment.
+    pub env: Vec<(String, String)>,
+    pub cwd: Option<String>,
+    // Shell-style rendering (env, program, args) for pasting into a terminal.
+    pub command_line: String,
+    pub recorded_at: u64,
+}
+
+impl CommandHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn all(&self) -> Vec<RecordedCommand> {
+        let mut commands: Vec<RecordedCommand> = self
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        commands.sort_by_key(|command| command.recorded_at);
+        commands
+    }
+}
+
+fn quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && !arg
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '&' | '|' | ';' | '$'))
+    {
+        return arg.to_string();
+    }
+    format!("\"{}\"", arg.replace('"', "\\\""))
+}
+
+/// Records `command` as the last one run for `step`, with secrets redacted.
+pub fn record_command(app_handle: &AppHandle, step: &str, command: &Command) {
+    let Some(history) = app_handle.try_state::<CommandHistory>() else {
+        return;
+    };
+    let std_command = command.as_std();
+    let program = std_command.get_program().to_string_lossy().to_string();
+    let args = redact_args(
+        &std_command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>(),
+    );
+    let env: Vec<(String, String)> = std_command
+        .get_envs()
+        .filter_map(|(key, value)| {
+            let key = key.to_string_lossy().to_string();
+            let value = value?.to_string_lossy().to_string();
+            let redacted = redact_line(&format!("{}={}", key, value));
+            let value = redacted
+                .split_once('=')
+                .map_or(value, |(_, value)| value.to_string());
+            Some((key, value))
+        })
+        .collect();
+
+    let command_line = env
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, quote(value)))
+        .chain(std::iter::once(quote(&program)))
+        .chain(args.iter().map(|arg| quote(arg)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    history.0.lock().unwrap_or_else(|e| e.into_inner()).insert(
+        step.to_string(),
+        RecordedCommand {
+            step: step.to_string(),
+            program,
+            args,
+            env,
+            cwd: std_command
+                .get_current_dir()
+                .map(|dir| dir.to_string_lossy().to_string()),
+            command_line,
+            recorded_at: unix_timestamp(),
+        },
+    );
+}
+
+/// The command line each deploy/server step last ran, oldest first, so a failing step
+/// can be reproduced by hand in a terminal.
+#[tauri::command]
+pub fn get_last_commands(
+    history: State<'_, CommandHistory>,
+) -> Result<Vec<RecordedCommand>, String> {
+    Ok(history.all())
+}
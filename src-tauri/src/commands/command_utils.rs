@@ -1,11 +1,9 @@
 // src-tauri/src/commands/command_utils.rs
 
+use std::env;
 use std::ffi::OsStr;
 use tokio::process::Command;
 
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
-
 /// Create a `tokio::process::Command` with platform-specific defaults applied.
 pub fn new_command(program: impl AsRef<OsStr>) -> Command {
     let mut command = Command::new(program);
@@ -13,16 +11,119 @@ pub fn new_command(program: impl AsRef<OsStr>) -> Command {
     command
 }
 
-/// Ensure commands spawned from the Tauri UI stay hidden on Windows.
+/// Cleans a user-entered path before it is passed to a child process: surrounding
+/// whitespace, the quotes Explorer's "Copy as path" adds, and trailing separators, which
+/// some Windows tools read as an escaped closing quote (`"C:\index tts\"`). Roots
+/// such as `C:\` and `/` are kept.
+pub fn clean_path_arg(path: &str) -> String {
+    let trimmed = path.trim();
+    let unquoted = ['"', '\'']
+        .iter()
+        .find_map(|quote| {
+            trimmed
+                .strip_prefix(*quote)
+                .and_then(|rest| rest.strip_suffix(*quote))
+        })
+        .unwrap_or(trimmed)
+        .trim();
+    let stripped = unquoted.trim_end_matches(['\\', '/']);
+    if stripped.is_empty() || stripped.ends_with(':') {
+        // `/`, `C:\`: drop only the repeats.
+        unquoted[..(stripped.len() + 1).min(unquoted.len())].to_string()
+    } else {
+        stripped.to_string()
+    }
+}
+
+/// The locale the app inherited, resolved the way libc does (LC_ALL, LC_CTYPE, LANG).
+pub fn inherited_locale() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+}
+
+pub fn is_utf8_locale(locale: &str) -> bool {
+    let lower = locale.to_lowercase();
+    lower.contains("utf-8") || lower.contains("utf8")
+}
+
+/// UTF-8 locale to force on child processes when the inherited one isn't UTF-8.
+/// Windows tools don't read these variables, so nothing is overridden there.
+pub fn utf8_locale_override() -> Option<&'static str> {
+    if cfg!(windows) || inherited_locale().is_some_and(|locale| is_utf8_locale(&locale)) {
+        return None;
+    }
+    // Older macOS releases don't ship C.UTF-8.
+    if cfg!(target_os = "macos") {
+        Some("en_US.UTF-8")
+    } else {
+        Some("C.UTF-8")
+    }
+}
+
+/// Ensure commands spawned from the Tauri UI stay hidden on Windows and produce UTF-8
+/// output, so logs aren't garbled and non-ASCII paths survive.
 pub fn configure_command(command: &mut Command) {
+    // Python (uv-managed interpreters, pip builds) otherwise uses the ANSI code page on Windows.
+    command
+        .env("PYTHONUTF8", "1")
+        .env("PYTHONIOENCODING", "utf-8");
+    if let Some(locale) = utf8_locale_override() {
+        command.env("LC_ALL", locale).env("LANG", locale);
+    }
+
     #[cfg(windows)]
     {
         const CREATE_NO_WINDOW: u32 = 0x0800_0000;
         command.creation_flags(CREATE_NO_WINDOW);
     }
+}
 
-    #[cfg(not(windows))]
-    {
-        let _ = command;
+#[cfg(test)]
+mod tests {
+    use super::clean_path_arg;
+
+    #[test]
+    fn strips_trailing_separators() {
+        assert_eq!(clean_path_arg(r"C:\index-tts\"), r"C:\index-tts");
+        assert_eq!(clean_path_arg(r"C:\index-tts\\"), r"C:\index-tts");
+        assert_eq!(clean_path_arg("/opt/index-tts//"), "/opt/index-tts");
+    }
+
+    #[test]
+    fn keeps_roots() {
+        assert_eq!(clean_path_arg(r"C:\"), r"C:\");
+        assert_eq!(clean_path_arg(r"C:\\"), r"C:\");
+        assert_eq!(clean_path_arg("/"), "/");
+        assert_eq!(clean_path_arg("//"), "/");
+    }
+
+    #[test]
+    fn trims_surrounding_whitespace_but_not_inner_spaces() {
+        assert_eq!(clean_path_arg("  C:\\index tts\\  "), r"C:\index tts");
+        assert_eq!(
+            clean_path_arg("\t/home/me/index tts\n"),
+            "/home/me/index tts"
+        );
+    }
+
+    #[test]
+    fn strips_matching_quotes() {
+        assert_eq!(clean_path_arg(r#""C:\index tts\""#), r"C:\index tts");
+        assert_eq!(clean_path_arg(r#" " C:\index tts " "#), r"C:\index tts");
+        assert_eq!(
+            clean_path_arg("'/home/me/index tts/'"),
+            "/home/me/index tts"
+        );
+        // Unbalanced quotes are left alone.
+        assert_eq!(clean_path_arg(r#""C:\index tts"#), r#""C:\index tts"#);
+    }
+
+    #[test]
+    fn empty_input_stays_empty() {
+        assert_eq!(clean_path_arg(""), "");
+        assert_eq!(clean_path_arg("   "), "");
+        assert_eq!(clean_path_arg(r#""""#), "");
     }
 }
@@ -0,0 +1,336 @@
+// src-tauri/src/commands/config_migrations.rs
+
+use super::config_store::{load_json, remove_json, save_json};
+use super::deploy::DEPLOY_PROGRESS_FILE;
+use super::deployments::{unix_timestamp, DEPLOYMENTS_FILE};
+use super::engine::ENGINE_CONFIG_FILE;
+use super::server::STARTUP_HISTORY_FILE;
+use super::server_config::SERVER_CONFIG_FILE;
+use super::settings::SETTINGS_FILE;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+/// Schema of the persisted config files this build reads and writes.
+pub const CONFIG_SCHEMA_VERSION: u32 = 1;
+pub const SCHEMA_FILE: &str = "config_schema.json";
+// Files covered by the schema version; each migration sees the ones that exist.
+const CONFIG_FILES: [&str; 6] = [
+    SETTINGS_FILE,
+    ENGINE_CONFIG_FILE,
+    SERVER_CONFIG_FILE,
+    DEPLOYMENTS_FILE,
+    DEPLOY_PROGRESS_FILE,
+    STARTUP_HISTORY_FILE,
+];
+
+type ConfigFiles = BTreeMap<&'static str, Value>;
+// Upgrades the files from `version` to `version + 1`.
+type Migration = fn(&mut ConfigFiles);
+const MIGRATIONS: [(u32, Migration); 1] = [(0, migrate_v0_to_v1)];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct SchemaMarker {
+    schema_version: u32,
+    migrated_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigSchemaStatus {
+    pub app_schema_version: u32,
+    // `None` on a fresh install or for config written before versioning.
+    pub stored_schema_version: Option<u32>,
+    pub up_to_date: bool,
+    pub migrated_from: Option<u32>,
+    pub backups: Vec<String>,
+    pub warning: Option<String>,
+}
+
+/// v0 (unversioned): deployment entries written before every field existed fail to
+/// parse as a whole, and hand-edited presets may use upper-case values.
+fn migrate_v0_to_v1(files: &mut ConfigFiles) {
+    if let Some(Value::Array(deployments)) = files.get_mut(DEPLOYMENTS_FILE) {
+        for deployment in deployments.iter_mut().filter_map(Value::as_object_mut) {
+            deployment.entry("imported").or_insert(Value::Bool(false));
+            deployment
+                .entry("registered_at")
+                .or_insert(Value::from(0u64));
+        }
+    }
+
+    let presets = files
+        .get_mut(SERVER_CONFIG_FILE)
+        .and_then(|config| config.get_mut("presets"))
+        .and_then(Value::as_object_mut);
+    for preset in presets.into_iter().flat_map(|presets| presets.values_mut()) {
+        for key in ["device", "precision", "log_level"] {
+            if let Some(Value::String(value)) = preset.get_mut(key) {
+                *value = value.trim().to_lowercase();
+            }
+        }
+    }
+}
+
+/// Runs the migrations that take `files` from schema `from` to `CONFIG_SCHEMA_VERSION`.
+fn migrate_files(files: &mut ConfigFiles, from: u32) {
+    for (_, migration) in MIGRATIONS
+        .iter()
+        .filter(|(version, _)| *version >= from && *version < CONFIG_SCHEMA_VERSION)
+    {
+        migration(files);
+    }
+}
+
+/// `Ok(None)` when no marker was written yet; an unreadable marker is an error rather
+/// than a reason to treat the config as unversioned.
+fn stored_marker(app_handle: &AppHandle) -> Result<Option<SchemaMarker>, String> {
+    load_json(app_handle, SCHEMA_FILE)
+}
+
+fn corrupt_marker_warning(error: &str) -> String {
+    format!(
+        "The config schema marker could not be read ({}); the config was left unchanged. Delete '{}' to migrate it again.",
+        error, SCHEMA_FILE
+    )
+}
+
+/// `<name>.v<from>.bak`, `<name>.v<from>.1.bak`, ...: the names backups of `file_name`
+/// taken before migrating from schema `from` are written under, in order.
+fn backup_names(file_name: &str, from: u32) -> impl Iterator<Item = String> + '_ {
+    (0u32..).map(move |n| match n {
+        0 => format!("{}.v{}.bak", file_name, from),
+        n => format!("{}.v{}.{}.bak", file_name, from, n),
+    })
+}
+
+fn backup_exists(app_handle: &AppHandle, name: &str) -> bool {
+    !matches!(load_json::<Value>(app_handle, name), Ok(None))
+}
+
+/// First backup name that does not exist yet, so a backup from an earlier attempt is
+/// never overwritten.
+fn free_backup_name(app_handle: &AppHandle, file_name: &str, from: u32) -> String {
+    backup_names(file_name, from)
+        .find(|name| !backup_exists(app_handle, name))
+        .expect("unbounded range")
+}
+
+/// Deletes every pre-migration backup and returns the removed names. Backups take the
+/// first free name, so each series ends at the first name that does not exist.
+pub fn remove_migration_backups(app_handle: &AppHandle) -> (Vec<String>, Vec<String>) {
+    let mut removed = Vec::new();
+    let mut errors = Vec::new();
+    for file_name in CONFIG_FILES {
+        for from in 0..CONFIG_SCHEMA_VERSION {
+            for name in backup_names(file_name, from) {
+                if !backup_exists(app_handle, &name) {
+                    break;
+                }
+                match remove_json(app_handle, &name) {
+                    Ok(_) => removed.push(name),
+                    Err(e) => {
+                        errors.push(e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    (removed, errors)
+}
+
+fn save_marker(app_handle: &AppHandle) -> Result<(), String> {
+    save_json(
+        app_handle,
+        SCHEMA_FILE,
+        &SchemaMarker {
+            schema_version: CONFIG_SCHEMA_VERSION,
+            migrated_at: Some(unix_timestamp()),
+        },
+    )
+}
+
+/// Brings the config files up to `CONFIG_SCHEMA_VERSION`, running each migration in
+/// order. Every file is backed up as `<name>.v<old>.bak` before it is rewritten.
+/// Config from a newer app version, or with an unreadable marker, is left untouched;
+/// files that cannot be read are skipped and listed in the warning.
+pub fn migrate_config(app_handle: &AppHandle) -> Result<ConfigSchemaStatus, String> {
+    let mut status = ConfigSchemaStatus {
+        app_schema_version: CONFIG_SCHEMA_VERSION,
+        stored_schema_version: None,
+        up_to_date: true,
+        migrated_from: None,
+        backups: Vec::new(),
+        warning: None,
+    };
+    let stored = match stored_marker(app_handle) {
+        Ok(marker) => marker.map(|marker| marker.schema_version),
+        Err(e) => {
+            status.up_to_date = false;
+            status.warning = Some(corrupt_marker_warning(&e));
+            return Ok(status);
+        }
+    };
+    status.stored_schema_version = stored;
+    match stored {
+        Some(version) if version == CONFIG_SCHEMA_VERSION => return Ok(status),
+        Some(version) if version > CONFIG_SCHEMA_VERSION => {
+            status.up_to_date = false;
+            status.warning = Some(format!(
+                "The config was written by a newer version of the app (schema {}, this build reads {}); it was left unchanged.",
+                version, CONFIG_SCHEMA_VERSION
+            ));
+            return Ok(status);
+        }
+        _ => {}
+    }
+
+    let mut files = ConfigFiles::new();
+    let mut unreadable = Vec::new();
+    for file_name in CONFIG_FILES {
+        match load_json::<Value>(app_handle, file_name) {
+            Ok(Some(value)) => {
+                files.insert(file_name, value);
+            }
+            Ok(None) => {}
+            Err(e) => unreadable.push(e),
+        }
+    }
+    if !unreadable.is_empty() {
+        status.warning = Some(format!(
+            "Some config files could not be read and were not migrated: {}",
+            unreadable.join("; ")
+        ));
+    }
+    let from = stored.unwrap_or(0);
+    if files.is_empty() && unreadable.is_empty() {
+        // Fresh install: nothing to migrate, just tag the schema.
+        save_marker(app_handle)?;
+        status.stored_schema_version = Some(CONFIG_SCHEMA_VERSION);
+        return Ok(status);
+    }
+
+    for (file_name, value) in &files {
+        let backup = free_backup_name(app_handle, file_name, from);
+        save_json(app_handle, &backup, value)?;
+        status.backups.push(backup);
+    }
+    migrate_files(&mut files, from);
+    for (file_name, value) in &files {
+        save_json(app_handle, file_name, value)?;
+    }
+    save_marker(app_handle)?;
+
+    status.stored_schema_version = Some(CONFIG_SCHEMA_VERSION);
+    status.migrated_from = Some(from);
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn get_config_schema_version(app_handle: AppHandle) -> Result<ConfigSchemaStatus, String> {
+    let stored = match stored_marker(&app_handle) {
+        Ok(marker) => marker.map(|marker| marker.schema_version),
+        Err(e) => {
+            return Ok(ConfigSchemaStatus {
+                app_schema_version: CONFIG_SCHEMA_VERSION,
+                stored_schema_version: None,
+                up_to_date: false,
+                migrated_from: None,
+                backups: Vec::new(),
+                warning: Some(corrupt_marker_warning(&e)),
+            })
+        }
+    };
+    Ok(ConfigSchemaStatus {
+        app_schema_version: CONFIG_SCHEMA_VERSION,
+        stored_schema_version: stored,
+        up_to_date: stored == Some(CONFIG_SCHEMA_VERSION),
+        migrated_from: None,
+        backups: Vec::new(),
+        warning: stored
+            .filter(|version| *version > CONFIG_SCHEMA_VERSION)
+            .map(|version| {
+                format!(
+                    "The config was written by a newer version of the app (schema {}).",
+                    version
+                )
+            }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::deployments::Deployment;
+    use crate::commands::server_config::ServerConfig;
+
+    fn fixture(file_name: &str) -> Value {
+        let path = format!(
+            "{}/tests/fixtures/config_v0/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            file_name
+        );
+        let contents = std::fs::read_to_string(&path).expect("fixture missing");
+        serde_json::from_str(&contents).expect("fixture is not valid JSON")
+    }
+
+    #[test]
+    fn v0_fixture_migrates_without_data_loss() {
+        let mut files = ConfigFiles::new();
+        for file_name in [DEPLOYMENTS_FILE, SERVER_CONFIG_FILE, SETTINGS_FILE] {
+            files.insert(file_name, fixture(file_name));
+        }
+        let original = files.clone();
+        assert!(
+            serde_json::from_value::<Vec<Deployment>>(original[DEPLOYMENTS_FILE].clone()).is_err()
+        );
+
+        migrate_files(&mut files, 0);
+
+        let deployments: Vec<Deployment> =
+            serde_json::from_value(files[DEPLOYMENTS_FILE].clone()).expect("deployments parse");
+        assert_eq!(deployments.len(), 2);
+        assert_eq!(deployments[0].target_dir, r"D:\index-tts");
+        assert_eq!(deployments[0].commit.as_deref(), Some("3f1c2a9"));
+        assert!(!deployments[0].imported);
+        assert_eq!(deployments[0].registered_at, 0);
+        // Fields that were already there keep their value.
+        assert!(deployments[1].imported);
+        for (before, after) in original[DEPLOYMENTS_FILE]
+            .as_array()
+            .unwrap()
+            .iter()
+            .zip(files[DEPLOYMENTS_FILE].as_array().unwrap())
+        {
+            for (key, value) in before.as_object().unwrap() {
+                assert_eq!(after.get(key), Some(value), "deployment field {}", key);
+            }
+        }
+
+        let server: ServerConfig = serde_json::from_value(files[SERVER_CONFIG_FILE].clone())
+            .expect("server config parses");
+        let default = &server.presets["default"];
+        assert_eq!(default.device.as_deref(), Some("cuda"));
+        assert_eq!(default.precision.as_deref(), Some("fp16"));
+        assert_eq!(default.log_level.as_deref(), Some("info"));
+        assert_eq!(default.host.as_deref(), Some("0.0.0.0"));
+        assert_eq!(default.port, Some(7860));
+        assert_eq!(default.gpu_index, Some(1));
+        assert_eq!(server.presets["cpu"].cpu_threads, Some(8));
+        assert_eq!(server.cpu_threads, Some(4));
+        assert_eq!(server.stop_scan_ports, [7860, 7861]);
+
+        assert_eq!(files[SETTINGS_FILE], original[SETTINGS_FILE]);
+    }
+
+    #[test]
+    fn migrating_current_schema_changes_nothing() {
+        let mut files = ConfigFiles::new();
+        files.insert(SERVER_CONFIG_FILE, fixture(SERVER_CONFIG_FILE));
+        let original = files.clone();
+        migrate_files(&mut files, CONFIG_SCHEMA_VERSION);
+        assert_eq!(files, original);
+    }
+}
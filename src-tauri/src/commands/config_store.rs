@@ -0,0 +1,229 @@
+// src-tauri/src/commands/config_store.rs
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+const WRITE_PROBE_FILE: &str = ".write-test";
+
+/// Where config files currently go. Starts out as the app config directory and is
+/// switched by `check_config_writable` when that directory can't be written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StorageMode {
+    Primary,
+    Fallback(PathBuf),
+    Memory,
+}
+
+pub struct ConfigStorage {
+    mode: Mutex<StorageMode>,
+    memory: Mutex<HashMap<String, String>>,
+}
+
+impl ConfigStorage {
+    pub fn new() -> Self {
+        ConfigStorage {
+            mode: Mutex::new(StorageMode::Primary),
+            memory: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn mode(&self) -> StorageMode {
+        self.mode.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn set_mode(&self, mode: StorageMode) {
+        *self.mode.lock().unwrap_or_else(|e| e.into_inner()) = mode;
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigWritableStatus {
+    pub config_dir: Option<String>,
+    pub config_writable: bool,
+    pub log_dir: Option<String>,
+    pub log_writable: bool,
+    // "primary", "fallback" or "memory"
+    pub mode: String,
+    pub active_dir: Option<String>,
+    pub warning: Option<String>,
+}
+
+fn storage_mode(app_handle: &AppHandle) -> StorageMode {
+    app_handle
+        .try_state::<ConfigStorage>()
+        .map(|storage| storage.mode())
+        .unwrap_or(StorageMode::Primary)
+}
+
+/// Resolve the location of a JSON file inside the app's config directory.
+pub fn config_file_path(app_handle: &AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    if let StorageMode::Fallback(dir) = storage_mode(app_handle) {
+        return Ok(dir.join(file_name));
+    }
+    let config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve app config directory: {}", e))?;
+    Ok(config_dir.join(file_name))
+}
+
+/// Load a JSON file from the config directory, returning `None` when it does not exist yet.
+pub fn load_json<T: DeserializeOwned>(
+    app_handle: &AppHandle,
+    file_name: &str,
+) -> Result<Option<T>, String> {
+    if storage_mode(app_handle) == StorageMode::Memory {
+        let storage = app_handle.state::<ConfigStorage>();
+        let memory = storage.memory.lock().unwrap_or_else(|e| e.into_inner());
+        return memory
+            .get(file_name)
+            .map(|contents| {
+                serde_json::from_str(contents)
+                    .map_err(|e| format!("Failed to parse '{}': {}", file_name, e))
+            })
+            .transpose();
+    }
+
+    let path = config_file_path(app_handle, file_name)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    let value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse '{}': {}", path.display(), e))?;
+    Ok(Some(value))
+}
+
+/// Write a JSON file into the config directory, replacing it atomically.
+pub fn save_json<T: Serialize>(
+    app_handle: &AppHandle,
+    file_name: &str,
+    value: &T,
+) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to encode '{}': {}", file_name, e))?;
+
+    if storage_mode(app_handle) == StorageMode::Memory {
+        let storage = app_handle.state::<ConfigStorage>();
+        let mut memory = storage.memory.lock().unwrap_or_else(|e| e.into_inner());
+        memory.insert(file_name.to_string(), contents);
+        return Ok(());
+    }
+
+    let path = config_file_path(app_handle, file_name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+    }
+
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, contents)
+        .map_err(|e| format!("Failed to write '{}': {}", tmp_path.display(), e))?;
+    fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Failed to replace '{}': {}", path.display(), e))?;
+    Ok(())
+}
+
+/// Delete a JSON file from the config directory. Returns whether anything was removed.
+pub fn remove_json(app_handle: &AppHandle, file_name: &str) -> Result<bool, String> {
+    if storage_mode(app_handle) == StorageMode::Memory {
+        let storage = app_handle.state::<ConfigStorage>();
+        let mut memory = storage.memory.lock().unwrap_or_else(|e| e.into_inner());
+        return Ok(memory.remove(file_name).is_some());
+    }
+
+    let path = config_file_path(app_handle, file_name)?;
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(format!("Failed to remove '{}': {}", path.display(), e)),
+    }
+}
+
+fn is_writable(dir: &Path) -> bool {
+    if fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(WRITE_PROBE_FILE);
+    let written = fs::write(&probe, b"ok").is_ok();
+    let _ = fs::remove_file(&probe);
+    written
+}
+
+/// Verifies the config and log directories are writable and picks where settings are
+/// stored: the config dir, a fallback dir, or memory only (nothing persists).
+pub fn ensure_config_writable(app_handle: &AppHandle) -> ConfigWritableStatus {
+    let config_dir = app_handle.path().app_config_dir().ok();
+    let log_dir = app_handle.path().app_log_dir().ok();
+    let config_writable = config_dir.as_deref().is_some_and(is_writable);
+    let log_writable = log_dir.as_deref().is_some_and(is_writable);
+
+    let fallback_dirs = [
+        app_handle.path().app_local_data_dir().ok(),
+        Some(std::env::temp_dir().join("indextts-hub-config")),
+    ];
+    let mode = if config_writable {
+        StorageMode::Primary
+    } else {
+        fallback_dirs
+            .into_iter()
+            .flatten()
+            .find(|dir| Some(dir) != config_dir.as_ref() && is_writable(dir))
+            .map(StorageMode::Fallback)
+            .unwrap_or(StorageMode::Memory)
+    };
+
+    if let Some(storage) = app_handle.try_state::<ConfigStorage>() {
+        storage.set_mode(mode.clone());
+    }
+
+    let display = |dir: &Option<PathBuf>| dir.as_ref().map(|d| d.display().to_string());
+    let (mode_name, active_dir, mut warning) = match &mode {
+        StorageMode::Primary => ("primary", display(&config_dir), None),
+        StorageMode::Fallback(dir) => (
+            "fallback",
+            Some(dir.display().to_string()),
+            Some(format!(
+                "The config directory is not writable; settings are saved to {} instead.",
+                dir.display()
+            )),
+        ),
+        StorageMode::Memory => (
+            "memory",
+            None,
+            Some(
+                "No writable config directory was found; settings will not persist after the app closes."
+                    .to_string(),
+            ),
+        ),
+    };
+    if !log_writable {
+        let log_warning = "The log directory is not writable; log files will not be saved.";
+        warning = Some(match warning {
+            Some(existing) => format!("{} {}", existing, log_warning),
+            None => log_warning.to_string(),
+        });
+    }
+
+    ConfigWritableStatus {
+        config_dir: display(&config_dir),
+        config_writable,
+        log_dir: display(&log_dir),
+        log_writable,
+        mode: mode_name.to_string(),
+        active_dir,
+        warning,
+    }
+}
+
+#[tauri::command]
+pub fn check_config_writable(app_handle: AppHandle) -> Result<ConfigWritableStatus, String> {
+    Ok(ensure_config_writable(&app_handle))
+}
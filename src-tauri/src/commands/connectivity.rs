@@ -0,0 +1,180 @@
+// src-tauri/src/commands/connectivity.rs
+
+use serde::Serialize;
+use std::env;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+
+const PROBE_PORT: u16 = 443;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+// DNS has no timeout of its own, so each probe as a whole is capped.
+const PROBE_DEADLINE: Duration = Duration::from_secs(12);
+// Addresses tried per host; enough to get past a dead IPv6 route.
+const MAX_ADDRESSES: usize = 2;
+
+// Hosts the deploy talks to, with what they are used for.
+const REQUIRED_HOSTS: [(&str, &str); 10] = [
+    ("github.com", "Repository clone and updates"),
+    ("codeload.github.com", "Repository source archive"),
+    ("github-cloud.githubusercontent.com", "Git LFS files"),
+    ("huggingface.co", "Model download (Hugging Face)"),
+    ("hf-mirror.com", "Model download (Hugging Face mirror)"),
+    ("www.modelscope.cn", "Model download (ModelScope)"),
+    ("pypi.org", "Python package index"),
+    ("files.pythonhosted.org", "Python package files"),
+    (
+        "pypi.tuna.tsinghua.edu.cn",
+        "Python packages (Tsinghua mirror)",
+    ),
+    ("download.pytorch.org", "PyTorch wheels"),
+];
+
+// A blocked host paired with an endpoint that can stand in for it.
+const ALTERNATIVES: [(&str, &str); 4] = [
+    ("huggingface.co", "hf-mirror.com"),
+    ("huggingface.co", "www.modelscope.cn"),
+    ("pypi.org", "pypi.tuna.tsinghua.edu.cn"),
+    ("files.pythonhosted.org", "pypi.tuna.tsinghua.edu.cn"),
+];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HostReachability {
+    Reachable,
+    DnsFailed,
+    ConnectionRefused,
+    Timeout,
+    Error,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HostProbe {
+    pub host: String,
+    pub purpose: String,
+    pub status: HostReachability,
+    pub latency_ms: Option<u64>,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConnectivityReport {
+    pub hosts: Vec<HostProbe>,
+    // Proxy from the environment; the probes connect directly and don't use it.
+    pub proxy: Option<String>,
+    pub summary: Vec<String>,
+}
+
+fn probe_host_blocking(host: &str) -> (HostReachability, Option<u64>, Option<String>) {
+    let started = Instant::now();
+    let addresses: Vec<SocketAddr> = match (host, PROBE_PORT).to_socket_addrs() {
+        Ok(addresses) => addresses.take(MAX_ADDRESSES).collect(),
+        Err(e) => return (HostReachability::DnsFailed, None, Some(e.to_string())),
+    };
+    if addresses.is_empty() {
+        return (
+            HostReachability::DnsFailed,
+            None,
+            Some("No addresses returned.".to_string()),
+        );
+    }
+
+    let mut last_error = None;
+    for address in &addresses {
+        match TcpStream::connect_timeout(address, CONNECT_TIMEOUT) {
+            Ok(_) => {
+                let latency = started.elapsed().as_millis() as u64;
+                return (HostReachability::Reachable, Some(latency), None);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    let error = last_error.expect("at least one address was tried");
+    let status = match error.kind() {
+        ErrorKind::ConnectionRefused => HostReachability::ConnectionRefused,
+        ErrorKind::TimedOut | ErrorKind::WouldBlock => HostReachability::Timeout,
+        _ => HostReachability::Error,
+    };
+    (status, None, Some(error.to_string()))
+}
+
+fn summarize(hosts: &[HostProbe]) -> Vec<String> {
+    let reachable = |host: &str| {
+        hosts
+            .iter()
+            .any(|probe| probe.host == host && probe.status == HostReachability::Reachable)
+    };
+    if !hosts.iter().any(|probe| reachable(&probe.host)) {
+        return vec![
+            "None of the required hosts are reachable; check the network connection, proxy or firewall."
+                .to_string(),
+        ];
+    }
+
+    let mut summary = Vec::new();
+    for probe in hosts
+        .iter()
+        .filter(|probe| probe.status != HostReachability::Reachable)
+    {
+        let alternatives: Vec<&str> = ALTERNATIVES
+            .iter()
+            .filter(|(blocked, alternative)| *blocked == probe.host && reachable(alternative))
+            .map(|(_, alternative)| *alternative)
+            .collect();
+        summary.push(if alternatives.is_empty() {
+            format!("{} is blocked ({}).", probe.host, probe.purpose)
+        } else {
+            format!(
+                "{} is blocked but {} is reachable.",
+                probe.host,
+                alternatives.join(" and ")
+            )
+        });
+    }
+    summary
+}
+
+/// Tests direct TCP reachability of every host the deploy needs, concurrently and with
+/// a per-host deadline, so a firewall blocking one endpoint can be told apart from a
+/// dead network.
+#[tauri::command]
+pub async fn diagnose_connectivity() -> Result<ConnectivityReport, String> {
+    let handles: Vec<_> = REQUIRED_HOSTS
+        .iter()
+        .map(|(host, _)| tauri::async_runtime::spawn_blocking(move || probe_host_blocking(host)))
+        .collect();
+
+    let mut hosts = Vec::with_capacity(handles.len());
+    for ((host, purpose), handle) in REQUIRED_HOSTS.iter().zip(handles) {
+        let (status, latency_ms, detail) = match timeout(PROBE_DEADLINE, handle).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => (HostReachability::Error, None, Some(e.to_string())),
+            Err(_) => (
+                HostReachability::Timeout,
+                None,
+                Some(format!(
+                    "No answer within {} seconds.",
+                    PROBE_DEADLINE.as_secs()
+                )),
+            ),
+        };
+        hosts.push(HostProbe {
+            host: host.to_string(),
+            purpose: purpose.to_string(),
+            status,
+            latency_ms,
+            detail,
+        });
+    }
+
+    let proxy = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty());
+    Ok(ConnectivityReport {
+        summary: summarize(&hosts),
+        hosts,
+        proxy,
+    })
+}
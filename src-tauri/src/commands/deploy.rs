@@ -0,0 +1,524 @@
+// src-tauri/src/commands/deploy.rs
+
+use super::cloud_folder::detect_cloud_folder;
+use super::command_utils::clean_path_arg;
+use super::config_store::{load_json, save_json};
+use super::deploy_error::DeployError;
+use super::deployments::{
+    detect_venv, read_head_commit, read_project_version, read_python_version, unix_timestamp,
+};
+use super::fd_limits::check_fd_limit;
+use super::index_tts::{
+    clone_index_tts_repo, download_model_with_failover, emit_core_deploy_log, init_git_lfs,
+    model_has_core_files, repo_has_core_files, setup_index_tts_env, ModelSource,
+};
+use super::jobs::current_job_id;
+use super::notifications::{notify_deploy_finished, DeployFinishedEvent};
+use super::python_env::list_installed_packages;
+use super::resource_monitor::start_resource_monitor;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+pub const DEPLOY_PROGRESS_FILE: &str = "deploy_progress.json";
+const DEPLOY_STAGE_EVENT: &str = "deploy-stage";
+
+/// Deploy stages in execution order; names match the `step` used in deploy logs.
+pub const DEPLOY_STAGES: [&str; 4] = ["clone_repo", "init_lfs", "setup_env", "download_model"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DeployProgress {
+    pub target_dir: String,
+    pub network_environment: String,
+    pub model_save_path: Option<String>,
+    // Source pinned for the download; `None` picks one by network environment.
+    pub model_source: Option<String>,
+    pub download_mode: Option<String>,
+    pub model_source_used: Option<ModelSource>,
+    // Resolved absolute model directory, recorded once the download succeeds.
+    pub model_dir: Option<String>,
+    pub completed_stages: Vec<String>,
+    pub failed_stage: Option<String>,
+    pub last_error: Option<String>,
+    pub next_stage: Option<String>,
+    pub updated_at: u64,
+    // Wall-clock time of each stage's successful run, in milliseconds.
+    pub stage_durations_ms: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DeployStageEvent {
+    pub target_dir: String,
+    pub stage: String,
+    pub status: String,
+    pub elapsed_ms: Option<u64>,
+    pub job_id: Option<String>,
+}
+
+fn load_all_progress(app_handle: &AppHandle) -> Vec<DeployProgress> {
+    load_json(app_handle, DEPLOY_PROGRESS_FILE)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn save_progress(app_handle: &AppHandle, progress: &DeployProgress) -> Result<(), String> {
+    let mut all = load_all_progress(app_handle);
+    all.retain(|existing| existing.target_dir != progress.target_dir);
+    all.push(progress.clone());
+    save_json(app_handle, DEPLOY_PROGRESS_FILE, &all)
+}
+
+fn resolve_model_dir(target_dir: &str, model_save_path: Option<&str>) -> PathBuf {
+    let local_dir = Path::new(model_save_path.unwrap_or("checkpoints"));
+    if local_dir.is_absolute() {
+        local_dir.to_path_buf()
+    } else {
+        Path::new(target_dir).join(local_dir)
+    }
+}
+
+/// Filesystem evidence that a stage finished. `None` means the stage leaves no
+/// reliable artifact and only the persisted record counts.
+fn stage_artifacts_present(progress: &DeployProgress, stage: &str) -> Option<bool> {
+    let repo_path = Path::new(&progress.target_dir);
+    match stage {
+        "clone_repo" => Some(repo_path.join(".git").is_dir() && repo_has_core_files(repo_path)),
+        "setup_env" => Some(repo_path.join(".venv").join("pyvenv.cfg").is_file()),
+        // The directory the download reported wins: in "cache_only" mode the model lives
+        // in the HuggingFace cache, not under `model_save_path`.
+        "download_model" => {
+            let model_dir = progress
+                .model_dir
+                .as_deref()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| {
+                    resolve_model_dir(&progress.target_dir, progress.model_save_path.as_deref())
+                });
+            Some(model_has_core_files(&model_dir))
+        }
+        _ => None,
+    }
+}
+
+/// Reconciles the persisted record with what is actually on disk and fills in `next_stage`.
+fn reconcile(mut progress: DeployProgress) -> DeployProgress {
+    let recorded = progress.completed_stages.clone();
+    progress.completed_stages = DEPLOY_STAGES
+        .iter()
+        .filter(|stage| match stage_artifacts_present(&progress, stage) {
+            Some(present) => present,
+            None => recorded.iter().any(|done| done == *stage),
+        })
+        .map(|stage| stage.to_string())
+        .collect();
+
+    progress.next_stage = DEPLOY_STAGES
+        .iter()
+        .find(|stage| !progress.completed_stages.iter().any(|done| done == *stage))
+        .map(|stage| stage.to_string());
+    progress
+}
+
+pub fn load_progress(app_handle: &AppHandle, target_dir: &str) -> DeployProgress {
+    let stored = load_all_progress(app_handle)
+        .into_iter()
+        .find(|progress| progress.target_dir == target_dir)
+        .unwrap_or_else(|| DeployProgress {
+            target_dir: target_dir.to_string(),
+            ..Default::default()
+        });
+    reconcile(stored)
+}
+
+fn emit_stage(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    stage: &str,
+    status: &str,
+    elapsed_ms: Option<u64>,
+) {
+    let _ = app_handle.emit(
+        DEPLOY_STAGE_EVENT,
+        DeployStageEvent {
+            target_dir: target_dir.to_string(),
+            stage: stage.to_string(),
+            status: status.to_string(),
+            elapsed_ms,
+            job_id: current_job_id(),
+        },
+    );
+}
+
+async fn run_stage(
+    app_handle: &AppHandle,
+    progress: &mut DeployProgress,
+    stage: &str,
+) -> Result<(), DeployError> {
+    let target_dir = progress.target_dir.clone();
+    match stage {
+        "clone_repo" => {
+            clone_index_tts_repo(app_handle.clone(), target_dir, None, None, None, None)
+                .await
+                .map(|_| ())
+                .map_err(|e| DeployError::new(stage, e))
+        }
+        "init_lfs" => init_git_lfs(app_handle.clone(), target_dir)
+            .await
+            .map(|_| ())
+            .map_err(|e| DeployError::new(stage, e)),
+        "setup_env" => setup_index_tts_env(
+            app_handle.clone(),
+            target_dir,
+            progress.network_environment.clone(),
+            None,
+        )
+        .await
+        .map(|_| ()),
+        "download_model" => download_model_with_failover(
+            app_handle,
+            &target_dir,
+            &progress.network_environment,
+            progress.model_save_path.clone(),
+            progress.model_source.clone(),
+            progress.download_mode.clone(),
+        )
+        .await
+        .map(|outcome| {
+            progress.model_source_used = Some(outcome.source);
+            progress.model_dir = Some(outcome.model_dir);
+        })
+        .map_err(|e| DeployError::new(stage, e)),
+        other => Err(DeployError::new(
+            other,
+            format!("Unknown deploy stage '{}'.", other),
+        )),
+    }
+}
+
+/// Runs every stage that is not complete yet, persisting progress after each one.
+async fn run_pending_stages(
+    app_handle: &AppHandle,
+    progress: DeployProgress,
+) -> Result<DeployProgress, DeployError> {
+    let mut progress = reconcile(progress);
+    progress.failed_stage = None;
+    progress.last_error = None;
+
+    if let Some(cloud) = detect_cloud_folder(Path::new(&progress.target_dir)) {
+        emit_core_deploy_log(
+            app_handle,
+            "preflight",
+            "stderr",
+            &format!(
+                "警告: 目标目录位于 {} 同步文件夹 ({}) 中。同步会在安装环境时锁定文件、上传大量模型数据，并可能损坏虚拟环境，建议改用本地目录。",
+                cloud.provider, cloud.root
+            ),
+        );
+    }
+    let fd_limit = check_fd_limit(None, true);
+    if fd_limit.raised {
+        emit_core_deploy_log(
+            app_handle,
+            "preflight",
+            "stdout",
+            &format!(
+                "已将打开文件数上限提高到 {}。",
+                fd_limit.soft.unwrap_or_default()
+            ),
+        );
+    }
+    if let Some(warning) = fd_limit.warning {
+        emit_core_deploy_log(
+            app_handle,
+            "preflight",
+            "stderr",
+            &format!("警告: {}", warning),
+        );
+    }
+
+    for stage in DEPLOY_STAGES {
+        if progress.completed_stages.iter().any(|done| done == stage) {
+            emit_stage(app_handle, &progress.target_dir, stage, "skipped", None);
+            continue;
+        }
+
+        emit_stage(app_handle, &progress.target_dir, stage, "started", None);
+        let started = Instant::now();
+        let result = run_stage(app_handle, &mut progress, stage).await;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        progress.updated_at = unix_timestamp();
+
+        match result {
+            Ok(()) => {
+                progress.completed_stages.push(stage.to_string());
+                progress
+                    .stage_durations_ms
+                    .insert(stage.to_string(), elapsed_ms);
+                emit_stage(
+                    app_handle,
+                    &progress.target_dir,
+                    stage,
+                    "completed",
+                    Some(elapsed_ms),
+                );
+                let _ = save_progress(app_handle, &progress);
+            }
+            Err(error) => {
+                progress.failed_stage = Some(stage.to_string());
+                progress.last_error = Some(error.message.clone());
+                emit_stage(
+                    app_handle,
+                    &progress.target_dir,
+                    stage,
+                    "failed",
+                    Some(elapsed_ms),
+                );
+                let _ = save_progress(app_handle, &progress);
+                return Err(error);
+            }
+        }
+    }
+
+    let progress = reconcile(progress);
+    let _ = save_progress(app_handle, &progress);
+    Ok(progress)
+}
+
+async fn run_and_notify(
+    app_handle: &AppHandle,
+    progress: DeployProgress,
+) -> Result<DeployProgress, DeployError> {
+    let target_dir = progress.target_dir.clone();
+    let started = Instant::now();
+    let monitor = start_resource_monitor(app_handle, &target_dir);
+    let result = run_pending_stages(app_handle, progress).await;
+    drop(monitor);
+    notify_deploy_finished(
+        app_handle,
+        DeployFinishedEvent {
+            target_dir,
+            success: result.is_ok(),
+            duration_secs: started.elapsed().as_secs_f64(),
+            failed_stage: result.as_ref().err().map(|error| error.step.clone()),
+            error: result.as_ref().err().map(|error| error.message.clone()),
+        },
+    );
+    result
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn deploy_index_tts(
+    app_handle: AppHandle,
+    target_dir: String,
+    network_environment: String,
+    model_save_path: Option<String>,
+) -> Result<DeployProgress, DeployError> {
+    let target_dir = clean_path_arg(&target_dir);
+    let mut progress = load_progress(&app_handle, &target_dir);
+    progress.network_environment = network_environment;
+    progress.model_save_path = model_save_path;
+    run_and_notify(&app_handle, progress).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_deploy_progress(
+    app_handle: AppHandle,
+    target_dir: String,
+) -> Result<DeployProgress, String> {
+    Ok(load_progress(&app_handle, &target_dir))
+}
+
+/// Continues a previously started deploy from its first incomplete stage, reusing the
+/// network environment and model path it was started with.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn resume_deploy(
+    app_handle: AppHandle,
+    target_dir: String,
+) -> Result<DeployProgress, DeployError> {
+    let progress = load_progress(&app_handle, &target_dir);
+    if progress.updated_at == 0 {
+        return Err(DeployError::new(
+            "resume",
+            format!("No previous deploy was recorded for '{}'.", target_dir),
+        ));
+    }
+    run_and_notify(&app_handle, progress).await
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StageTiming {
+    pub stage: String,
+    pub elapsed_ms: u64,
+    pub share_percent: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DeployTimingSummary {
+    pub target_dir: String,
+    pub stages: Vec<StageTiming>,
+    pub total_ms: u64,
+    pub slowest_stage: Option<String>,
+    // Every stage has a recorded duration; stages skipped as already done have none.
+    pub complete: bool,
+}
+
+/// How long each deploy stage took for `target_dir`, to tell a network-bound deploy
+/// (clone, model download) from a CPU/disk-bound one (environment setup).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_deploy_timing_summary(
+    app_handle: AppHandle,
+    target_dir: String,
+) -> Result<DeployTimingSummary, String> {
+    let progress = load_progress(&app_handle, &target_dir);
+    let total_ms: u64 = progress.stage_durations_ms.values().sum();
+    let stages: Vec<StageTiming> = DEPLOY_STAGES
+        .iter()
+        .filter_map(|stage| {
+            let elapsed_ms = *progress.stage_durations_ms.get(*stage)?;
+            Some(StageTiming {
+                stage: stage.to_string(),
+                elapsed_ms,
+                share_percent: elapsed_ms as f64 * 100.0 / total_ms.max(1) as f64,
+            })
+        })
+        .collect();
+    Ok(DeployTimingSummary {
+        target_dir,
+        complete: stages.len() == DEPLOY_STAGES.len(),
+        slowest_stage: stages
+            .iter()
+            .max_by_key(|timing| timing.elapsed_ms)
+            .map(|timing| timing.stage.clone()),
+        stages,
+        total_ms,
+    })
+}
+
+/// Everything `deploy_headless` needs; mirrors the arguments of the interactive flow.
+#[derive(Debug, Deserialize, Clone)]
+pub struct HeadlessDeployConfig {
+    pub target_dir: String,
+    pub network_environment: String,
+    #[serde(default)]
+    pub model_save_path: Option<String>,
+    #[serde(default)]
+    pub model_source: Option<String>,
+    #[serde(default)]
+    pub download_mode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StageReport {
+    pub stage: String,
+    // "completed", "skipped" (already done before this run), "failed" or "pending".
+    pub status: String,
+    pub elapsed_ms: Option<u64>,
+}
+
+/// Outcome of a whole deploy run, for scripts and integration tests.
+#[derive(Debug, Serialize, Clone)]
+pub struct DeployReport {
+    pub success: bool,
+    pub target_dir: String,
+    pub venv_dir: Option<String>,
+    pub model_dir: Option<String>,
+    pub model_source: Option<ModelSource>,
+    pub commit: Option<String>,
+    pub project_version: Option<String>,
+    pub python_version: Option<String>,
+    pub torch_version: Option<String>,
+    pub stages: Vec<StageReport>,
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub total_ms: u64,
+    pub error: Option<DeployError>,
+    // The persisted deploy record after the run, as `get_deploy_progress` returns it.
+    pub progress: DeployProgress,
+}
+
+/// Runs the whole deploy pipeline without UI interaction and returns one report with
+/// the resolved paths, versions, per-stage durations and the final state. Stages that
+/// are already complete are skipped, so re-running after a failure resumes. A failed
+/// stage is reported in `error` rather than as an `Err`.
+#[tauri::command]
+pub async fn deploy_headless(
+    app_handle: AppHandle,
+    config: HeadlessDeployConfig,
+) -> Result<DeployReport, String> {
+    let target_dir = clean_path_arg(&config.target_dir);
+    if target_dir.is_empty() {
+        return Err("target_dir must not be empty.".to_string());
+    }
+    let mut progress = load_progress(&app_handle, &target_dir);
+    progress.network_environment = config.network_environment;
+    progress.model_save_path = config.model_save_path;
+    progress.model_source = config.model_source;
+    progress.download_mode = config.download_mode;
+    // Checked against the requested model path, as the run itself will.
+    let already_done = reconcile(progress.clone()).completed_stages;
+
+    let started_at = unix_timestamp();
+    let started = Instant::now();
+    let result = run_and_notify(&app_handle, progress).await;
+    let total_ms = started.elapsed().as_millis() as u64;
+    let (progress, error) = match result {
+        Ok(progress) => (progress, None),
+        Err(error) => (load_progress(&app_handle, &target_dir), Some(error)),
+    };
+
+    let stages = DEPLOY_STAGES
+        .iter()
+        .map(|stage| {
+            let done = |list: &[String]| list.iter().any(|done| done == stage);
+            let status = if done(&already_done) {
+                "skipped"
+            } else if done(&progress.completed_stages) {
+                "completed"
+            } else if progress.failed_stage.as_deref() == Some(*stage) {
+                "failed"
+            } else {
+                "pending"
+            };
+            StageReport {
+                stage: stage.to_string(),
+                status: status.to_string(),
+                elapsed_ms: (status == "completed")
+                    .then(|| progress.stage_durations_ms.get(*stage).copied())
+                    .flatten(),
+            }
+        })
+        .collect();
+
+    let repo_path = Path::new(&target_dir);
+    let venv_dir = detect_venv(repo_path);
+    let torch_version = match venv_dir {
+        Some(_) => list_installed_packages(&target_dir)
+            .await
+            .ok()
+            .and_then(|packages| packages.into_iter().find(|package| package.name == "torch"))
+            .map(|package| package.version),
+        None => None,
+    };
+
+    Ok(DeployReport {
+        success: error.is_none(),
+        target_dir: target_dir.clone(),
+        python_version: venv_dir.as_deref().and_then(read_python_version),
+        venv_dir: venv_dir.map(|dir| dir.display().to_string()),
+        model_dir: progress.model_dir.clone(),
+        model_source: progress.model_source_used,
+        commit: read_head_commit(repo_path).await,
+        project_version: read_project_version(repo_path),
+        torch_version,
+        stages,
+        started_at,
+        finished_at: unix_timestamp(),
+        total_ms,
+        error,
+        progress,
+    })
+}
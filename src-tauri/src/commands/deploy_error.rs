@@ -0,0 +1,116 @@
+// src-tauri/src/commands/deploy_error.rs
+
+use super::uv_conflict::{parse_uv_conflict_output, UvConflictReport};
+use serde::Serialize;
+use std::env;
+
+/// Structured error returned by deploy steps so the UI can show a targeted fix
+/// while still keeping the raw log for deeper debugging.
+#[derive(Debug, Serialize, Clone)]
+pub struct DeployError {
+    pub step: String,
+    pub kind: String,
+    pub message: String,
+    pub missing_prerequisite: Option<String>,
+    pub remediation: Option<String>,
+    // Command the UI can run to fix the failure in one step, e.g. "download_model".
+    pub recoverable_action: Option<String>,
+    // Parsed uv resolver conflict when `kind` is "resolution_conflict".
+    pub conflict: Option<UvConflictReport>,
+    pub log: String,
+}
+
+impl DeployError {
+    pub fn new(step: &str, message: impl Into<String>) -> Self {
+        let message = message.into();
+        DeployError {
+            step: step.to_string(),
+            kind: "command_failed".to_string(),
+            log: message.clone(),
+            message,
+            missing_prerequisite: None,
+            remediation: None,
+            recoverable_action: None,
+            conflict: None,
+        }
+    }
+}
+
+impl From<String> for DeployError {
+    fn from(message: String) -> Self {
+        DeployError::new("unknown", message)
+    }
+}
+
+// (signature in build output, missing prerequisite)
+const NATIVE_BUILD_SIGNATURES: [(&str, &str); 12] = [
+    (
+        "Microsoft Visual C++ 14.0 or greater is required",
+        "c_compiler",
+    ),
+    ("error: command 'cl.exe' failed", "c_compiler"),
+    ("xcrun: error: invalid active developer path", "c_compiler"),
+    ("error: command 'gcc' failed", "c_compiler"),
+    ("error: command 'cc' failed", "c_compiler"),
+    ("error: command 'g++' failed", "c_compiler"),
+    ("unable to execute 'gcc'", "c_compiler"),
+    ("Python.h: No such file or directory", "python_headers"),
+    ("CUDA_HOME environment variable is not set", "cuda_toolkit"),
+    ("cuda_runtime.h: No such file or directory", "cuda_toolkit"),
+    (
+        "cuda_runtime_api.h: No such file or directory",
+        "cuda_toolkit",
+    ),
+    ("No CUDA runtime is found", "cuda_toolkit"),
+];
+
+fn remediation_for(prerequisite: &str) -> String {
+    let os = env::consts::OS;
+    match (prerequisite, os) {
+        ("c_compiler", "windows") => "Install \"Microsoft C++ Build Tools\" (Desktop development with C++) from https://visualstudio.microsoft.com/visual-cpp-build-tools/ and retry.".to_string(),
+        ("c_compiler", "macos") => "Install the Xcode Command Line Tools with `xcode-select --install` and retry.".to_string(),
+        ("c_compiler", _) => "Install a C/C++ toolchain, e.g. `sudo apt-get install build-essential` (Debian/Ubuntu) or `sudo dnf groupinstall \"Development Tools\"` (Fedora), and retry.".to_string(),
+        ("python_headers", "windows") | ("python_headers", "macos") => "Reinstall Python from python.org so its development headers are included, then retry.".to_string(),
+        ("python_headers", _) => "Install the Python development headers, e.g. `sudo apt-get install python3-dev`, and retry.".to_string(),
+        ("cuda_toolkit", _) => "Install the CUDA Toolkit matching your torch build (https://developer.nvidia.com/cuda-downloads) and make sure CUDA_HOME points at it, or install without the native CUDA extras.".to_string(),
+        _ => "Check the log for the failing compiler command.".to_string(),
+    }
+}
+
+/// Inspects the output of a failed `uv sync`/`uv lock` for a dependency resolution conflict.
+pub fn classify_resolution_conflict(step: &str, log: &str) -> Option<DeployError> {
+    let conflict = parse_uv_conflict_output(log)?;
+    let mut error = DeployError::new(
+        step,
+        format!("Dependencies could not be resolved: {}", conflict.summary),
+    );
+    error.kind = "resolution_conflict".to_string();
+    error.log = log.to_string();
+    error.remediation = Some(
+        "Adjust or remove the constraint named above (or pick a different torch build), then run the environment setup again."
+            .to_string(),
+    );
+    error.conflict = Some(conflict);
+    Some(error)
+}
+
+/// Inspects the output of a failed build step for native-extension compile failures.
+pub fn classify_native_build_failure(step: &str, log: &str) -> Option<DeployError> {
+    let (signature, prerequisite) = NATIVE_BUILD_SIGNATURES
+        .iter()
+        .find(|(signature, _)| log.contains(signature))?;
+
+    Some(DeployError {
+        step: step.to_string(),
+        kind: "native_build".to_string(),
+        message: format!(
+            "Building a native extension failed because a build prerequisite is missing ({}): {}",
+            prerequisite, signature
+        ),
+        missing_prerequisite: Some(prerequisite.to_string()),
+        remediation: Some(remediation_for(prerequisite)),
+        recoverable_action: None,
+        conflict: None,
+        log: log.to_string(),
+    })
+}
@@ -0,0 +1,69 @@
+// src-tauri/src/commands/deploy_log.rs
+
+use super::index_tts::CoreDeployLogEvent;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::State;
+
+const DEPLOY_LOG_BUFFER_LIMIT: usize = 2000;
+const DEFAULT_RECENT_LOG_LIMIT: usize = 500;
+
+/// Recent `core-deploy-log` lines, so a UI that subscribes late can catch up.
+#[derive(Default)]
+pub struct DeployLogBuffer(Mutex<DeployLogState>);
+
+#[derive(Default)]
+struct DeployLogState {
+    next_seq: u64,
+    lines: VecDeque<CoreDeployLogEvent>,
+}
+
+impl DeployLogBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a line and returns it tagged with its sequence number.
+    pub fn push(&self, mut event: CoreDeployLogEvent) -> CoreDeployLogEvent {
+        let mut state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        state.next_seq += 1;
+        event.seq = state.next_seq;
+        if state.lines.len() >= DEPLOY_LOG_BUFFER_LIMIT {
+            state.lines.pop_front();
+        }
+        state.lines.push_back(event.clone());
+        event
+    }
+
+    pub fn clear(&self) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .lines
+            .clear();
+    }
+
+    pub fn recent(&self, step: Option<&str>, limit: usize) -> Vec<CoreDeployLogEvent> {
+        let state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let mut lines: Vec<CoreDeployLogEvent> = state
+            .lines
+            .iter()
+            .rev()
+            .filter(|event| step.is_none_or(|step| event.step == step))
+            .take(limit)
+            .cloned()
+            .collect();
+        lines.reverse();
+        lines
+    }
+}
+
+/// Returns the most recent deploy log lines (oldest first), optionally for a single step.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_recent_deploy_logs(
+    step: Option<String>,
+    limit: Option<usize>,
+    buffer: State<'_, DeployLogBuffer>,
+) -> Result<Vec<CoreDeployLogEvent>, String> {
+    Ok(buffer.recent(step.as_deref(), limit.unwrap_or(DEFAULT_RECENT_LOG_LIMIT)))
+}
@@ -0,0 +1,422 @@
+// src-tauri/src/commands/deployments.rs
+
+use super::command_utils::{clean_path_arg, new_command};
+use super::config_store::{load_json, save_json};
+use super::engine::{persist_engine_config, EngineManager};
+use super::index_tts::{model_has_core_files, repo_has_core_files, MODEL_CORE_FILES};
+use super::python_env::list_installed_packages;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, State};
+
+pub const DEPLOYMENTS_FILE: &str = "deployments.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Deployment {
+    pub target_dir: String,
+    pub venv_dir: Option<String>,
+    pub model_dir: Option<String>,
+    pub commit: Option<String>,
+    pub version: Option<String>,
+    pub imported: bool,
+    pub registered_at: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub deployment: Deployment,
+    pub found: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+pub fn load_deployments(app_handle: &AppHandle) -> Result<Vec<Deployment>, String> {
+    Ok(load_json(app_handle, DEPLOYMENTS_FILE)?.unwrap_or_default())
+}
+
+/// Insert or replace the registry entry for `deployment.target_dir`.
+pub fn register_deployment(app_handle: &AppHandle, deployment: Deployment) -> Result<(), String> {
+    let mut deployments = load_deployments(app_handle)?;
+    deployments.retain(|existing| existing.target_dir != deployment.target_dir);
+    deployments.push(deployment);
+    save_json(app_handle, DEPLOYMENTS_FILE, &deployments)
+}
+
+/// Updates the model directory of a registered deployment; unregistered ones are left
+/// alone.
+pub fn set_deployment_model_dir(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    model_dir: &str,
+) -> Result<(), String> {
+    let mut deployments = load_deployments(app_handle)?;
+    let Some(deployment) = deployments
+        .iter_mut()
+        .find(|deployment| deployment.target_dir == target_dir)
+    else {
+        return Ok(());
+    };
+    deployment.model_dir = Some(model_dir.to_string());
+    save_json(app_handle, DEPLOYMENTS_FILE, &deployments)
+}
+
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn detect_venv(repo_path: &Path) -> Option<PathBuf> {
+    [".venv", "venv"]
+        .iter()
+        .map(|name| repo_path.join(name))
+        .find(|candidate| candidate.join("pyvenv.cfg").is_file())
+}
+
+fn detect_model_dir(repo_path: &Path) -> Option<PathBuf> {
+    let checkpoints = repo_path.join("checkpoints");
+    if model_has_core_files(&checkpoints) {
+        return Some(checkpoints);
+    }
+
+    // Some downloaders nest the files inside a folder named after the model repo.
+    let nested = checkpoints.join("IndexTTS-2");
+    if model_has_core_files(&nested) {
+        return Some(nested);
+    }
+
+    None
+}
+
+pub async fn read_head_commit(repo_path: &Path) -> Option<String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .await
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!hash.is_empty()).then_some(hash)
+}
+
+pub fn read_project_version(repo_path: &Path) -> Option<String> {
+    let pyproject = fs::read_to_string(repo_path.join("pyproject.toml")).ok()?;
+    pyproject
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| line.starts_with("version") && line.contains('='))
+        .and_then(|line| line.split('=').nth(1))
+        .map(|value| value.trim().trim_matches('"').to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct IsolationOverlap {
+    pub kind: String,
+    pub other_target_dir: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IsolationReport {
+    pub target_dir: String,
+    pub venv_dir: Option<String>,
+    pub venv_inside_deployment: bool,
+    pub model_dir: Option<String>,
+    pub overlaps: Vec<IsolationOverlap>,
+    pub isolated: bool,
+}
+
+fn resolve_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn paths_overlap(a: &Path, b: &Path) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+#[tauri::command]
+pub fn list_deployments(app_handle: AppHandle) -> Result<Vec<Deployment>, String> {
+    load_deployments(&app_handle)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_existing_deployment(
+    app_handle: AppHandle,
+    path: String,
+    engine: State<'_, EngineManager>,
+) -> Result<ImportReport, String> {
+    let repo_path = PathBuf::from(clean_path_arg(&path));
+    if !repo_path.is_dir() {
+        return Err(format!("Directory does not exist: {}", repo_path.display()));
+    }
+    if !repo_has_core_files(&repo_path) {
+        return Err(format!(
+            "'{}' does not look like an index-tts checkout (pyproject.toml or webui.py is missing).",
+            repo_path.display()
+        ));
+    }
+
+    let mut found = vec!["repository".to_string()];
+    let mut missing = Vec::new();
+
+    let commit = read_head_commit(&repo_path).await;
+    match commit {
+        Some(_) => found.push("git".to_string()),
+        None => missing.push("git".to_string()),
+    }
+
+    let venv_dir = detect_venv(&repo_path);
+    match venv_dir {
+        Some(_) => found.push("venv".to_string()),
+        None => missing.push("venv".to_string()),
+    }
+
+    let model_dir = detect_model_dir(&repo_path);
+    match model_dir {
+        Some(_) => found.push("model".to_string()),
+        None => missing.push("model".to_string()),
+    }
+
+    let deployment = Deployment {
+        target_dir: repo_path.to_string_lossy().to_string(),
+        venv_dir: venv_dir.map(|p| p.to_string_lossy().to_string()),
+        model_dir: model_dir.map(|p| p.to_string_lossy().to_string()),
+        commit,
+        version: read_project_version(&repo_path),
+        imported: true,
+        registered_at: unix_timestamp(),
+    };
+
+    register_deployment(&app_handle, deployment.clone())?;
+
+    {
+        let mut config = engine.lock();
+        config.install_dir = deployment.target_dir.clone();
+        if let Some(model_dir) = &deployment.model_dir {
+            config.model_dir = model_dir.clone();
+        }
+    }
+    persist_engine_config(&app_handle)?;
+
+    Ok(ImportReport {
+        deployment,
+        found,
+        missing,
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn check_isolation(
+    app_handle: AppHandle,
+    target_dir: String,
+) -> Result<IsolationReport, String> {
+    let repo_path = resolve_path(Path::new(target_dir.trim()));
+    let deployments = load_deployments(&app_handle)?;
+
+    let this = deployments
+        .iter()
+        .find(|deployment| resolve_path(Path::new(&deployment.target_dir)) == repo_path);
+
+    // A `.venv` that is a symlink/junction can resolve to another deployment's environment.
+    let venv_dir = detect_venv(&repo_path).map(|venv| resolve_path(&venv));
+    let venv_inside_deployment = venv_dir
+        .as_ref()
+        .is_some_and(|venv| venv.starts_with(&repo_path));
+
+    let model_dir = this
+        .and_then(|deployment| deployment.model_dir.as_ref())
+        .map(|dir| resolve_path(Path::new(dir)))
+        .or_else(|| detect_model_dir(&repo_path).map(|dir| resolve_path(&dir)));
+
+    let mut overlaps = Vec::new();
+    for other in &deployments {
+        let other_root = resolve_path(Path::new(&other.target_dir));
+        if other_root == repo_path {
+            continue;
+        }
+
+        if paths_overlap(&other_root, &repo_path) {
+            overlaps.push(IsolationOverlap {
+                kind: "nested_deployment".to_string(),
+                other_target_dir: other.target_dir.clone(),
+                path: other_root.to_string_lossy().to_string(),
+            });
+        }
+
+        let other_venv = other
+            .venv_dir
+            .as_ref()
+            .map(|dir| resolve_path(Path::new(dir)))
+            .or_else(|| detect_venv(&other_root).map(|venv| resolve_path(&venv)));
+        if let (Some(venv), Some(other_venv)) = (&venv_dir, &other_venv) {
+            if venv == other_venv {
+                overlaps.push(IsolationOverlap {
+                    kind: "shared_venv".to_string(),
+                    other_target_dir: other.target_dir.clone(),
+                    path: venv.to_string_lossy().to_string(),
+                });
+            }
+        }
+
+        let other_model = other
+            .model_dir
+            .as_ref()
+            .map(|dir| resolve_path(Path::new(dir)));
+        if let (Some(model), Some(other_model)) = (&model_dir, &other_model) {
+            if paths_overlap(model, other_model) {
+                overlaps.push(IsolationOverlap {
+                    kind: "shared_model".to_string(),
+                    other_target_dir: other.target_dir.clone(),
+                    path: model.to_string_lossy().to_string(),
+                });
+            }
+        }
+    }
+
+    let isolated = overlaps.is_empty() && (venv_dir.is_none() || venv_inside_deployment);
+
+    Ok(IsolationReport {
+        target_dir: repo_path.to_string_lossy().to_string(),
+        venv_dir: venv_dir.map(|venv| venv.to_string_lossy().to_string()),
+        venv_inside_deployment,
+        model_dir: model_dir.map(|dir| dir.to_string_lossy().to_string()),
+        overlaps,
+        isolated,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeploymentDifference {
+    pub category: String,
+    pub key: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeploymentDiff {
+    pub dir_a: String,
+    pub dir_b: String,
+    pub identical: bool,
+    pub differences: Vec<DeploymentDifference>,
+}
+
+pub fn read_python_version(venv_dir: &Path) -> Option<String> {
+    let config = fs::read_to_string(venv_dir.join("pyvenv.cfg")).ok()?;
+    let values: BTreeMap<&str, &str> = config
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+    values
+        .get("version_info")
+        .or_else(|| values.get("version"))
+        .map(|version| version.to_string())
+}
+
+/// Flattened view of a deployment: category -> key -> value.
+async fn snapshot_deployment(
+    app_handle: &AppHandle,
+    target_dir: &str,
+) -> BTreeMap<&'static str, BTreeMap<String, String>> {
+    let repo_path = Path::new(target_dir);
+    let mut snapshot: BTreeMap<&'static str, BTreeMap<String, String>> = BTreeMap::new();
+
+    let repo = snapshot.entry("repository").or_default();
+    if let Some(commit) = read_head_commit(repo_path).await {
+        repo.insert("commit".to_string(), commit);
+    }
+    if let Some(version) = read_project_version(repo_path) {
+        repo.insert("version".to_string(), version);
+    }
+
+    if let Some(python) = detect_venv(repo_path).and_then(|venv| read_python_version(&venv)) {
+        snapshot
+            .entry("python")
+            .or_default()
+            .insert("version".to_string(), python);
+    }
+
+    if let Ok(packages) = list_installed_packages(target_dir).await {
+        snapshot.entry("packages").or_default().extend(
+            packages
+                .into_iter()
+                .map(|package| (package.name.to_lowercase(), package.version)),
+        );
+    }
+
+    let registered_model_dir = load_deployments(app_handle)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|deployment| {
+            resolve_path(Path::new(&deployment.target_dir)) == resolve_path(repo_path)
+        })
+        .and_then(|deployment| deployment.model_dir)
+        .map(PathBuf::from);
+    if let Some(model_dir) = registered_model_dir.or_else(|| detect_model_dir(repo_path)) {
+        // File sizes are a cheap fingerprint that still tells apart partial or
+        // different model revisions.
+        let model = snapshot.entry("model").or_default();
+        for file in MODEL_CORE_FILES {
+            if let Ok(meta) = fs::metadata(model_dir.join(file)) {
+                model.insert(file.to_string(), format!("{} bytes", meta.len()));
+            }
+        }
+    }
+
+    snapshot
+}
+
+/// Compares two deployments and returns only what differs: commit/version, python
+/// version, installed package versions and model file fingerprints.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diff_deployments(
+    app_handle: AppHandle,
+    dir_a: String,
+    dir_b: String,
+) -> Result<DeploymentDiff, String> {
+    for dir in [&dir_a, &dir_b] {
+        if !Path::new(dir).is_dir() {
+            return Err(format!("Deployment directory does not exist: {}", dir));
+        }
+    }
+
+    let a = snapshot_deployment(&app_handle, &dir_a).await;
+    let b = snapshot_deployment(&app_handle, &dir_b).await;
+    let empty = BTreeMap::new();
+
+    let mut differences = Vec::new();
+    let categories: BTreeSet<&str> = a.keys().chain(b.keys()).copied().collect();
+    for category in categories {
+        let values_a = a.get(category).unwrap_or(&empty);
+        let values_b = b.get(category).unwrap_or(&empty);
+        let keys: BTreeSet<&String> = values_a.keys().chain(values_b.keys()).collect();
+        for key in keys {
+            let value_a = values_a.get(key);
+            let value_b = values_b.get(key);
+            if value_a != value_b {
+                differences.push(DeploymentDifference {
+                    category: category.to_string(),
+                    key: key.clone(),
+                    a: value_a.cloned(),
+                    b: value_b.cloned(),
+                });
+            }
+        }
+    }
+
+    Ok(DeploymentDiff {
+        dir_a,
+        dir_b,
+        identical: differences.is_empty(),
+        differences,
+    })
+}
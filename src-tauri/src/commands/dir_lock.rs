@@ -0,0 +1,124 @@
+// src-tauri/src/commands/dir_lock.rs
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use sysinfo::{Pid, System};
+
+/// Cross-process lock guarding a deployment directory; the lock file is removed on drop.
+#[derive(Debug)]
+pub struct DirLock {
+    path: PathBuf,
+}
+
+impl Drop for DirLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// 64-bit FNV-1a. The lock name has to be the same for every build of the app that may
+/// run at once (e.g. an old instance still running after an update), so std's
+/// `DefaultHasher`, whose algorithm is unspecified, can't be used.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+// Lock files live outside the target dir so they never make `git clone` see a non-empty folder.
+// The path is not canonicalized because the directory may not exist yet (e.g. before clone).
+fn lock_file_path(target_dir: &str) -> PathBuf {
+    let normalized = target_dir
+        .trim()
+        .trim_end_matches(['/', '\\'])
+        .replace('\\', "/")
+        .to_lowercase();
+    env::temp_dir()
+        .join("indextts-hub-locks")
+        .join(format!("{:016x}.lock", fnv1a_64(normalized.as_bytes())))
+}
+
+fn process_is_alive(pid: u32) -> bool {
+    let mut sys = System::new();
+    sys.refresh_process(Pid::from_u32(pid))
+}
+
+/// Reads the owner of an existing lock file, returning `None` when the owner is gone.
+fn live_lock_owner(path: &Path) -> Option<(u32, String)> {
+    let contents = fs::read_to_string(path).ok()?;
+    let mut parts = contents.splitn(2, '\n');
+    let pid = parts.next()?.trim().parse::<u32>().ok()?;
+    let operation = parts.next().unwrap_or("").trim().to_string();
+    process_is_alive(pid).then_some((pid, operation))
+}
+
+/// Acquire the lock for `target_dir`, failing if another live process (or another
+/// operation in this process) is already mutating it.
+pub fn acquire_dir_lock(target_dir: &str, operation: &str) -> Result<DirLock, String> {
+    let path = lock_file_path(target_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "Failed to create lock directory '{}': {}",
+                parent.display(),
+                e
+            )
+        })?;
+    }
+
+    for _ in 0..2 {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                let _ = write!(file, "{}\n{}", process::id(), operation);
+                return Ok(DirLock { path });
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => match live_lock_owner(&path) {
+                Some((pid, owner_operation)) => {
+                    return Err(format!(
+                        "'{}' is busy: {} is already running (PID {}). Wait for it to finish or close the other IndexTTS Hub window.",
+                        target_dir, owner_operation, pid
+                    ));
+                }
+                None => {
+                    // Stale lock left behind by a crashed process.
+                    let _ = fs::remove_file(&path);
+                }
+            },
+            Err(e) => {
+                return Err(format!(
+                    "Failed to create lock file '{}': {}",
+                    path.display(),
+                    e
+                ))
+            }
+        }
+    }
+
+    Err(format!("Failed to acquire lock for '{}'.", target_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_matches_reference_vectors() {
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn lock_name_is_stable_and_ignores_path_spelling() {
+        let expected = env::temp_dir()
+            .join("indextts-hub-locks")
+            .join("b5022f95418fd243.lock");
+        for spelling in [r"C:\index-tts", r"c:\Index-TTS\", " C:/index-tts/ "] {
+            assert_eq!(lock_file_path(spelling), expected, "{}", spelling);
+        }
+    }
+}
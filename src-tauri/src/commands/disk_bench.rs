@@ -0,0 +1,168 @@
+// src-tauri/src/commands/disk_bench.rs
+
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const DEFAULT_BENCH_SIZE_MB: u64 = 256;
+const MIN_BENCH_SIZE_MB: u64 = 16;
+const MAX_BENCH_SIZE_MB: u64 = 2048;
+const BENCH_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+// Each phase stops early on very slow disks; the rate is computed from what was done.
+const BENCH_PHASE_LIMIT: Duration = Duration::from_secs(20);
+// Sequential writes below this are typical of spinning disks or network shares.
+const SLOW_DISK_WRITE_MB_S: f64 = 80.0;
+
+// Rough sizes of a full deployment, in MB.
+pub const MODEL_DOWNLOAD_MB: f64 = 5_800.0;
+const ENV_DOWNLOAD_MB: f64 = 3_500.0;
+const ENV_INSTALLED_MB: f64 = 7_500.0;
+const DEFAULT_BANDWIDTH_MBPS: f64 = 50.0;
+// Resolution, builds and git work that is neither network- nor disk-bound.
+const FIXED_OVERHEAD_SECS: f64 = 120.0;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiskBenchmark {
+    pub path: String,
+    pub bytes_tested: u64,
+    pub write_mb_s: f64,
+    pub read_mb_s: f64,
+    pub slow: bool,
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DeployTimeEstimate {
+    pub download_minutes: f64,
+    pub disk_minutes: f64,
+    pub total_minutes: f64,
+    pub bandwidth_mbps: f64,
+    pub disk: DiskBenchmark,
+}
+
+fn closest_existing_dir(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.is_dir())
+        .map(Path::to_path_buf)
+}
+
+fn mb_per_sec(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64().max(0.001);
+    bytes as f64 / 1024.0 / 1024.0 / secs
+}
+
+fn run_benchmark(dir: &Path, size_mb: u64) -> Result<(u64, f64, f64), String> {
+    let file_path = dir.join(format!(".indextts-hub-bench-{}.tmp", std::process::id()));
+    let result = write_and_read(&file_path, size_mb);
+    let _ = fs::remove_file(&file_path);
+    result
+}
+
+fn write_and_read(file_path: &Path, size_mb: u64) -> Result<(u64, f64, f64), String> {
+    let target_bytes = size_mb * 1024 * 1024;
+    let chunk: Vec<u8> = (0..BENCH_CHUNK_BYTES).map(|i| (i % 251) as u8).collect();
+
+    let mut file = File::create(file_path)
+        .map_err(|e| format!("Failed to create {}: {}", file_path.display(), e))?;
+    let started = Instant::now();
+    let mut written: u64 = 0;
+    while written < target_bytes && started.elapsed() < BENCH_PHASE_LIMIT {
+        file.write_all(&chunk)
+            .map_err(|e| format!("Failed to write {}: {}", file_path.display(), e))?;
+        written += chunk.len() as u64;
+    }
+    // Include the flush to the device, otherwise this only measures the page cache.
+    file.sync_all()
+        .map_err(|e| format!("Failed to sync {}: {}", file_path.display(), e))?;
+    let write_mb_s = mb_per_sec(written, started.elapsed());
+    drop(file);
+
+    // The data may still be cached, so the read figure is an upper bound.
+    let mut file = File::open(file_path)
+        .map_err(|e| format!("Failed to open {}: {}", file_path.display(), e))?;
+    let mut buffer = vec![0u8; BENCH_CHUNK_BYTES];
+    let started = Instant::now();
+    let mut read: u64 = 0;
+    while started.elapsed() < BENCH_PHASE_LIMIT {
+        let n = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+        if n == 0 {
+            break;
+        }
+        read += n as u64;
+    }
+    let read_mb_s = mb_per_sec(read, started.elapsed());
+
+    Ok((written, write_mb_s, read_mb_s))
+}
+
+pub async fn benchmark_dir(
+    target_dir: &str,
+    size_mb: Option<u64>,
+) -> Result<DiskBenchmark, String> {
+    let dir = closest_existing_dir(Path::new(target_dir))
+        .ok_or_else(|| format!("No existing directory found for {}.", target_dir))?;
+    let size_mb = size_mb
+        .unwrap_or(DEFAULT_BENCH_SIZE_MB)
+        .clamp(MIN_BENCH_SIZE_MB, MAX_BENCH_SIZE_MB);
+
+    let bench_dir = dir.clone();
+    let (bytes_tested, write_mb_s, read_mb_s) =
+        tauri::async_runtime::spawn_blocking(move || run_benchmark(&bench_dir, size_mb))
+            .await
+            .map_err(|e| format!("Disk benchmark task failed: {}", e))??;
+
+    let slow = write_mb_s < SLOW_DISK_WRITE_MB_S;
+    Ok(DiskBenchmark {
+        path: dir.display().to_string(),
+        bytes_tested,
+        write_mb_s,
+        read_mb_s,
+        slow,
+        warning: slow.then(|| {
+            format!(
+                "Sequential write speed is {:.0} MB/s; this looks like a hard disk or network share. Model loading and installs will be slow.",
+                write_mb_s
+            )
+        }),
+    })
+}
+
+/// Measures sequential write/read throughput of the volume holding `target_dir`.
+/// `size_mb` is clamped to 16–2048 MB and each phase stops after 20 seconds.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn benchmark_disk(
+    target_dir: String,
+    size_mb: Option<u64>,
+) -> Result<DiskBenchmark, String> {
+    benchmark_dir(&target_dir, size_mb).await
+}
+
+/// Rough wall-clock estimate for a full deployment into `target_dir`, based on a quick
+/// disk benchmark and the given (or assumed) network bandwidth in Mbit/s.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn estimate_deploy_time(
+    target_dir: String,
+    bandwidth_mbps: Option<f64>,
+) -> Result<DeployTimeEstimate, String> {
+    let disk = benchmark_dir(&target_dir, Some(MIN_BENCH_SIZE_MB * 4)).await?;
+    let bandwidth_mbps = bandwidth_mbps
+        .filter(|value| *value > 0.0)
+        .unwrap_or(DEFAULT_BANDWIDTH_MBPS);
+
+    let download_secs = (MODEL_DOWNLOAD_MB + ENV_DOWNLOAD_MB) / (bandwidth_mbps / 8.0);
+    let disk_secs = (MODEL_DOWNLOAD_MB + ENV_INSTALLED_MB) / disk.write_mb_s.max(1.0);
+    // Downloads are written while they stream, so the slower of the two dominates.
+    let total_secs = download_secs.max(disk_secs) + FIXED_OVERHEAD_SECS;
+
+    Ok(DeployTimeEstimate {
+        download_minutes: download_secs / 60.0,
+        disk_minutes: disk_secs / 60.0,
+        total_minutes: total_secs / 60.0,
+        bandwidth_mbps,
+        disk,
+    })
+}
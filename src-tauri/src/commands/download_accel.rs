@@ -0,0 +1,118 @@
+// src-tauri/src/commands/download_accel.rs
+
+use super::command_utils::new_command;
+use super::settings::{load_settings, save_settings};
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+// Name of the uv tool environment `run_model_download` installs the `hf` CLI into.
+const HF_TOOL_ENV: &str = "huggingface-hub";
+const HF_TOOL_SPEC: &str = "huggingface-hub[cli,hf_xet]";
+const HF_TOOL_SPEC_ACCELERATED: &str = "huggingface-hub[cli,hf_xet,hf_transfer]";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DownloadAcceleration {
+    // The user asked for hf_transfer.
+    pub enabled: bool,
+    // hf_transfer is importable in the hf tool environment.
+    pub installed: bool,
+    // Downloads will set HF_HUB_ENABLE_HF_TRANSFER=1.
+    pub active: bool,
+    pub message: String,
+}
+
+/// Package spec for `uv tool install`, with the hf_transfer extra when acceleration is on.
+pub fn hf_tool_spec(accelerate: bool) -> &'static str {
+    if accelerate {
+        HF_TOOL_SPEC_ACCELERATED
+    } else {
+        HF_TOOL_SPEC
+    }
+}
+
+async fn hf_tool_python() -> Option<PathBuf> {
+    let output = new_command("uv")
+        .args(["tool", "dir"])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let env_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()).join(HF_TOOL_ENV);
+    let python = if cfg!(windows) {
+        env_dir.join("Scripts").join("python.exe")
+    } else {
+        env_dir.join("bin").join("python")
+    };
+    python.is_file().then_some(python)
+}
+
+/// Whether the hf tool environment can import hf_transfer. huggingface_hub silently
+/// falls back to plain downloads when the flag is set without it.
+pub async fn hf_transfer_installed() -> bool {
+    let Some(python) = hf_tool_python().await else {
+        return false;
+    };
+    new_command(python)
+        .args(["-c", "import hf_transfer"])
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+}
+
+async fn acceleration_status(enabled: bool) -> DownloadAcceleration {
+    let installed = hf_transfer_installed().await;
+    let active = enabled && installed;
+    let message = match (enabled, installed) {
+        (true, true) => "HuggingFace downloads use hf_transfer.",
+        (true, false) => {
+            "hf_transfer is enabled but not installed yet; it is installed with the next HuggingFace download."
+        }
+        (false, true) => "hf_transfer is installed but disabled; downloads use the standard client.",
+        (false, false) => "hf_transfer is disabled; downloads use the standard client.",
+    };
+    DownloadAcceleration {
+        enabled,
+        installed,
+        active,
+        message: message.to_string(),
+    }
+}
+
+/// Reports whether HuggingFace downloads will use hf_transfer.
+#[tauri::command]
+pub async fn get_download_acceleration_status(
+    app_handle: AppHandle,
+) -> Result<DownloadAcceleration, String> {
+    Ok(acceleration_status(load_settings(&app_handle).hf_transfer_enabled).await)
+}
+
+/// Turns hf_transfer on or off for model downloads. With `install`, the hf tool is
+/// reinstalled with the accelerator right away instead of on the next download.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_hf_transfer_enabled(
+    app_handle: AppHandle,
+    enabled: bool,
+    install: Option<bool>,
+) -> Result<DownloadAcceleration, String> {
+    let mut settings = load_settings(&app_handle);
+    settings.hf_transfer_enabled = enabled;
+    save_settings(&app_handle, &settings)?;
+
+    if enabled && install.unwrap_or(false) {
+        let output = new_command("uv")
+            .args(["tool", "install", hf_tool_spec(true)])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute uv tool install: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Installing hf_transfer failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+    }
+    Ok(acceleration_status(enabled).await)
+}
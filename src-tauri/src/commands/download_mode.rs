@@ -0,0 +1,166 @@
+// src-tauri/src/commands/download_mode.rs
+
+use super::index_tts::{ModelSource, MODEL_REPO_ID};
+use super::storage_media::dir_size_bytes;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// Where the model files end up on disk.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadMode {
+    // Real files in the model directory (`--local-dir`).
+    #[default]
+    LocalDir,
+    // Files in the HuggingFace cache, linked into the model directory.
+    CacheSymlink,
+    // Files in the HuggingFace cache only; webui.py is pointed at the snapshot.
+    CacheOnly,
+}
+
+impl DownloadMode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "local_dir" => Ok(DownloadMode::LocalDir),
+            "cache_symlink" => Ok(DownloadMode::CacheSymlink),
+            "cache_only" => Ok(DownloadMode::CacheOnly),
+            other => Err(format!(
+                "Unsupported download mode '{}'. Use \"local_dir\", \"cache_symlink\" or \"cache_only\".",
+                other
+            )),
+        }
+    }
+
+    pub fn uses_hf_cache(self) -> bool {
+        self != DownloadMode::LocalDir
+    }
+
+    /// Only the HuggingFace CLI has a content-addressed cache with a stable snapshot path.
+    pub fn check_source(self, source: ModelSource) -> Result<(), String> {
+        if self.uses_hf_cache() && source == ModelSource::ModelScope {
+            return Err(
+                "Cache download modes need the HuggingFace source (or its mirror); ModelScope only supports \"local_dir\"."
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// `HF_HOME`, defaulting to `~/.cache/huggingface` like huggingface_hub does.
+pub fn hf_home() -> Option<PathBuf> {
+    env::var_os("HF_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME")
+                .or_else(|| env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".cache").join("huggingface"))
+        })
+}
+
+fn hf_hub_cache() -> Option<PathBuf> {
+    env::var_os("HF_HUB_CACHE")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| hf_home().map(|home| home.join("hub")))
+}
+
+/// `models--IndexTeam--IndexTTS-2` in the hub cache, holding blobs and snapshots.
+pub fn hf_model_cache_dir() -> Option<PathBuf> {
+    hf_hub_cache().map(|cache| cache.join(format!("models--{}", MODEL_REPO_ID.replace('/', "--"))))
+}
+
+/// The snapshot `refs/main` points at, after `hf download` filled the cache.
+pub fn hf_snapshot_dir() -> Result<PathBuf, String> {
+    let cache = hf_model_cache_dir()
+        .ok_or_else(|| "Could not determine the HuggingFace cache directory.".to_string())?;
+    let revision = fs::read_to_string(cache.join("refs").join("main"))
+        .map_err(|e| format!("No cached snapshot of {} found: {}", MODEL_REPO_ID, e))?;
+    let snapshot = cache.join("snapshots").join(revision.trim());
+    if !snapshot.is_dir() {
+        return Err(format!(
+            "Snapshot directory {} does not exist.",
+            snapshot.display()
+        ));
+    }
+    Ok(snapshot)
+}
+
+#[cfg(unix)]
+fn link_file(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+// Symlinks need Developer Mode or admin rights on Windows; hard links on the same
+// volume cost no space either.
+#[cfg(windows)]
+fn link_file(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link).or_else(|_| fs::hard_link(target, link))
+}
+
+/// Links every file of the cached snapshot into `model_dir`, replacing older links.
+pub fn link_snapshot(snapshot: &Path, model_dir: &Path) -> Result<usize, String> {
+    let mut linked = 0;
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let source_dir = snapshot.join(&relative);
+        let entries = fs::read_dir(&source_dir)
+            .map_err(|e| format!("Failed to read {}: {}", source_dir.display(), e))?;
+        for entry in entries.flatten() {
+            let relative = relative.join(entry.file_name());
+            let link = model_dir.join(&relative);
+            if entry.path().is_dir() {
+                fs::create_dir_all(&link)
+                    .map_err(|e| format!("Failed to create {}: {}", link.display(), e))?;
+                pending.push(relative);
+                continue;
+            }
+            if link.is_symlink() || link.is_file() {
+                let _ = fs::remove_file(&link);
+            }
+            // Snapshot entries are themselves links into blobs/; link the real file.
+            let target = fs::canonicalize(entry.path()).unwrap_or_else(|_| entry.path());
+            link_file(&target, &link).map_err(|e| {
+                format!(
+                    "Failed to link {} into {}: {}. Use the \"cache_only\" download mode instead.",
+                    target.display(),
+                    model_dir.display(),
+                    e
+                )
+            })?;
+            linked += 1;
+        }
+    }
+    Ok(linked)
+}
+
+/// Disk space the download occupies: the model directory for `local_dir`, the cache
+/// entry otherwise (links in the model directory take no space).
+pub fn footprint_mb(mode: DownloadMode, model_dir: &Path) -> f64 {
+    let bytes = match (mode, hf_model_cache_dir()) {
+        (DownloadMode::LocalDir, _) | (_, None) => dir_size_bytes(model_dir),
+        (_, Some(cache)) => dir_size_bytes(&cache.join("blobs")),
+    };
+    bytes as f64 / BYTES_PER_MB
+}
+
+/// Warns when a `local_dir` download sits next to a second copy in the HF cache.
+pub fn duplicate_copy_warning(mode: DownloadMode) -> Option<String> {
+    if mode != DownloadMode::LocalDir {
+        return None;
+    }
+    let cache = hf_model_cache_dir()?;
+    let cached_mb = dir_size_bytes(&cache.join("blobs")) as f64 / BYTES_PER_MB;
+    (cached_mb >= 1.0).then(|| {
+        format!(
+            "The HuggingFace cache at {} also holds {:.0} MB of this model, so it is stored twice. Delete that cache entry or use the \"cache_symlink\" download mode.",
+            cache.display(),
+            cached_mb
+        )
+    })
+}
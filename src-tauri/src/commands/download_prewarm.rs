@@ -0,0 +1,209 @@
+// src-tauri/src/commands/download_prewarm.rs
+
+use super::command_utils::new_command;
+use super::disk_bench::MODEL_DOWNLOAD_MB;
+use super::download_mode::hf_home;
+use super::index_tts::{ModelSource, MODEL_REPO_ID};
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+// Largest checkpoint in the repo, so redirects and the CDN are the same as the real download.
+const PREWARM_FILE: &str = "gpt.pth";
+// First 16 MB: long enough to get past TCP slow start, short enough to finish quickly.
+const PREWARM_RANGE: &str = "0-16777215";
+const PREWARM_MAX_SECS: u64 = 30;
+// A timed-out probe that still moved this much data gives a usable speed.
+const MIN_MEASURED_BYTES: u64 = 1024 * 1024;
+const HF_MIRROR_ENDPOINT: &str = "https://hf-mirror.com";
+const HF_DEFAULT_ENDPOINT: &str = "https://huggingface.co";
+const MODELSCOPE_ENDPOINT: &str = "https://modelscope.cn";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PrewarmReport {
+    pub source: ModelSource,
+    pub repo_id: String,
+    pub url: String,
+    pub ok: bool,
+    pub http_status: Option<u16>,
+    pub bytes: u64,
+    pub throughput_mb_s: Option<f64>,
+    // Time for the whole model at the measured speed.
+    pub estimated_total_secs: Option<f64>,
+    // Whether a Hugging Face token was sent with the request.
+    pub authenticated: bool,
+    pub error: Option<String>,
+}
+
+/// The endpoint the HuggingFace CLI would use for this network environment.
+fn hf_endpoint(network_environment: Option<&str>) -> String {
+    if network_environment == Some("mainland_china") {
+        return HF_MIRROR_ENDPOINT.to_string();
+    }
+    env::var("HF_ENDPOINT")
+        .ok()
+        .filter(|endpoint| !endpoint.is_empty())
+        .unwrap_or_else(|| HF_DEFAULT_ENDPOINT.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// `HF_TOKEN`, or the token `hf auth login` stored in the HuggingFace home.
+fn hf_token() -> Option<String> {
+    if let Some(token) = env::var("HF_TOKEN").ok().filter(|t| !t.trim().is_empty()) {
+        return Some(token.trim().to_string());
+    }
+    fs::read_to_string(hf_home()?.join("token"))
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+fn prewarm_url(source: ModelSource, repo_id: &str, network_environment: Option<&str>) -> String {
+    match source {
+        ModelSource::HuggingFace => format!(
+            "{}/{}/resolve/main/{}",
+            hf_endpoint(network_environment),
+            repo_id,
+            PREWARM_FILE
+        ),
+        ModelSource::ModelScope => format!(
+            "{}/models/{}/resolve/master/{}",
+            MODELSCOPE_ENDPOINT, repo_id, PREWARM_FILE
+        ),
+    }
+}
+
+/// Turns curl's exit code and the HTTP status into a reason the user can act on.
+fn failure_reason(exit_code: Option<i32>, http_status: Option<u16>, stderr: &str) -> String {
+    match http_status {
+        Some(401) | Some(403) => {
+            return "The source refused access (HTTP 401/403). The repository may be gated or \
+                    the access token is missing or invalid."
+                .to_string()
+        }
+        Some(404) => {
+            return "The repository or file was not found (HTTP 404). Check the repo id and \
+                    that the mirror carries it."
+                .to_string()
+        }
+        Some(429) => {
+            return "The source is rate limiting requests (HTTP 429). Wait a while or sign in \
+                    with an access token."
+                .to_string()
+        }
+        Some(status) if status >= 400 => {
+            return format!("The source answered with HTTP {}.", status);
+        }
+        _ => {}
+    }
+    match exit_code {
+        Some(5) => "Could not resolve the proxy host. Check the proxy settings.".to_string(),
+        Some(6) => "DNS lookup failed for the download host.".to_string(),
+        Some(7) => "Could not connect to the download host; it may be blocked.".to_string(),
+        Some(28) => format!(
+            "No usable data within {} seconds; the connection is too slow or stalled.",
+            PREWARM_MAX_SECS
+        ),
+        Some(35) | Some(60) => {
+            "TLS handshake failed; a proxy or firewall may be intercepting HTTPS.".to_string()
+        }
+        _ if !stderr.trim().is_empty() => stderr.trim().to_string(),
+        _ => format!("curl exited with code {:?}.", exit_code),
+    }
+}
+
+/// Fetches the first chunk of the model's largest file from the source the real download
+/// will use (same mirror and token), measures the throughput and estimates how long the
+/// full model download will take.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn prewarm_download(
+    model_source: String,
+    repo_id: Option<String>,
+    network_environment: Option<String>,
+) -> Result<PrewarmReport, String> {
+    let source = ModelSource::parse(&model_source)?;
+    let repo_id = repo_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| MODEL_REPO_ID.to_string());
+    let url = prewarm_url(source, &repo_id, network_environment.as_deref());
+    let token = match source {
+        ModelSource::HuggingFace => hf_token(),
+        ModelSource::ModelScope => None,
+    };
+
+    let mut command = new_command("curl");
+    command
+        .args(["-L", "-sS", "-r", PREWARM_RANGE, "--max-time"])
+        .arg(PREWARM_MAX_SECS.to_string())
+        .args(["-w", "%{http_code} %{size_download} %{speed_download}"])
+        .args(["-o", if cfg!(windows) { "NUL" } else { "/dev/null" }])
+        // Read the token from stdin so it never appears in the process list.
+        .args(["-K", "-"])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let config = token
+            .as_ref()
+            .map(|token| format!("header = \"Authorization: Bearer {}\"\n", token))
+            .unwrap_or_default();
+        stdin
+            .write_all(config.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to pass curl options: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout.split_whitespace();
+    let http_status = fields
+        .next()
+        .and_then(|code| code.parse::<u16>().ok())
+        .filter(|code| *code != 0);
+    let bytes = fields
+        .next()
+        .and_then(|size| size.parse::<f64>().ok())
+        .unwrap_or(0.0) as u64;
+    let bytes_per_sec = fields
+        .next()
+        .and_then(|speed| speed.parse::<f64>().ok())
+        .unwrap_or(0.0);
+
+    let http_ok = http_status.is_some_and(|status| (200..300).contains(&status));
+    // A probe cut off by --max-time still measured the link if data was flowing.
+    let ok = http_ok
+        && (output.status.success()
+            || (output.status.code() == Some(28) && bytes >= MIN_MEASURED_BYTES));
+    let throughput_mb_s = (ok && bytes_per_sec > 0.0).then(|| bytes_per_sec / 1_048_576.0);
+    let error = (!ok).then(|| {
+        failure_reason(
+            output.status.code(),
+            http_status,
+            &String::from_utf8_lossy(&output.stderr),
+        )
+    });
+
+    Ok(PrewarmReport {
+        source,
+        repo_id,
+        url,
+        ok,
+        http_status,
+        bytes,
+        estimated_total_secs: throughput_mb_s.map(|speed| MODEL_DOWNLOAD_MB / speed),
+        throughput_mb_s,
+        authenticated: token.is_some(),
+        error,
+    })
+}
@@ -0,0 +1,326 @@
+// src-tauri/src/commands/download_progress.rs
+
+use super::index_tts::ModelSource;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+
+const MODEL_FILE_STATUS_EVENT: &str = "model-file-status";
+const MODEL_DOWNLOAD_SUMMARY_EVENT: &str = "model-download-summary";
+const CORE_DEPLOY_PROGRESS_EVENT: &str = "core-deploy-progress";
+// tqdm size suffixes; each step multiplies by the bar's divisor.
+const SIZE_SUFFIXES: [char; 4] = ['k', 'M', 'G', 'T'];
+
+/// Divisor between tqdm size suffixes: huggingface_hub keeps tqdm's default of 1000,
+/// modelscope sets `unit_divisor=1024`.
+fn size_divisor(source: ModelSource) -> f64 {
+    match source {
+        ModelSource::HuggingFace => 1000.0,
+        ModelSource::ModelScope => 1024.0,
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelFileState {
+    Queued,
+    Downloading,
+    Complete,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelFileStatusEvent {
+    pub file: String,
+    pub status: ModelFileState,
+    pub percent: Option<f32>,
+}
+
+/// Aggregate progress over every file whose size the download output has reported.
+/// Files that have not started yet are not counted, so `total_bytes` can grow.
+#[derive(Debug, Serialize, Clone)]
+pub struct CoreDeployProgressEvent {
+    pub step: String,
+    pub percent: f32,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ModelDownloadSummary {
+    pub queued: usize,
+    pub downloading: usize,
+    pub complete: usize,
+    pub failed: usize,
+}
+
+/// Parses a per-file progress line printed by `hf download` (`gpt.pth: 45%|...`) or
+/// `modelscope download` (`Downloading [gpt.pth]: 45%|...`).
+fn parse_file_progress(line: &str) -> Option<(String, f32)> {
+    let line = line.trim();
+    let (label, rest) = line.split_once(": ")?;
+    let percent = rest.split_once('%')?.0.trim().parse::<f32>().ok()?;
+
+    let label = label.trim();
+    let file = match label.strip_prefix("Downloading [") {
+        Some(inner) => inner.strip_suffix(']')?,
+        None => label,
+    };
+
+    // Aggregate bars such as "Fetching 12 files" are not file names.
+    if file.is_empty() || file.contains(char::is_whitespace) {
+        return None;
+    }
+
+    Some((file.to_string(), percent))
+}
+
+/// Parses a tqdm size such as `2.1G`, `512k` or `4.70GB` into bytes, scaling each
+/// suffix by `divisor`. An explicit binary unit (`13.0MiB`) is always scaled by 1024.
+fn parse_size(value: &str, divisor: f64) -> Option<u64> {
+    let value = value.trim().trim_end_matches('B');
+    let (value, divisor) = match value.strip_suffix('i') {
+        Some(value) => (value, 1024.0),
+        None => (value, divisor),
+    };
+    let last = value.chars().last()?;
+    let (number, power) = match SIZE_SUFFIXES
+        .iter()
+        .position(|suffix| suffix.eq_ignore_ascii_case(&last))
+    {
+        Some(index) => (&value[..value.len() - 1], index as i32 + 1),
+        None => (value, 0),
+    };
+    let number = number.trim().parse::<f64>().ok()?;
+    Some((number * divisor.powi(power)) as u64)
+}
+
+/// The `2.1G/4.7G` byte counts after the bar of a per-file progress line.
+fn parse_file_bytes(line: &str, divisor: f64) -> Option<(u64, u64)> {
+    let counts = line.split('|').nth(2)?.split_whitespace().next()?;
+    let (done, total) = counts.split_once('/')?;
+    let total = parse_size(total, divisor)?;
+    (total > 0).then_some((parse_size(done, divisor)?.min(total), total))
+}
+
+/// Tracks the state of each model file seen in the download output and emits
+/// `model-file-status` whenever a file changes state.
+pub struct ModelFileTracker {
+    app_handle: AppHandle,
+    // Divisor of the downloading CLI's size suffixes, see `size_divisor`.
+    size_divisor: f64,
+    files: Mutex<BTreeMap<String, ModelFileState>>,
+    // Downloaded and total bytes per file, from the byte counts on its progress bar.
+    bytes: Mutex<BTreeMap<String, (u64, u64)>>,
+    // Last whole percentage emitted, so the bar's many redraws don't flood the UI.
+    last_percent: Mutex<Option<u32>>,
+}
+
+impl ModelFileTracker {
+    pub fn new(app_handle: AppHandle, expected_files: &[&str], source: ModelSource) -> Arc<Self> {
+        let tracker = Arc::new(ModelFileTracker {
+            app_handle,
+            size_divisor: size_divisor(source),
+            files: Mutex::new(BTreeMap::new()),
+            bytes: Mutex::new(BTreeMap::new()),
+            last_percent: Mutex::new(None),
+        });
+        for file in expected_files {
+            tracker.transition(file, ModelFileState::Queued, None);
+        }
+        tracker
+    }
+
+    pub fn observe(&self, line: &str) {
+        let Some((file, percent)) = parse_file_progress(line) else {
+            return;
+        };
+
+        let state = if percent >= 100.0 {
+            ModelFileState::Complete
+        } else if percent > 0.0 {
+            ModelFileState::Downloading
+        } else {
+            ModelFileState::Queued
+        };
+        self.transition(&file, state, Some(percent));
+        if let Some(bytes) = parse_file_bytes(line, self.size_divisor) {
+            self.record_bytes(&file, bytes);
+        }
+    }
+
+    fn record_bytes(&self, file: &str, bytes: (u64, u64)) {
+        let (downloaded_bytes, total_bytes) = {
+            let Ok(mut files) = self.bytes.lock() else {
+                return;
+            };
+            files.insert(file.to_string(), bytes);
+            files
+                .values()
+                .fold((0, 0), |(done, total), (d, t)| (done + d, total + t))
+        };
+        let percent = downloaded_bytes as f32 * 100.0 / total_bytes.max(1) as f32;
+        {
+            let Ok(mut last) = self.last_percent.lock() else {
+                return;
+            };
+            if *last == Some(percent as u32) {
+                return;
+            }
+            *last = Some(percent as u32);
+        }
+
+        let _ = self.app_handle.emit(
+            CORE_DEPLOY_PROGRESS_EVENT,
+            CoreDeployProgressEvent {
+                step: "download_model".to_string(),
+                percent,
+                downloaded_bytes,
+                total_bytes,
+            },
+        );
+    }
+
+    fn transition(&self, file: &str, state: ModelFileState, percent: Option<f32>) {
+        {
+            let Ok(mut files) = self.files.lock() else {
+                return;
+            };
+            if files.get(file) == Some(&state) {
+                return;
+            }
+            files.insert(file.to_string(), state);
+        }
+
+        let _ = self.app_handle.emit(
+            MODEL_FILE_STATUS_EVENT,
+            ModelFileStatusEvent {
+                file: file.to_string(),
+                status: state,
+                percent,
+            },
+        );
+    }
+
+    /// Settles the files that are not complete yet and emits the final per-state counts.
+    /// After a successful download they are complete too: files that were already in
+    /// place or too small to draw a bar never print one. Otherwise they failed.
+    pub fn finish(&self, success: bool) -> ModelDownloadSummary {
+        let unfinished: Vec<String> = self
+            .files
+            .lock()
+            .map(|files| {
+                files
+                    .iter()
+                    .filter(|(_, state)| **state != ModelFileState::Complete)
+                    .map(|(file, _)| file.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let (state, percent) = if success {
+            (ModelFileState::Complete, Some(100.0))
+        } else {
+            (ModelFileState::Failed, None)
+        };
+        for file in unfinished {
+            self.transition(&file, state, percent);
+        }
+
+        let mut summary = ModelDownloadSummary::default();
+        if let Ok(files) = self.files.lock() {
+            for state in files.values() {
+                match state {
+                    ModelFileState::Queued => summary.queued += 1,
+                    ModelFileState::Downloading => summary.downloading += 1,
+                    ModelFileState::Complete => summary.complete += 1,
+                    ModelFileState::Failed => summary.failed += 1,
+                }
+            }
+        }
+
+        let _ = self
+            .app_handle
+            .emit(MODEL_DOWNLOAD_SUMMARY_EVENT, summary.clone());
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HF: f64 = 1000.0;
+    const MODELSCOPE: f64 = 1024.0;
+
+    #[test]
+    fn parses_hf_file_bars() {
+        let line = "gpt.pth:  45%|████▌     | 1.57G/3.49G [00:30<00:37, 52.3MB/s]";
+        assert_eq!(
+            parse_file_progress(line),
+            Some(("gpt.pth".to_string(), 45.0))
+        );
+        assert_eq!(
+            parse_file_bytes(line, HF),
+            Some((1_570_000_000, 3_490_000_000))
+        );
+
+        let line = "config.yaml: 100%|██████████| 2.12k/2.12k [00:00<00:00, 1.05MB/s]";
+        assert_eq!(
+            parse_file_progress(line),
+            Some(("config.yaml".to_string(), 100.0))
+        );
+        assert_eq!(parse_file_bytes(line, HF), Some((2120, 2120)));
+    }
+
+    #[test]
+    fn parses_modelscope_file_bars() {
+        let line = "Downloading [gpt.pth]:  45%|████▌     | 1.46G/3.25G [00:30<00:37, 52.3MB/s]";
+        assert_eq!(
+            parse_file_progress(line),
+            Some(("gpt.pth".to_string(), 45.0))
+        );
+        assert_eq!(
+            parse_file_bytes(line, MODELSCOPE),
+            Some((1_567_663_063, 3_489_660_928))
+        );
+
+        let line = "Downloading [bpe.model]: 100%|██████████| 465k/465k [00:00<00:00, 1.20MB/s]";
+        assert_eq!(
+            parse_file_progress(line),
+            Some(("bpe.model".to_string(), 100.0))
+        );
+        assert_eq!(parse_file_bytes(line, MODELSCOPE), Some((476_160, 476_160)));
+    }
+
+    #[test]
+    fn ignores_lines_that_are_not_file_bars() {
+        for line in [
+            "Fetching 12 files:  25%|██▌       | 3/12 [00:01<00:03,  2.50it/s]",
+            "Downloading Model to directory: /home/me/index-tts/checkpoints",
+            "2025-06-01 12:00:00,000 - modelscope - INFO - Got 12 files, start to download ...",
+            "",
+        ] {
+            assert_eq!(parse_file_progress(line), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn size_suffixes_follow_the_divisor() {
+        assert_eq!(parse_size("512", HF), Some(512));
+        assert_eq!(parse_size("2.1G", HF), Some(2_100_000_000));
+        assert_eq!(parse_size("2.1G", MODELSCOPE), Some(2_254_857_830));
+        assert_eq!(parse_size("4.70GB", HF), Some(4_700_000_000));
+        assert_eq!(parse_size("512k", MODELSCOPE), Some(524_288));
+        assert_eq!(parse_size("13.0MiB", HF), Some(13_631_488));
+        assert_eq!(parse_size("?", HF), None);
+    }
+
+    #[test]
+    fn byte_counts_need_a_total() {
+        let line = "gpt.pth:   0%|          | 0.00/0.00 [00:00<?, ?B/s]";
+        assert_eq!(parse_file_bytes(line, HF), None);
+        let line = "gpt.pth:  10%|█         | 1.00G/? [00:10<?, 100MB/s]";
+        assert_eq!(parse_file_bytes(line, HF), None);
+    }
+}
@@ -0,0 +1,147 @@
+// src-tauri/src/commands/driver_check.rs
+
+use super::command_utils::clean_path_arg;
+use super::config_store::{load_json, save_json};
+use super::deployments::unix_timestamp;
+use super::index_tts::run_gpu_check;
+use super::settings::load_settings;
+use super::torch_build::{driver_supports_build, query_nvidia_driver_version, suggest_for_driver};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+pub const KNOWN_GOOD_DRIVERS_FILE: &str = "known_good_drivers.json";
+
+/// Driver a deployment last launched successfully on CUDA with.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KnownGoodDriver {
+    pub driver_version: String,
+    pub torch_build: Option<String>,
+    pub recorded_at: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DriverRevalidation {
+    pub current_driver: Option<String>,
+    pub last_good: Option<KnownGoodDriver>,
+    pub driver_changed: bool,
+    // torch inside the deployment's environment reports a usable CUDA device.
+    pub torch_sees_gpu: bool,
+    pub gpu_name: Option<String>,
+    pub gpu_check_error: Option<String>,
+    // Torch build to reinstall with, when the current one no longer fits the driver.
+    pub suggested_torch_build: Option<String>,
+    pub recommendation: Option<String>,
+}
+
+type KnownGoodDrivers = BTreeMap<String, KnownGoodDriver>;
+
+fn load_known_good(app_handle: &AppHandle) -> KnownGoodDrivers {
+    load_json(app_handle, KNOWN_GOOD_DRIVERS_FILE)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Remembers the current driver as working for `target_dir`. Called once a CUDA
+/// server has come up.
+pub async fn record_known_good_driver(app_handle: &AppHandle, target_dir: &str) {
+    let Some(driver_version) = query_nvidia_driver_version().await else {
+        return;
+    };
+    let mut known = load_known_good(app_handle);
+    known.insert(
+        target_dir.to_string(),
+        KnownGoodDriver {
+            driver_version,
+            torch_build: load_settings(app_handle).torch_build,
+            recorded_at: unix_timestamp(),
+        },
+    );
+    let _ = save_json(app_handle, KNOWN_GOOD_DRIVERS_FILE, &known);
+}
+
+fn recommend(
+    current_driver: Option<&str>,
+    last_good: Option<&KnownGoodDriver>,
+    torch_sees_gpu: bool,
+    torch_build: Option<&str>,
+) -> (Option<String>, Option<String>) {
+    let Some(driver) = current_driver else {
+        return (
+            None,
+            Some(
+                "No NVIDIA driver was found. Reinstall the GPU driver, or launch on the CPU."
+                    .to_string(),
+            ),
+        );
+    };
+    if torch_sees_gpu {
+        return (None, None);
+    }
+    let (suggested, reason) = suggest_for_driver(Some(driver));
+    let build_fits = torch_build.is_none_or(|build| driver_supports_build(driver, build));
+    if !build_fits {
+        return (
+            Some(suggested.clone()),
+            Some(format!(
+                "The installed torch build {} no longer works with driver {}. {} Set the torch build to {} and re-run the environment setup.",
+                torch_build.unwrap_or_default(),
+                driver,
+                reason,
+                suggested
+            )),
+        );
+    }
+    let changed = last_good.is_some_and(|good| good.driver_version != driver);
+    (
+        Some(suggested),
+        Some(if changed {
+            format!(
+                "torch can no longer see the GPU since the driver changed from {} to {}. Re-run the environment setup to reinstall torch, or restart the computer if the driver update is still pending.",
+                last_good.map(|good| good.driver_version.as_str()).unwrap_or_default(),
+                driver
+            )
+        } else {
+            "torch cannot see the GPU. Re-run the environment setup to reinstall torch.".to_string()
+        }),
+    )
+}
+
+/// Re-runs the in-environment torch CUDA check and compares the NVIDIA driver with the
+/// one recorded at the last successful CUDA launch of this deployment.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn revalidate_after_driver_update(
+    app_handle: AppHandle,
+    target_dir: String,
+) -> Result<DriverRevalidation, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    let current_driver = query_nvidia_driver_version().await;
+    let last_good = load_known_good(&app_handle).remove(&target_dir);
+    let (torch_sees_gpu, gpu_name, gpu_check_error) = match run_gpu_check(target_dir).await {
+        Ok(info) => (info.has_cuda, info.name, None),
+        Err(e) => (false, None, Some(e)),
+    };
+    let torch_build = load_settings(&app_handle).torch_build;
+    let (suggested_torch_build, recommendation) = recommend(
+        current_driver.as_deref(),
+        last_good.as_ref(),
+        torch_sees_gpu,
+        torch_build.as_deref(),
+    );
+
+    Ok(DriverRevalidation {
+        driver_changed: match (&last_good, &current_driver) {
+            (Some(good), Some(current)) => &good.driver_version != current,
+            (Some(_), None) => true,
+            _ => false,
+        },
+        current_driver,
+        last_good,
+        torch_sees_gpu,
+        gpu_name,
+        gpu_check_error,
+        suggested_torch_build,
+        recommendation,
+    })
+}
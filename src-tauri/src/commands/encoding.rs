@@ -0,0 +1,64 @@
+// src-tauri/src/commands/encoding.rs
+
+use super::command_utils::{inherited_locale, is_utf8_locale, new_command, utf8_locale_override};
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EncodingInfo {
+    pub inherited_locale: Option<String>,
+    pub inherited_utf8: bool,
+    // Locale forced on child processes, if the inherited one isn't UTF-8.
+    pub subprocess_locale: Option<String>,
+    pub python_utf8: bool,
+    pub windows_code_page: Option<u32>,
+    pub warning: Option<String>,
+}
+
+/// Active ANSI/OEM code page as reported by `chcp`, e.g. 936 for GBK.
+async fn windows_code_page() -> Option<u32> {
+    if !cfg!(windows) {
+        return None;
+    }
+    let output = new_command("cmd")
+        .args(["/C", "chcp"])
+        .output()
+        .await
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .split(':')
+        .nth(1)?
+        .trim()
+        .trim_end_matches('.')
+        .parse()
+        .ok()
+}
+
+/// Reports the locale the app inherited and what spawned commands run with.
+#[tauri::command]
+pub async fn check_encoding() -> Result<EncodingInfo, String> {
+    let inherited_locale = inherited_locale();
+    let inherited_utf8 = inherited_locale.as_deref().is_some_and(is_utf8_locale);
+    let windows_code_page = windows_code_page().await;
+
+    let warning = match windows_code_page {
+        Some(page) if page != 65001 => Some(format!(
+            "The Windows code page is {}. Python output is forced to UTF-8, but other tools may still print garbled text for non-ASCII paths.",
+            page
+        )),
+        _ if !cfg!(windows) && !inherited_utf8 => Some(format!(
+            "The app inherited a non-UTF-8 locale ({}); deploy commands run with {} instead.",
+            inherited_locale.as_deref().unwrap_or("unset"),
+            utf8_locale_override().unwrap_or("C.UTF-8")
+        )),
+        _ => None,
+    };
+
+    Ok(EncodingInfo {
+        inherited_locale,
+        inherited_utf8,
+        subprocess_locale: utf8_locale_override().map(str::to_string),
+        python_utf8: true,
+        windows_code_page,
+        warning,
+    })
+}
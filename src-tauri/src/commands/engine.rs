@@ -0,0 +1,236 @@
+// src-tauri/src/commands/engine.rs
+
+use super::command_utils::clean_path_arg;
+use super::config_store::{load_json, save_json};
+use super::index_tts::model_has_core_files;
+use super::system_info::volume_for_path;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+// Stat calls against a NAS can take a while, and a dead share can block for much longer.
+const MODEL_DIR_PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunMode {
+    #[default]
+    Cpu,
+    Cuda,
+    Mps,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct EngineConfig {
+    pub install_dir: String,
+    pub model_dir: String,
+    pub run_mode: RunMode,
+}
+
+pub const ENGINE_CONFIG_FILE: &str = "engine_config.json";
+
+// Holds the engine configuration currently used by the UI, managed by Tauri State
+pub struct EngineManager(Mutex<EngineConfig>);
+
+impl EngineManager {
+    /// Starts empty; `restore_engine_config` fills in the saved config during setup,
+    /// once the config directory is known.
+    pub fn new() -> Self {
+        EngineManager(Mutex::new(EngineConfig::default()))
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, EngineConfig> {
+        self.0.lock().expect("Engine config mutex poisoned")
+    }
+}
+
+/// Loads the config saved by the last session. A missing, corrupt or unreadable file
+/// leaves the defaults in place, and the latter two return a warning; fields added since
+/// it was written take their defaults.
+pub fn restore_engine_config(app_handle: &AppHandle) -> Option<String> {
+    let config = match load_json::<EngineConfig>(app_handle, ENGINE_CONFIG_FILE) {
+        Ok(Some(config)) => config,
+        Ok(None) => return None,
+        Err(e) => return Some(format!("Ignoring saved engine config: {}", e)),
+    };
+    *app_handle.state::<EngineManager>().lock() = config;
+    None
+}
+
+/// Saves the current engine config so the next launch starts with it.
+pub fn persist_engine_config(app_handle: &AppHandle) -> Result<(), String> {
+    let config = app_handle.state::<EngineManager>().lock().clone();
+    save_json(app_handle, ENGINE_CONFIG_FILE, &config)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelDirProbe {
+    pub model_dir: String,
+    pub is_network: bool,
+    pub has_core_files: bool,
+    pub warning: Option<String>,
+}
+
+fn is_unc_path(path: &str) -> bool {
+    path.starts_with(r"\\") || path.starts_with("//")
+}
+
+fn probe_model_dir_blocking(model_dir: String) -> Result<ModelDirProbe, String> {
+    let path = Path::new(&model_dir);
+    let is_network =
+        is_unc_path(&model_dir) || volume_for_path(path).is_some_and(|volume| volume.is_network);
+
+    if !path.is_dir() {
+        return Err(if is_network {
+            format!(
+                "Model directory {} is not reachable. Check that the network share is online and mounted.",
+                model_dir
+            )
+        } else {
+            format!("Model directory does not exist: {}", model_dir)
+        });
+    }
+    std::fs::read_dir(path)
+        .map_err(|e| format!("Model directory {} is not readable: {}", model_dir, e))?;
+
+    Ok(ModelDirProbe {
+        is_network,
+        has_core_files: model_has_core_files(path),
+        warning: is_network.then(|| {
+            "The model is on a network path; loading and inference may be slower than from a local disk."
+                .to_string()
+        }),
+        model_dir,
+    })
+}
+
+/// Checks that a (possibly network/UNC) model directory is reachable and readable,
+/// giving up after `MODEL_DIR_PROBE_TIMEOUT` instead of hanging on an offline share.
+pub async fn probe_model_dir(model_dir: &str) -> Result<ModelDirProbe, String> {
+    let owned = model_dir.to_string();
+    let probe = tauri::async_runtime::spawn_blocking(move || probe_model_dir_blocking(owned));
+    match tokio::time::timeout(MODEL_DIR_PROBE_TIMEOUT, probe).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => Err(format!(
+            "Failed to check model directory {}: {}",
+            model_dir, e
+        )),
+        Err(_) => Err(format!(
+            "Timed out after {}s while accessing {}. The network share may be down.",
+            MODEL_DIR_PROBE_TIMEOUT.as_secs(),
+            model_dir
+        )),
+    }
+}
+
+#[tauri::command]
+pub fn get_engine_config(state: State<'_, EngineManager>) -> Result<EngineConfig, String> {
+    Ok(state.lock().clone())
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DirRelationship {
+    Separate,
+    ModelInsideRepo,
+    RepoInsideModel,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DirLayout {
+    pub relationship: DirRelationship,
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigUpdate {
+    pub config: EngineConfig,
+    pub layout: DirLayout,
+}
+
+fn normalized(path: &Path) -> std::path::PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+pub fn dir_relationship(install_dir: &Path, model_dir: &Path) -> DirRelationship {
+    let (install_dir, model_dir) = (normalized(install_dir), normalized(model_dir));
+    if model_dir.starts_with(&install_dir) {
+        DirRelationship::ModelInsideRepo
+    } else if install_dir.starts_with(&model_dir) {
+        DirRelationship::RepoInsideModel
+    } else {
+        DirRelationship::Separate
+    }
+}
+
+/// Path of `model_dir` relative to `install_dir` when the model lives inside the repo,
+/// so destructive repo operations can leave it alone.
+pub fn model_dir_within_repo(install_dir: &Path, model_dir: &Path) -> Option<std::path::PathBuf> {
+    normalized(model_dir)
+        .strip_prefix(normalized(install_dir))
+        .ok()
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+}
+
+pub fn dir_layout(install_dir: &str, model_dir: &str) -> DirLayout {
+    if install_dir.is_empty() || model_dir.is_empty() {
+        return DirLayout {
+            relationship: DirRelationship::Separate,
+            warning: None,
+        };
+    }
+    let relationship = dir_relationship(Path::new(install_dir), Path::new(model_dir));
+    let warning = match relationship {
+        DirRelationship::Separate => None,
+        DirRelationship::ModelInsideRepo => Some(
+            "The model directory is inside the install directory. Repairs keep it, but deleting the install directory deletes the model too.".to_string(),
+        ),
+        DirRelationship::RepoInsideModel => Some(
+            "The install directory is inside the model directory. Cleaning the model directory would delete the installation; choose separate folders.".to_string(),
+        ),
+    };
+    DirLayout {
+        relationship,
+        warning,
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_config(
+    app_handle: AppHandle,
+    mut config: EngineConfig,
+    state: State<'_, EngineManager>,
+) -> Result<ConfigUpdate, String> {
+    config.install_dir = clean_path_arg(&config.install_dir);
+    config.model_dir = clean_path_arg(&config.model_dir);
+    if !config.model_dir.is_empty() {
+        probe_model_dir(&config.model_dir).await?;
+    }
+    let layout = dir_layout(&config.install_dir, &config.model_dir);
+    *state.lock() = config.clone();
+    persist_engine_config(&app_handle)?;
+    Ok(ConfigUpdate { config, layout })
+}
+
+/// Points the engine at a model directory, which may live on a NAS or UNC share.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_active_model(
+    app_handle: AppHandle,
+    model_dir: String,
+    state: State<'_, EngineManager>,
+) -> Result<ModelDirProbe, String> {
+    let model_dir = clean_path_arg(&model_dir);
+    let probe = probe_model_dir(&model_dir).await?;
+    if !probe.has_core_files {
+        return Err(format!(
+            "{} does not contain the IndexTTS model files.",
+            model_dir
+        ));
+    }
+    state.lock().model_dir = model_dir;
+    persist_engine_config(&app_handle)?;
+    Ok(probe)
+}
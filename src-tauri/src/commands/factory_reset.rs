@@ -0,0 +1,104 @@
+// src-tauri/src/commands/factory_reset.rs
+
+use super::config_migrations::{remove_migration_backups, SCHEMA_FILE};
+use super::config_store::remove_json;
+use super::deploy::DEPLOY_PROGRESS_FILE;
+use super::deploy_log::DeployLogBuffer;
+use super::deployments::{load_deployments, DEPLOYMENTS_FILE};
+use super::driver_check::KNOWN_GOOD_DRIVERS_FILE;
+use super::engine::{EngineConfig, EngineManager, ENGINE_CONFIG_FILE};
+use super::log_files::remove_log_files;
+use super::platform_defaults::PLATFORM_DEFAULTS_FILE;
+use super::server::{ServerChildProcess, STARTUP_HISTORY_FILE};
+use super::server_config::SERVER_CONFIG_FILE;
+use super::settings::SETTINGS_FILE;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+// Every JSON file the app persists in its config directory, besides the pre-migration
+// backups removed by `remove_migration_backups`.
+const PERSISTED_FILES: [&str; 9] = [
+    SETTINGS_FILE,
+    ENGINE_CONFIG_FILE,
+    SERVER_CONFIG_FILE,
+    DEPLOY_PROGRESS_FILE,
+    DEPLOYMENTS_FILE,
+    STARTUP_HISTORY_FILE,
+    KNOWN_GOOD_DRIVERS_FILE,
+    PLATFORM_DEFAULTS_FILE,
+    SCHEMA_FILE,
+];
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct FactoryResetReport {
+    pub removed_files: Vec<String>,
+    pub removed_deployments: Vec<String>,
+    pub kept_deployments: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+fn remove_dir(path: &str, report: &mut FactoryResetReport) {
+    if !Path::new(path).exists() {
+        return;
+    }
+    match fs::remove_dir_all(path) {
+        Ok(()) => report.removed_deployments.push(path.to_string()),
+        Err(e) => report
+            .errors
+            .push(format!("Failed to remove {}: {}", path, e)),
+    }
+}
+
+/// Returns the app to first-run defaults: clears every persisted config file, the
+/// deployment registry and log files. Deployment and model folders on disk are kept
+/// unless `remove_deployments` is set. Nothing happens unless `confirm` is true.
+#[tauri::command(rename_all = "snake_case")]
+pub fn factory_reset(
+    app_handle: AppHandle,
+    remove_deployments: bool,
+    confirm: bool,
+) -> Result<FactoryResetReport, String> {
+    if !confirm {
+        return Err("Factory reset was not confirmed; nothing was changed.".to_string());
+    }
+    if app_handle.state::<ServerChildProcess>().lock().is_some() {
+        return Err("Stop the IndexTTS server before resetting the app.".to_string());
+    }
+
+    let mut report = FactoryResetReport::default();
+    // Read the registry before it is wiped so the folders can still be found.
+    let deployments = load_deployments(&app_handle).unwrap_or_default();
+
+    for file_name in PERSISTED_FILES {
+        match remove_json(&app_handle, file_name) {
+            Ok(true) => report.removed_files.push(file_name.to_string()),
+            Ok(false) => {}
+            Err(e) => report.errors.push(e),
+        }
+    }
+    let (backups, backup_errors) = remove_migration_backups(&app_handle);
+    report.removed_files.extend(backups);
+    report.errors.extend(backup_errors);
+    report.removed_files.extend(remove_log_files(&app_handle));
+
+    for deployment in deployments {
+        if !remove_deployments {
+            report.kept_deployments.push(deployment.target_dir);
+            continue;
+        }
+        remove_dir(&deployment.target_dir, &mut report);
+        if let Some(model_dir) = deployment.model_dir {
+            // A model dir inside the deployment is already gone with it.
+            if !Path::new(&model_dir).starts_with(&deployment.target_dir) {
+                remove_dir(&model_dir, &mut report);
+            }
+        }
+    }
+
+    *app_handle.state::<EngineManager>().lock() = EngineConfig::default();
+    app_handle.state::<DeployLogBuffer>().clear();
+
+    Ok(report)
+}
@@ -0,0 +1,124 @@
+// src-tauri/src/commands/fd_limits.rs
+
+use super::git_settings::RECOMMENDED_GIT_SETTINGS;
+use serde::Serialize;
+
+// Descriptors the app, git and uv hold regardless of transfer concurrency.
+const FD_BASELINE: u64 = 512;
+// Sockets, pack/temp files and pipes per concurrent LFS transfer or download.
+const FDS_PER_TRANSFER: u64 = 64;
+// macOS rejects soft limits above OPEN_MAX even when the hard limit is unlimited.
+#[cfg(unix)]
+const FD_RAISE_TARGET: u64 = 10240;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FdLimitStatus {
+    // False on Windows, where handles aren't limited this way.
+    pub supported: bool,
+    pub soft: Option<u64>,
+    pub hard: Option<u64>,
+    pub required: u64,
+    pub raised: bool,
+    pub sufficient: bool,
+    pub warning: Option<String>,
+}
+
+fn planned_concurrency() -> u64 {
+    RECOMMENDED_GIT_SETTINGS
+        .iter()
+        .find(|(key, _)| *key == "lfs.concurrenttransfers")
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(8)
+}
+
+#[cfg(unix)]
+fn read_limit() -> Option<(u64, u64)> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid, writable rlimit for the duration of the call.
+    let result = unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) };
+    (result == 0).then_some((limit.rlim_cur, limit.rlim_max))
+}
+
+#[cfg(unix)]
+fn set_soft_limit(soft: u64, hard: u64) -> bool {
+    let limit = libc::rlimit {
+        rlim_cur: soft,
+        rlim_max: hard,
+    };
+    // SAFETY: `limit` is a valid rlimit; only this process (and its future children) is affected.
+    unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) == 0 }
+}
+
+/// Compares the open-file limit against what the planned LFS/download concurrency
+/// needs. With `raise`, a low soft limit is lifted (up to the hard limit) for this
+/// process, which child processes inherit; otherwise the `ulimit` fix is returned.
+#[cfg(unix)]
+pub fn check_fd_limit(concurrency: Option<u64>, raise: bool) -> FdLimitStatus {
+    let required = FD_BASELINE + concurrency.unwrap_or_else(planned_concurrency) * FDS_PER_TRANSFER;
+    let Some((mut soft, hard)) = read_limit() else {
+        return FdLimitStatus {
+            supported: true,
+            soft: None,
+            hard: None,
+            required,
+            raised: false,
+            sufficient: true,
+            warning: None,
+        };
+    };
+
+    let mut raised = false;
+    if soft < required && raise {
+        let target = FD_RAISE_TARGET.max(required).min(hard);
+        if target > soft && set_soft_limit(target, hard) {
+            soft = target;
+            raised = true;
+        }
+    }
+    let sufficient = soft >= required;
+    FdLimitStatus {
+        supported: true,
+        soft: Some(soft),
+        hard: Some(hard),
+        required,
+        raised,
+        sufficient,
+        warning: (!sufficient).then(|| {
+            format!(
+                "The open-file limit is {} but about {} is needed for concurrent LFS transfers and downloads; large pulls may fail with \"Too many open files\". Run `ulimit -n {}` in the shell that starts the app{}.",
+                soft,
+                required,
+                required,
+                if hard < required {
+                    format!(" (the hard limit is {}, so raise it in /etc/security/limits.conf or with launchctl limit maxfiles first)", hard)
+                } else {
+                    String::new()
+                }
+            )
+        }),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn check_fd_limit(concurrency: Option<u64>, _raise: bool) -> FdLimitStatus {
+    FdLimitStatus {
+        supported: false,
+        soft: None,
+        hard: None,
+        required: FD_BASELINE + concurrency.unwrap_or_else(planned_concurrency) * FDS_PER_TRANSFER,
+        raised: false,
+        sufficient: true,
+        warning: None,
+    }
+}
+
+#[tauri::command]
+pub fn check_file_descriptor_limit(
+    concurrency: Option<u64>,
+    raise: Option<bool>,
+) -> Result<FdLimitStatus, String> {
+    Ok(check_fd_limit(concurrency, raise.unwrap_or(false)))
+}
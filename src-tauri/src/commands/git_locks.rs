@@ -0,0 +1,106 @@
+// src-tauri/src/commands/git_locks.rs
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use sysinfo::System;
+
+// A lock this fresh may belong to a git process that is just starting up.
+const STALE_LOCK_MIN_AGE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct GitLockReport {
+    pub cleared: Vec<String>,
+    pub held: Vec<String>,
+}
+
+/// Whether git output is the "another git process seems to be running" failure.
+pub fn is_git_lock_error(output: &str) -> bool {
+    output.contains("Another git process seems to be running")
+        || (output.contains(".lock") && output.contains("File exists"))
+}
+
+fn collect_lock_files(dir: &Path, locks: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            // Object storage is huge and never holds lock files.
+            if entry.file_name() != "objects" {
+                collect_lock_files(&path, locks);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "lock") {
+            locks.push(path);
+        }
+    }
+}
+
+/// True when a git (or git-lfs / git-remote-*) process may be working in `repo_path`.
+/// Processes whose working directory can't be read count as active, to stay on the safe side.
+fn git_process_active_in(repo_path: &Path) -> bool {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    let repo_text = repo_path.to_string_lossy().to_string();
+
+    sys.processes().values().any(|process| {
+        if !process.name().to_lowercase().starts_with("git") {
+            return false;
+        }
+        match process.cwd() {
+            Some(cwd) if !cwd.as_os_str().is_empty() => {
+                cwd.starts_with(repo_path)
+                    || process.cmd().iter().any(|arg| arg.contains(&repo_text))
+            }
+            _ => true,
+        }
+    })
+}
+
+fn is_fresh(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < STALE_LOCK_MIN_AGE)
+}
+
+/// Removes lock files left in `.git` by interrupted git/LFS operations. Locks are left
+/// alone while a git process is running in the repository.
+pub fn clear_stale_git_locks(target_dir: &str) -> Result<GitLockReport, String> {
+    let repo_path = Path::new(target_dir);
+    let git_dir = repo_path.join(".git");
+    if !git_dir.is_dir() {
+        return Err(format!("{} is not a git repository.", target_dir));
+    }
+
+    let mut locks = Vec::new();
+    collect_lock_files(&git_dir, &mut locks);
+    let mut report = GitLockReport::default();
+    if locks.is_empty() {
+        return Ok(report);
+    }
+
+    let resolved_repo = repo_path
+        .canonicalize()
+        .unwrap_or_else(|_| repo_path.to_path_buf());
+    let git_running = git_process_active_in(&resolved_repo);
+
+    for lock in locks {
+        let display = lock.display().to_string();
+        if git_running || is_fresh(&lock) {
+            report.held.push(display);
+            continue;
+        }
+        fs::remove_file(&lock).map_err(|e| format!("Failed to remove {}: {}", display, e))?;
+        report.cleared.push(display);
+    }
+    Ok(report)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_git_locks(target_dir: String) -> Result<GitLockReport, String> {
+    clear_stale_git_locks(&target_dir)
+}
@@ -0,0 +1,215 @@
+// src-tauri/src/commands/git_settings.rs
+
+use super::command_utils::new_command;
+use super::settings::load_settings;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+// Repo-local settings that make large LFS clones faster and less fragile.
+pub const RECOMMENDED_GIT_SETTINGS: [(&str, &str); 3] = [
+    // LFS objects are already compressed; recompressing only burns CPU.
+    ("core.compression", "0"),
+    // Avoids "RPC failed; HTTP 400" on large pushes/fetches over some proxies.
+    ("http.postBuffer", "524288000"),
+    ("lfs.concurrenttransfers", "8"),
+];
+
+// Repo-local identity used when none is configured, so merges and stashes don't stop
+// at "Please tell me who you are".
+const FALLBACK_IDENTITY: [(&str, &str); 2] = [
+    ("user.name", "IndexTTS Hub"),
+    ("user.email", "indextts-hub@localhost"),
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GitIdentityStatus {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    // Keys that had no value in any scope before this check.
+    pub missing: Vec<String>,
+    pub set_locally: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GitSettingChange {
+    pub key: String,
+    pub previous: Option<String>,
+    pub value: String,
+    pub changed: bool,
+}
+
+/// Recommended settings with `overrides` applied. Unknown keys are rejected so this
+/// can't be used to set arbitrary git config.
+pub fn resolve_git_settings(
+    overrides: Option<HashMap<String, String>>,
+) -> Result<Vec<(String, String)>, String> {
+    let mut overrides = overrides.unwrap_or_default();
+    if let Some(unknown) = overrides.keys().find(|key| {
+        !RECOMMENDED_GIT_SETTINGS
+            .iter()
+            .any(|(known, _)| known == key)
+    }) {
+        return Err(format!("Unsupported git setting '{}'.", unknown));
+    }
+
+    Ok(RECOMMENDED_GIT_SETTINGS
+        .iter()
+        .map(|(key, value)| {
+            let value = overrides.remove(*key).unwrap_or_else(|| value.to_string());
+            (key.to_string(), value)
+        })
+        .collect())
+}
+
+/// The `lfs.concurrenttransfers` value saved from `apply_recommended_settings`, if any.
+pub fn saved_lfs_concurrency(app_handle: &AppHandle) -> Option<usize> {
+    load_settings(app_handle).lfs_concurrent_transfers
+}
+
+/// Recommended settings with the saved LFS concurrency and then `overrides` applied.
+pub fn app_git_settings(
+    app_handle: &AppHandle,
+    overrides: Option<HashMap<String, String>>,
+) -> Result<Vec<(String, String)>, String> {
+    let mut overrides = overrides.unwrap_or_default();
+    if let Some(transfers) = saved_lfs_concurrency(app_handle) {
+        overrides
+            .entry("lfs.concurrenttransfers".to_string())
+            .or_insert_with(|| transfers.to_string());
+    }
+    resolve_git_settings(Some(overrides))
+}
+
+async fn read_local_config(target_dir: &str, key: &str) -> Option<String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(["config", "--local", "--get", key])
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Effective value of `key` across system, global and repo config.
+async fn read_effective_config(target_dir: &str, key: &str) -> Option<String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(["config", "--get", key])
+        .output()
+        .await
+        .ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+async fn write_local_config(target_dir: &str, key: &str, value: &str) -> Result<(), String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(["config", "--local", key, value])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute git config: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to set {}: {}",
+            key,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Reports whether git has a user identity for `target_dir` and, with `apply`, fills
+/// the missing parts with a throwaway identity in the repo's own config. Global config
+/// is never touched.
+pub async fn ensure_repo_identity(
+    target_dir: &str,
+    apply: bool,
+) -> Result<GitIdentityStatus, String> {
+    let mut status = GitIdentityStatus {
+        name: read_effective_config(target_dir, "user.name").await,
+        email: read_effective_config(target_dir, "user.email").await,
+        missing: Vec::new(),
+        set_locally: false,
+        message: String::new(),
+    };
+    for (key, fallback) in FALLBACK_IDENTITY {
+        let value = if key == "user.name" {
+            &mut status.name
+        } else {
+            &mut status.email
+        };
+        if value.is_some() {
+            continue;
+        }
+        status.missing.push(key.to_string());
+        if apply {
+            write_local_config(target_dir, key, fallback).await?;
+            *value = Some(fallback.to_string());
+            status.set_locally = true;
+        }
+    }
+    status.message = if status.missing.is_empty() {
+        "Git identity is configured.".to_string()
+    } else if status.set_locally {
+        format!(
+            "No git {} was configured; a placeholder was set in this repository only.",
+            status.missing.join(" / ")
+        )
+    } else {
+        format!(
+            "No git {} is configured. Clone and pull work, but merges and stashes will fail until one is set.",
+            status.missing.join(" / ")
+        )
+    };
+    Ok(status)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_git_identity(
+    target_dir: String,
+    apply: Option<bool>,
+) -> Result<GitIdentityStatus, String> {
+    if !Path::new(&target_dir).join(".git").exists() {
+        return Err(format!("{} is not a git repository.", target_dir));
+    }
+    ensure_repo_identity(&target_dir, apply.unwrap_or(false)).await
+}
+
+/// Writes the LFS-friendly settings into the repository's own config (never global)
+/// and reports what changed.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn optimize_git_settings(
+    app_handle: AppHandle,
+    target_dir: String,
+    overrides: Option<HashMap<String, String>>,
+) -> Result<Vec<GitSettingChange>, String> {
+    if !Path::new(&target_dir).join(".git").exists() {
+        return Err(format!("{} is not a git repository.", target_dir));
+    }
+
+    let mut changes = Vec::new();
+    for (key, value) in app_git_settings(&app_handle, overrides)? {
+        let previous = read_local_config(&target_dir, &key).await;
+        let changed = previous.as_deref() != Some(value.as_str());
+        if changed {
+            write_local_config(&target_dir, &key, &value).await?;
+        }
+        changes.push(GitSettingChange {
+            key,
+            previous,
+            value,
+            changed,
+        });
+    }
+    Ok(changes)
+}
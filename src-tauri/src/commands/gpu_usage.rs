@@ -0,0 +1,356 @@
+// src-tauri/src/commands/gpu_usage.rs
+
+use super::command_utils::new_command;
+use super::deployments::unix_timestamp;
+use super::index_tts::run_gpu_check;
+use serde::Serialize;
+
+// IndexTTS-2 needs a few GB for weights and activations; less than this free will OOM.
+const LOW_FREE_VRAM_MB: u64 = 4096;
+// Approximate peak VRAM of IndexTTS-2 inference (weights plus activations) per precision.
+const INDEX_TTS2_VRAM_MB: [(&str, u64); 2] = [("fp16", 6 * 1024), ("fp32", 10 * 1024)];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,
+    pub used_memory_mb: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GpuDevice {
+    pub index: u32,
+    pub uuid: String,
+    pub name: String,
+    pub memory_total_mb: Option<u64>,
+    pub memory_used_mb: Option<u64>,
+    pub memory_free_mb: Option<u64>,
+    pub utilization_percent: Option<u32>,
+    pub processes: Vec<GpuProcess>,
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct GpuUsage {
+    pub available: bool,
+    pub devices: Vec<GpuDevice>,
+    // The card with the most free memory, a good default for `gpu_index`.
+    pub least_loaded_index: Option<u32>,
+    pub error: Option<String>,
+}
+
+/// Driver and GPU state at one moment, taken when the server crashes.
+#[derive(Debug, Serialize, Clone)]
+pub struct GpuSnapshot {
+    pub driver_version: Option<String>,
+    // Highest CUDA version the driver supports, from the nvidia-smi banner.
+    pub cuda_version: Option<String>,
+    pub usage: GpuUsage,
+    pub captured_at: u64,
+}
+
+/// nvidia-smi prints `[N/A]` or `[Not Supported]` for values a device doesn't report.
+fn parse_field<T: std::str::FromStr>(value: &str) -> Option<T> {
+    value.trim().parse().ok()
+}
+
+/// Runs nvidia-smi with the given query. `Err` carries the failure text when the tool
+/// exists but can't talk to the driver; `Ok(None)` means nvidia-smi is not installed.
+async fn nvidia_smi_query(args: &[&str]) -> Result<Option<String>, String> {
+    let output = match new_command("nvidia-smi").args(args).output().await {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to run nvidia-smi: {}", e)),
+    };
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = if stderr.trim().is_empty() {
+            stdout.trim().to_string()
+        } else {
+            stderr.trim().to_string()
+        };
+        return Err(format!("nvidia-smi failed: {}", message));
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+}
+
+fn parse_devices(output: &str) -> Vec<GpuDevice> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            Some(GpuDevice {
+                index: parse_field(fields[0])?,
+                uuid: fields[1].to_string(),
+                name: fields[2].to_string(),
+                memory_total_mb: parse_field(fields[3]),
+                memory_used_mb: parse_field(fields[4]),
+                memory_free_mb: parse_field(fields[5]),
+                utilization_percent: parse_field(fields[6]),
+                processes: Vec::new(),
+                warning: None,
+            })
+        })
+        .collect()
+}
+
+fn attach_processes(devices: &mut [GpuDevice], output: &str) {
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let Some(pid) = parse_field(fields[1]) else {
+            continue;
+        };
+        if let Some(device) = devices.iter_mut().find(|device| device.uuid == fields[0]) {
+            device.processes.push(GpuProcess {
+                pid,
+                name: fields[2].to_string(),
+                used_memory_mb: parse_field(fields[3]),
+            });
+        }
+    }
+}
+
+pub async fn query_gpu_usage() -> GpuUsage {
+    let devices_output = match nvidia_smi_query(&[
+        "--query-gpu=index,uuid,name,memory.total,memory.used,memory.free,utilization.gpu",
+        "--format=csv,noheader,nounits",
+    ])
+    .await
+    {
+        Ok(Some(output)) => output,
+        Ok(None) => return GpuUsage::default(),
+        Err(error) => {
+            return GpuUsage {
+                error: Some(error),
+                ..GpuUsage::default()
+            }
+        }
+    };
+
+    let mut devices = parse_devices(&devices_output);
+    // Process listing is best effort; some drivers and WSL don't support it.
+    if let Ok(Some(apps)) = nvidia_smi_query(&[
+        "--query-compute-apps=gpu_uuid,pid,process_name,used_memory",
+        "--format=csv,noheader,nounits",
+    ])
+    .await
+    {
+        attach_processes(&mut devices, &apps);
+    }
+
+    for device in &mut devices {
+        if let Some(free) = device
+            .memory_free_mb
+            .filter(|free| *free < LOW_FREE_VRAM_MB)
+        {
+            device.warning = Some(format!(
+                "Only {:.1} GB free on GPU {} ({} process(es) using it); the server may run out of memory.",
+                free as f64 / 1024.0,
+                device.index,
+                device.processes.len()
+            ));
+        }
+    }
+
+    let least_loaded_index = devices
+        .iter()
+        .filter(|device| device.memory_free_mb.is_some())
+        .max_by_key(|device| device.memory_free_mb)
+        .map(|device| device.index);
+
+    GpuUsage {
+        available: !devices.is_empty(),
+        devices,
+        least_loaded_index,
+        error: None,
+    }
+}
+
+fn cuda_device_error(target_dir: &str, gpu_index: Option<u32>, usage: &GpuUsage) -> Option<String> {
+    if let Some(index) = gpu_index {
+        if usage.available && !usage.devices.iter().any(|device| device.index == index) {
+            let indices: Vec<String> = usage
+                .devices
+                .iter()
+                .map(|device| device.index.to_string())
+                .collect();
+            return Some(format!(
+                "GPU {} does not exist on this machine (available: {}).",
+                index,
+                indices.join(", ")
+            ));
+        }
+    }
+    if usage.available {
+        return None;
+    }
+    Some(match &usage.error {
+        Some(error) => format!(
+            "Device 'cuda' was selected but the NVIDIA driver is not responding ({}). Reinstall or update the driver, or start with device 'cpu'.",
+            error
+        ),
+        None => format!(
+            "Device 'cuda' was selected but no NVIDIA GPU was detected for {}. Start with device 'cpu' instead.",
+            target_dir
+        ),
+    })
+}
+
+/// Launch precondition: `device` must exist on this machine. `cpu` always does, `mps`
+/// needs Apple Silicon, and `cuda` needs a GPU seen by nvidia-smi or, when nvidia-smi
+/// is not on PATH (e.g. some WSL setups), by torch in the deployment's environment.
+pub async fn check_device_available(
+    target_dir: &str,
+    device: &str,
+    gpu_index: Option<u32>,
+) -> Result<(), String> {
+    match device {
+        "mps" if !cfg!(all(target_os = "macos", target_arch = "aarch64")) => Err(
+            "Device 'mps' needs a Mac with Apple Silicon. Start with device 'cpu' instead."
+                .to_string(),
+        ),
+        "cuda" => {
+            let usage = query_gpu_usage().await;
+            let Some(error) = cuda_device_error(target_dir, gpu_index, &usage) else {
+                return Ok(());
+            };
+            let torch_sees_cuda = !usage.available
+                && usage.error.is_none()
+                && run_gpu_check(target_dir.to_string())
+                    .await
+                    .is_ok_and(|info| info.has_cuda);
+            if torch_sees_cuda {
+                Ok(())
+            } else {
+                Err(error)
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn validate_device(
+    target_dir: String,
+    device: String,
+    gpu_index: Option<u32>,
+) -> Result<(), String> {
+    check_device_available(&target_dir, &device, gpu_index).await
+}
+
+/// `CUDA Version: 12.2` from the banner of plain `nvidia-smi`.
+fn parse_cuda_version(banner: &str) -> Option<String> {
+    let rest = &banner[banner.find("CUDA Version:")? + "CUDA Version:".len()..];
+    rest.split_whitespace()
+        .next()
+        .map(|version| version.trim_end_matches('|').to_string())
+        .filter(|version| !version.is_empty())
+}
+
+/// Driver version, supported CUDA version, VRAM and the processes on every GPU.
+pub async fn capture_gpu_snapshot() -> GpuSnapshot {
+    let driver_version = nvidia_smi_query(&["--query-gpu=driver_version", "--format=csv,noheader"])
+        .await
+        .ok()
+        .flatten()
+        .and_then(|output| output.lines().next().map(|line| line.trim().to_string()))
+        .filter(|version| !version.is_empty());
+    let cuda_version = nvidia_smi_query(&[])
+        .await
+        .ok()
+        .flatten()
+        .and_then(|banner| parse_cuda_version(&banner));
+    GpuSnapshot {
+        driver_version,
+        cuda_version,
+        usage: query_gpu_usage().await,
+        captured_at: unix_timestamp(),
+    }
+}
+
+/// Reports free VRAM and the compute processes on every NVIDIA GPU so a launch can be
+/// pointed at the least-loaded card.
+#[tauri::command]
+pub async fn get_gpu_usage() -> Result<GpuUsage, String> {
+    Ok(query_gpu_usage().await)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct VramEstimate {
+    pub precision: String,
+    pub required_mb: u64,
+    pub gpu_index: Option<u32>,
+    pub free_mb: Option<u64>,
+    // `None` when no GPU could be queried.
+    pub fits: Option<bool>,
+    pub recommendation: Option<String>,
+}
+
+pub fn vram_required_mb(precision: &str) -> Result<u64, String> {
+    INDEX_TTS2_VRAM_MB
+        .iter()
+        .find(|(name, _)| *name == precision)
+        .map(|(_, mb)| *mb)
+        .ok_or_else(|| {
+            format!(
+                "Unsupported precision '{}'. Expected one of: fp16, fp32.",
+                precision
+            )
+        })
+}
+
+/// Compares the estimated need for `precision` with the free VRAM of `gpu_index`
+/// (default: the least-loaded GPU).
+pub async fn estimate_vram(
+    precision: &str,
+    gpu_index: Option<u32>,
+) -> Result<VramEstimate, String> {
+    let required_mb = vram_required_mb(precision)?;
+    let usage = query_gpu_usage().await;
+    let device = gpu_index
+        .or(usage.least_loaded_index)
+        .and_then(|index| usage.devices.iter().find(|device| device.index == index));
+    let free_mb = device.and_then(|device| device.memory_free_mb);
+    let fits = free_mb.map(|free| free >= required_mb);
+
+    let recommendation = (fits == Some(false)).then(|| {
+        let free_mb = free_mb.unwrap_or(0);
+        let fp16_fits = precision != "fp16" && free_mb >= vram_required_mb("fp16").unwrap_or(0);
+        format!(
+            "{} needs about {:.1} GB but only {:.1} GB is free; {}.",
+            precision,
+            required_mb as f64 / 1024.0,
+            free_mb as f64 / 1024.0,
+            if fp16_fits {
+                "use fp16 instead"
+            } else {
+                "free up GPU memory or run on the CPU"
+            }
+        )
+    });
+
+    Ok(VramEstimate {
+        precision: precision.to_string(),
+        required_mb,
+        gpu_index: device.map(|device| device.index),
+        free_mb,
+        fits,
+        recommendation,
+    })
+}
+
+/// Approximate VRAM IndexTTS-2 needs at `precision`, checked against the detected free VRAM.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn estimate_vram_required(
+    precision: String,
+    gpu_index: Option<u32>,
+) -> Result<VramEstimate, String> {
+    estimate_vram(&precision, gpu_index).await
+}
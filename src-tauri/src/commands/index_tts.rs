@@ -1,40 +1,99 @@
 // src-tauri/src/commands/index_tts.rs
 
-use super::command_utils::{configure_command, new_command};
+use super::command_history::record_command;
+use super::command_utils::{clean_path_arg, configure_command, new_command};
+use super::deploy_error::{
+    classify_native_build_failure, classify_resolution_conflict, DeployError,
+};
+use super::deploy_log::DeployLogBuffer;
+use super::dir_lock::acquire_dir_lock;
+use super::download_accel::{hf_tool_spec, hf_transfer_installed};
+use super::download_mode::{
+    duplicate_copy_warning, footprint_mb, hf_snapshot_dir, link_snapshot, DownloadMode,
+};
+use super::download_progress::ModelFileTracker;
+use super::engine::{model_dir_within_repo, EngineManager};
+use super::git_locks::clear_stale_git_locks;
+use super::git_settings::app_git_settings;
+use super::install_tools::emit_install_tools_log;
+use super::jobs::{current_job_id, scope_job};
+use super::lfs::{lfs_smudge_skipped, lfs_status, set_lfs_smudge_skipped};
+use super::log_files::{append_log_line, DEPLOY_LOG};
+use super::model_layout::{locate_model, record_model_location};
+use super::repo_archive::{download_repo_archive, is_archive_checkout, CloneMethod};
+use super::repo_remote::{check_repo_remote, expected_repo_url, set_origin_url};
+use super::settings::load_settings;
+use super::step_cancel::track_step_process;
+use super::system_info::{
+    check_linux_compat, ensure_free_space, recommend_fp16, required_free_disk_gb,
+};
+use super::torch_build::{torch_index_url, TORCH_INDEX_NAME};
+use super::uv_cache::apply_uv_cache_dir;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::path::PathBuf;
-use std::process::Stdio;
+use std::process::{ExitStatus, Stdio};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use std::{env, fs};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
 use tokio::process::Command;
+use tokio::time::sleep;
 
 const CORE_DEPLOY_EVENT: &str = "core-deploy-log";
 
 #[derive(Debug, Serialize, Clone)]
 pub struct CoreDeployLogEvent {
+    pub seq: u64,
+    pub job_id: Option<String>,
     pub step: String,
     pub stream: String,
     pub line: String,
 }
 
-fn emit_core_deploy_log(app_handle: &AppHandle, step: &str, stream: &str, line: &str) {
+pub fn emit_core_deploy_log(app_handle: &AppHandle, step: &str, stream: &str, line: &str) {
+    append_log_line(
+        app_handle,
+        DEPLOY_LOG,
+        &format!("{}/{}", step, stream),
+        line,
+    );
     let payload = CoreDeployLogEvent {
+        seq: 0,
+        job_id: current_job_id(),
         step: step.to_string(),
         stream: stream.to_string(),
         line: line.to_string(),
     };
+    let payload = match app_handle.try_state::<DeployLogBuffer>() {
+        Some(buffer) => buffer.push(payload),
+        None => payload,
+    };
     let _ = app_handle.emit(CORE_DEPLOY_EVENT, payload);
 }
 
-fn repo_has_core_files(path: &Path) -> bool {
+pub const MODEL_REPO_ID: &str = "IndexTeam/IndexTTS-2";
+
+pub const MODEL_CORE_FILES: [&str; 5] = [
+    "config.yaml",
+    "gpt.pth",
+    "s2mel.pth",
+    "bpe.model",
+    "wav2vec2bert_stats.pt",
+];
+
+pub fn repo_has_core_files(path: &Path) -> bool {
     let pyproject = path.join("pyproject.toml");
     let webui = path.join("webui.py");
     pyproject.exists() && webui.exists()
 }
 
+/// Checks that a model directory contains the files webui.py refuses to start without.
+pub fn model_has_core_files(path: &Path) -> bool {
+    MODEL_CORE_FILES.iter().all(|file| path.join(file).is_file())
+}
+
 fn directory_is_empty(path: &Path) -> Result<bool, String> {
     match fs::read_dir(path) {
         Ok(mut entries) => Ok(entries.next().is_none()),
@@ -46,23 +105,91 @@ fn directory_is_empty(path: &Path) -> Result<bool, String> {
     }
 }
 
-async fn run_command_with_streaming(
+/// Callback invoked with every line a streamed command prints, on either stream.
+pub type LineHook = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Event channel the lines of a streamed command are emitted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogChannel {
+    CoreDeploy,
+    InstallTools,
+}
+
+impl LogChannel {
+    fn emit(self, app_handle: &AppHandle, step: &str, stream: &str, line: &str) {
+        match self {
+            LogChannel::CoreDeploy => emit_core_deploy_log(app_handle, step, stream, line),
+            LogChannel::InstallTools => emit_install_tools_log(app_handle, step, stream, line),
+        }
+    }
+}
+
+/// How a streamed command ended, with its stderr kept for error messages.
+pub struct StreamedExit {
+    pub status: ExitStatus,
+    pub stderr: String,
+}
+
+pub async fn run_command_with_streaming(
     app_handle: &AppHandle,
     step: &str,
-    mut command: Command,
+    command: Command,
 ) -> Result<(), String> {
+    run_command_with_line_hook(app_handle, step, command, None).await
+}
+
+async fn run_command_with_line_hook(
+    app_handle: &AppHandle,
+    step: &str,
+    command: Command,
+    line_hook: Option<LineHook>,
+) -> Result<(), String> {
+    let exit = stream_command(app_handle, step, command, LogChannel::CoreDeploy, line_hook).await?;
+    if !exit.status.success() {
+        return Err(format!("{} failed: {}", step, exit.stderr));
+    }
+    Ok(())
+}
+
+/// Spawns `command`, emits every line on `channel` as it is printed and waits for it
+/// to exit. A non-zero exit is returned as is; only spawn failures and cancellation
+/// through `cancel_deploy_step` are errors.
+pub async fn stream_command(
+    app_handle: &AppHandle,
+    step: &str,
+    mut command: Command,
+    channel: LogChannel,
+    line_hook: Option<LineHook>,
+) -> Result<StreamedExit, String> {
     configure_command(&mut command);
+    record_command(app_handle, step, &command);
     command.stdout(Stdio::piped());
     command.stderr(Stdio::piped());
+    // A cancelled job drops this future; take the child process down with it.
+    command.kill_on_drop(true);
+    // Own process group so `cancel_deploy_step` also reaches git-lfs/uv children.
+    #[cfg(unix)]
+    command.process_group(0);
 
     let mut child = command
         .spawn()
         .map_err(|e| format!("Failed to spawn {}: {}", step, e))?;
+    let pid = child.id();
+    // Unregisters on drop as well, when this future is aborted mid-step.
+    let tracked = track_step_process(app_handle, step, pid);
 
     let stderr_accumulator: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
 
     if let Some(stdout) = child.stdout.take() {
-        spawn_stream_reader(stdout, app_handle.clone(), step.to_string(), "stdout", None);
+        spawn_stream_reader(
+            stdout,
+            app_handle.clone(),
+            step.to_string(),
+            "stdout",
+            channel,
+            None,
+            line_hook.clone(),
+        );
     }
 
     if let Some(stderr) = child.stderr.take() {
@@ -71,24 +198,24 @@ async fn run_command_with_streaming(
             app_handle.clone(),
             step.to_string(),
             "stderr",
+            channel,
             Some(stderr_accumulator.clone()),
+            line_hook,
         );
     }
 
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| format!("Failed to wait for {}: {}", step, e))?;
-
-    if !status.success() {
-        let stderr_output = stderr_accumulator
-            .lock()
-            .map(|buf| buf.join("\n"))
-            .unwrap_or_else(|_| "command failed".to_string());
-        return Err(format!("{} failed: {}", step, stderr_output));
+    let status = child.wait().await;
+    if tracked.finish() {
+        channel.emit(app_handle, step, "stderr", "已取消。");
+        return Err(format!("{} was cancelled.", step));
     }
+    let status = status.map_err(|e| format!("Failed to wait for {}: {}", step, e))?;
 
-    Ok(())
+    let stderr = stderr_accumulator
+        .lock()
+        .map(|buf| buf.join("\n"))
+        .unwrap_or_else(|_| "command failed".to_string());
+    Ok(StreamedExit { status, stderr })
 }
 
 fn spawn_stream_reader<R>(
@@ -96,11 +223,13 @@ fn spawn_stream_reader<R>(
     app_handle: AppHandle,
     step_name: String,
     stream_name: &'static str,
+    channel: LogChannel,
     buffer: Option<Arc<Mutex<Vec<String>>>>,
+    line_hook: Option<LineHook>,
 ) where
     R: AsyncRead + Unpin + Send + 'static,
 {
-    tokio::spawn(async move {
+    tokio::spawn(scope_job(current_job_id(), async move {
         let mut reader = BufReader::new(stream);
         let mut chunk = vec![0u8; 4096];
         let mut carry = String::new();
@@ -113,8 +242,10 @@ fn spawn_stream_reader<R>(
                             &app_handle,
                             &step_name,
                             stream_name,
+                            channel,
                             &carry,
                             buffer.as_ref(),
+                            line_hook.as_ref(),
                         );
                         carry.clear();
                     }
@@ -131,33 +262,78 @@ fn spawn_stream_reader<R>(
                     while let Some(pos) = carry.find('\n') {
                         let line = carry[..pos].to_string();
                         carry.drain(..=pos);
-                        forward_line(&app_handle, &step_name, stream_name, &line, buffer.as_ref());
+                        forward_line(
+                            &app_handle,
+                            &step_name,
+                            stream_name,
+                            channel,
+                            &line,
+                            buffer.as_ref(),
+                            line_hook.as_ref(),
+                        );
                     }
                 }
                 Err(_) => break,
             }
         }
-    });
+    }));
 }
 
 fn forward_line(
     app_handle: &AppHandle,
     step_name: &str,
     stream_name: &str,
+    channel: LogChannel,
     line: &str,
     buffer: Option<&Arc<Mutex<Vec<String>>>>,
+    line_hook: Option<&LineHook>,
 ) {
     if let Some(buf) = buffer {
         if let Ok(mut guard) = buf.lock() {
             guard.push(line.to_string());
         }
     }
+    if let Some(hook) = line_hook {
+        hook(line);
+    }
     if !line.is_empty() {
-        emit_core_deploy_log(app_handle, step_name, stream_name, line);
+        channel.emit(app_handle, step_name, stream_name, line);
     }
 }
 
+/// Repo-relative directories `git clean` must not touch: the configured model dir when it
+/// is nested in the repo, the default checkpoints folder and the virtualenv.
+fn repair_exclusions(app_handle: &AppHandle, target_dir: &str) -> Vec<String> {
+    let repo_path = Path::new(target_dir);
+    let mut exclusions = vec!["checkpoints".to_string(), ".venv".to_string()];
+    let model_dir = app_handle
+        .try_state::<EngineManager>()
+        .map(|engine| engine.lock().model_dir.clone())
+        .unwrap_or_default();
+    if !model_dir.is_empty() {
+        if let Some(relative) = model_dir_within_repo(repo_path, Path::new(&model_dir)) {
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if !exclusions.contains(&relative) {
+                exclusions.push(relative);
+            }
+        }
+    }
+    exclusions.retain(|relative| repo_path.join(relative).is_dir());
+    exclusions
+}
+
 async fn repair_existing_repo(app_handle: &AppHandle, target_dir: &str) -> Result<(), String> {
+    // An interrupted earlier run may have left .git/index.lock behind.
+    let locks = clear_stale_git_locks(target_dir)?;
+    if !locks.cleared.is_empty() {
+        emit_core_deploy_log(
+            app_handle,
+            "clone_repo",
+            "stdout",
+            &format!("已清理残留的 git 锁文件: {}", locks.cleared.join(", ")),
+        );
+    }
+
     let mut reset_cmd = new_command("git");
     reset_cmd
         .arg("-C")
@@ -167,18 +343,91 @@ async fn repair_existing_repo(app_handle: &AppHandle, target_dir: &str) -> Resul
 
     let mut clean_cmd = new_command("git");
     clean_cmd.arg("-C").arg(target_dir).args(["clean", "-fdx"]);
+    // `-x` would also delete the ignored model files and venv inside the repo.
+    for keep in repair_exclusions(app_handle, target_dir) {
+        emit_core_deploy_log(
+            app_handle,
+            "clone_repo",
+            "stdout",
+            &format!("保留目录: {}", keep),
+        );
+        clean_cmd.arg("-e").arg(format!("/{}/", keep));
+    }
     run_command_with_streaming(app_handle, "repair_repo_clean", clean_cmd).await?;
 
     Ok(())
 }
 
+/// Re-points origin and checks out the remote's default branch, discarding the code
+/// of the previously cloned repository.
+async fn switch_to_expected_remote(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    expected: &str,
+) -> Result<(), String> {
+    set_origin_url(target_dir, expected).await?;
+
+    let mut fetch_cmd = new_command("git");
+    fetch_cmd
+        .arg("-C")
+        .arg(target_dir)
+        .args(["fetch", "origin", "HEAD"]);
+    run_command_with_streaming(app_handle, "clone_repo", fetch_cmd).await?;
+
+    let mut reset_cmd = new_command("git");
+    reset_cmd
+        .arg("-C")
+        .arg(target_dir)
+        .args(["reset", "--hard", "FETCH_HEAD"]);
+    run_command_with_streaming(app_handle, "clone_repo", reset_cmd).await?;
+    Ok(())
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn clone_index_tts_repo(
     app_handle: AppHandle,
     target_dir: String,
+    force: Option<bool>,
+    skip_lfs: Option<bool>,
+    clone_method: Option<String>,
+    init_git: Option<bool>,
 ) -> Result<String, String> {
-    let repo_url = "https://github.com/index-tts/index-tts.git";
+    let target_dir = clean_path_arg(&target_dir);
+    let repo_url = expected_repo_url(&app_handle);
     let target_path = Path::new(&target_dir);
+    let clone_method = CloneMethod::parse(clone_method.as_deref())?;
+    let _dir_lock = acquire_dir_lock(&target_dir, "clone_index_tts_repo")?;
+
+    if clone_method == CloneMethod::Archive {
+        if repo_has_core_files(target_path) {
+            emit_core_deploy_log(
+                &app_handle,
+                "clone_repo",
+                "stdout",
+                "目标目录已存在，跳过下载。",
+            );
+            return Ok("SUCCESS".to_string());
+        }
+        if target_path.exists() && !directory_is_empty(target_path)? {
+            return Err(format!(
+                "Target directory '{}' exists and contains files. Please choose an empty directory or clean it before retrying.",
+                target_dir
+            ));
+        }
+        ensure_free_space(
+            target_path,
+            required_free_disk_gb(&app_handle),
+            "the repository and its environment",
+        )?;
+        download_repo_archive(
+            &app_handle,
+            &target_dir,
+            &repo_url,
+            init_git.unwrap_or(false),
+        )
+        .await?;
+        return Ok("SUCCESS".to_string());
+    }
 
     // Check if the directory already exists
     if target_path.exists() && target_path.is_dir() {
@@ -193,6 +442,27 @@ pub async fn clone_index_tts_repo(
             .map_err(|e| format!("Failed to check if {} is a git repo: {}", target_dir, e))?;
 
         if is_git_repo.status.success() {
+            let remote = check_repo_remote(&app_handle, &target_dir).await;
+            if !remote.matches {
+                let actual = remote.actual.as_deref().unwrap_or("(no origin)");
+                if !force.unwrap_or(false) {
+                    return Err(format!(
+                        "The repository at '{}' has origin {}, but {} was expected. Use set_repo_remote or retry with force to switch it.",
+                        target_dir, actual, remote.expected
+                    ));
+                }
+                emit_core_deploy_log(
+                    &app_handle,
+                    "clone_repo",
+                    "stderr",
+                    &format!(
+                        "仓库远程地址不匹配 ({})，切换到 {}",
+                        actual, remote.expected
+                    ),
+                );
+                switch_to_expected_remote(&app_handle, &target_dir, &remote.expected).await?;
+            }
+
             if repo_has_core_files(target_path) {
                 emit_core_deploy_log(
                     &app_handle,
@@ -264,14 +534,40 @@ pub async fn clone_index_tts_repo(
         }
     }
 
+    ensure_free_space(
+        target_path,
+        required_free_disk_gb(&app_handle),
+        "the repository and its environment",
+    )?;
+
     let mut command = new_command("git");
-    command.args(["clone", repo_url, &target_dir]);
+    command.arg("clone");
+    // `-c` on clone writes the settings into the new repository's local config.
+    for (key, value) in app_git_settings(&app_handle, None)? {
+        command.arg("-c").arg(format!("{}={}", key, value));
+    }
+    command.arg(&repo_url).arg(&target_dir);
+    let skip_lfs = skip_lfs.unwrap_or(false);
+    if skip_lfs {
+        // Thin clone: LFS files stay as pointers and the model download provides the weights.
+        command.env("GIT_LFS_SKIP_SMUDGE", "1");
+        emit_core_deploy_log(
+            &app_handle,
+            "clone_repo",
+            "stdout",
+            "已跳过 LFS 文件下载，模型权重将通过模型下载步骤获取。",
+        );
+    }
     run_command_with_streaming(&app_handle, "clone_repo", command).await?;
+    set_lfs_smudge_skipped(&target_dir, skip_lfs)?;
     Ok("SUCCESS".to_string())
 }
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn init_git_lfs(app_handle: AppHandle, target_dir: String) -> Result<String, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    let _dir_lock = acquire_dir_lock(&target_dir, "init_git_lfs")?;
+
     let mut install_cmd = new_command("git");
     install_cmd
         .arg("-C")
@@ -279,6 +575,26 @@ pub async fn init_git_lfs(app_handle: AppHandle, target_dir: String) -> Result<S
         .args(["lfs", "install"]);
     run_command_with_streaming(&app_handle, "init_lfs", install_cmd).await?;
 
+    if is_archive_checkout(&target_dir) && !Path::new(&target_dir).join(".git").is_dir() {
+        emit_core_deploy_log(
+            &app_handle,
+            "init_lfs",
+            "stdout",
+            "仓库来自源码压缩包，跳过 Git LFS 初始化。",
+        );
+        return Ok("SUCCESS".to_string());
+    }
+
+    if lfs_smudge_skipped(&target_dir) {
+        emit_core_deploy_log(
+            &app_handle,
+            "init_lfs",
+            "stdout",
+            "仓库以精简模式克隆，跳过 git lfs pull。",
+        );
+        return Ok("SUCCESS".to_string());
+    }
+
     let mut pull_cmd = new_command("git");
     pull_cmd.arg("-C").arg(&target_dir).args(["lfs", "pull"]);
     run_command_with_streaming(&app_handle, "init_lfs", pull_cmd).await?;
@@ -286,6 +602,74 @@ pub async fn init_git_lfs(app_handle: AppHandle, target_dir: String) -> Result<S
     Ok("SUCCESS".to_string())
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub struct LfsRepairReport {
+    pub repaired: Vec<String>,
+    pub still_pointers: Vec<String>,
+}
+
+/// Materializes LFS files that were left as pointer stubs (failed or skipped LFS pull)
+/// and reports which files were fixed.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn repair_lfs(
+    app_handle: AppHandle,
+    target_dir: String,
+) -> Result<LfsRepairReport, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    let _dir_lock = acquire_dir_lock(&target_dir, "repair_lfs")?;
+
+    let before = lfs_status(&target_dir).await?;
+    if before.resolved {
+        emit_core_deploy_log(
+            &app_handle,
+            "repair_lfs",
+            "stdout",
+            "所有 LFS 文件均已就绪。",
+        );
+        return Ok(LfsRepairReport {
+            repaired: Vec::new(),
+            still_pointers: Vec::new(),
+        });
+    }
+    emit_core_deploy_log(
+        &app_handle,
+        "repair_lfs",
+        "stdout",
+        &format!(
+            "发现 {} 个未下载的 LFS 文件，开始修复...",
+            before.pointer_files.len()
+        ),
+    );
+
+    let mut fetch_cmd = new_command("git");
+    fetch_cmd
+        .arg("-C")
+        .arg(&target_dir)
+        .args(["lfs", "fetch", "--all"]);
+    run_command_with_streaming(&app_handle, "repair_lfs", fetch_cmd).await?;
+
+    let mut checkout_cmd = new_command("git");
+    checkout_cmd
+        .arg("-C")
+        .arg(&target_dir)
+        .args(["lfs", "checkout"]);
+    run_command_with_streaming(&app_handle, "repair_lfs", checkout_cmd).await?;
+
+    let after = lfs_status(&target_dir).await?;
+    if after.resolved {
+        set_lfs_smudge_skipped(&target_dir, false)?;
+    }
+    let repaired = before
+        .pointer_files
+        .into_iter()
+        .filter(|file| !after.pointer_files.contains(file))
+        .collect();
+    Ok(LfsRepairReport {
+        repaired,
+        still_pointers: after.pointer_files,
+    })
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub fn check_index_tts_repo(repo_dir: Option<String>) -> Result<bool, String> {
     let normalized = match repo_dir {
@@ -310,32 +694,113 @@ pub fn check_index_tts_repo(repo_dir: Option<String>) -> Result<bool, String> {
     Ok(true)
 }
 
+/// Extras `uv sync` installs on this platform.
+pub fn sync_extra_args() -> &'static [&'static str] {
+    if env::consts::OS == "windows" {
+        // Windows users are encouraged to avoid the heavy extras such as DeepSpeed.
+        &["--extra", "webui"]
+    } else {
+        &["--all-extras"]
+    }
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn setup_index_tts_env(
     app_handle: AppHandle,
     target_dir: String,
     network_environment: String,
-) -> Result<String, String> {
+    offline_wheel_dir: Option<String>,
+) -> Result<String, DeployError> {
+    let target_dir = clean_path_arg(&target_dir);
+    let offline_wheel_dir = offline_wheel_dir.map(|dir| clean_path_arg(&dir));
+    let _dir_lock = acquire_dir_lock(&target_dir, "setup_index_tts_env")
+        .map_err(|e| DeployError::new("setup_env", e))?;
     let use_china_mirror = network_environment == "mainland_china";
-    let is_windows = env::consts::OS == "windows";
 
     let mut command = new_command("uv");
-    command.arg("sync").current_dir(&target_dir);
-
-    if is_windows {
-        // Windows users are encouraged to avoid the heavy extras such as DeepSpeed.
-        command.args(["--extra", "webui"]);
-    } else {
-        command.arg("--all-extras");
-    }
+    command
+        .arg("sync")
+        .args(sync_extra_args())
+        .current_dir(&target_dir);
+    apply_uv_cache_dir(&app_handle, &mut command);
 
-    if use_china_mirror {
+    // Install strictly from a wheel directory filled by `prefetch_wheels`.
+    if let Some(wheel_dir) = &offline_wheel_dir {
+        if !Path::new(wheel_dir).is_dir() {
+            return Err(DeployError::new(
+                "setup_env",
+                format!("Offline wheel directory {} does not exist.", wheel_dir),
+            ));
+        }
+        command.args(["--offline", "--find-links"]).arg(wheel_dir);
+        emit_core_deploy_log(
+            &app_handle,
+            "setup_env",
+            "stdout",
+            &format!("离线安装，仅使用本地 wheel 缓存: {}", wheel_dir),
+        );
+    } else if use_china_mirror {
         command
             .arg("--default-index")
             .arg("https://pypi.tuna.tsinghua.edu.cn/simple");
     }
 
-    run_command_with_streaming(&app_handle, "setup_env", command).await?;
+    let torch_build = load_settings(&app_handle).torch_build;
+    match &torch_build {
+        Some(build) => {
+            command
+                .arg("--index")
+                .arg(format!("{}={}", TORCH_INDEX_NAME, torch_index_url(build)));
+            emit_core_deploy_log(
+                &app_handle,
+                "setup_env",
+                "stdout",
+                &format!("使用 PyTorch 构建: {}", build),
+            );
+        }
+        None => emit_core_deploy_log(
+            &app_handle,
+            "setup_env",
+            "stdout",
+            "使用仓库默认的 PyTorch 构建。",
+        ),
+    }
+
+    if let Some(warning) = check_linux_compat(torch_build.as_deref())
+        .await
+        .and_then(|compat| compat.warning)
+    {
+        emit_core_deploy_log(
+            &app_handle,
+            "setup_env",
+            "stderr",
+            &format!("系统兼容性警告: {}", warning),
+        );
+    }
+
+    if let Err(log) = run_command_with_streaming(&app_handle, "setup_env", command).await {
+        if let Some(error) = classify_native_build_failure("setup_env", &log) {
+            if let Some(remediation) = &error.remediation {
+                emit_core_deploy_log(
+                    &app_handle,
+                    "setup_env",
+                    "stderr",
+                    &format!("原生扩展编译失败，缺少构建依赖。{}", remediation),
+                );
+            }
+            return Err(error);
+        }
+        if let Some(error) = classify_resolution_conflict("setup_env", &log) {
+            emit_core_deploy_log(
+                &app_handle,
+                "setup_env",
+                "stderr",
+                &format!("依赖版本冲突: {}", error.message),
+            );
+            return Err(error);
+        }
+        return Err(DeployError::new("setup_env", log));
+    }
     Ok("SUCCESS".to_string())
 }
 
@@ -370,69 +835,545 @@ pub async fn install_hf_or_modelscope_tools(
     ))
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum ModelSource {
+    #[serde(rename = "huggingface")]
     HuggingFace,
+    #[serde(rename = "modelscope")]
     ModelScope,
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub async fn download_index_tts_model(
-    app_handle: AppHandle,
-    target_dir: String,
-    network_environment: String,
-    model_save_path: Option<String>,
-) -> Result<String, String> {
-    let model_source = if network_environment == "mainland_china" {
-        ModelSource::ModelScope
+impl ModelSource {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "huggingface" | "hf" => Ok(ModelSource::HuggingFace),
+            "modelscope" => Ok(ModelSource::ModelScope),
+            other => Err(format!(
+                "Unsupported model source '{}'. Use \"huggingface\" or \"modelscope\".",
+                other
+            )),
+        }
+    }
+
+    fn alternate(self) -> Self {
+        match self {
+            ModelSource::HuggingFace => ModelSource::ModelScope,
+            ModelSource::ModelScope => ModelSource::HuggingFace,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ModelSource::HuggingFace => "HuggingFace",
+            ModelSource::ModelScope => "ModelScope",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelDownloadOutcome {
+    pub source: ModelSource,
+    pub failed_over: bool,
+    // Absolute directory webui.py should load the model from.
+    pub model_dir: String,
+    // Directory the download was asked to use; differs from `model_dir` when the tool
+    // nested the files or the model stayed in the HF cache.
+    pub requested_dir: String,
+    pub download_mode: DownloadMode,
+    // Disk space the downloaded files take up.
+    pub footprint_mb: f64,
+    pub warning: Option<String>,
+}
+
+/// Puts a finished download in place for its mode and measures what it costs on disk.
+fn finish_model_download(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    source: ModelSource,
+    failed_over: bool,
+    local_dir: String,
+    mode: DownloadMode,
+) -> Result<ModelDownloadOutcome, String> {
+    let requested_dir = local_dir.clone();
+    let model_dir = match mode {
+        DownloadMode::LocalDir => local_dir,
+        DownloadMode::CacheSymlink => {
+            let snapshot = hf_snapshot_dir()?;
+            let linked = link_snapshot(&snapshot, Path::new(&local_dir))?;
+            emit_core_deploy_log(
+                app_handle,
+                "download_model",
+                "stdout",
+                &format!(
+                    "已将 {} 个模型文件从 HuggingFace 缓存链接到 {}",
+                    linked, local_dir
+                ),
+            );
+            local_dir
+        }
+        DownloadMode::CacheOnly => {
+            let snapshot = hf_snapshot_dir()?.to_string_lossy().to_string();
+            emit_core_deploy_log(
+                app_handle,
+                "download_model",
+                "stdout",
+                &format!("模型保留在 HuggingFace 缓存中: {}", snapshot),
+            );
+            snapshot
+        }
+    };
+    // ModelScope and older hf releases may nest the files below the requested folder.
+    let location = locate_model(Path::new(&model_dir));
+    if !location.found {
+        return Err(format!(
+            "The download finished but the model files were not found in {}.",
+            model_dir
+        ));
+    }
+    if location.differs {
+        emit_core_deploy_log(
+            app_handle,
+            "download_model",
+            "stdout",
+            &format!(
+                "模型文件实际位于 {}（请求的目录: {}）",
+                location.effective_dir, requested_dir
+            ),
+        );
+    }
+    record_model_location(app_handle, target_dir, &location);
+    let model_dir = location.effective_dir;
+    let footprint_mb = footprint_mb(mode, Path::new(&model_dir));
+    emit_core_deploy_log(
+        app_handle,
+        "download_model",
+        "stdout",
+        &format!(
+            "模型占用磁盘 {:.0} MB，webui.py 的模型目录: {}",
+            footprint_mb, model_dir
+        ),
+    );
+    let warning = duplicate_copy_warning(mode);
+    if let Some(warning) = &warning {
+        emit_core_deploy_log(app_handle, "download_model", "stderr", warning);
+    }
+    Ok(ModelDownloadOutcome {
+        source,
+        failed_over,
+        footprint_mb,
+        model_dir,
+        requested_dir,
+        download_mode: mode,
+        warning,
+    })
+}
+
+/// Resolves the model save path against `target_dir`, creates it with its parents and
+/// confirms it is writable, so a bad path fails before the download tool starts.
+pub fn prepare_model_dir(target_dir: &str, model_save_path: &str) -> Result<PathBuf, String> {
+    let requested = Path::new(model_save_path);
+    let model_dir = if requested.is_absolute() {
+        requested.to_path_buf()
     } else {
-        ModelSource::HuggingFace
+        Path::new(target_dir).join(requested)
     };
+    fs::create_dir_all(&model_dir).map_err(|e| {
+        format!(
+            "Failed to create model directory {}: {}",
+            model_dir.display(),
+            e
+        )
+    })?;
+    let probe = model_dir.join(".indextts-write-test");
+    fs::write(&probe, b"ok").map_err(|e| {
+        format!(
+            "Model directory {} is not writable: {}",
+            model_dir.display(),
+            e
+        )
+    })?;
+    let _ = fs::remove_file(&probe);
 
-    let use_hf_mirror = network_environment == "mainland_china";
-    let local_dir = model_save_path.unwrap_or_else(|| "checkpoints".to_string());
+    let canonical = fs::canonicalize(&model_dir).map_err(|e| {
+        format!(
+            "Failed to resolve model directory {}: {}",
+            model_dir.display(),
+            e
+        )
+    })?;
+    // Windows returns `\\?\C:\...`, which some Python tools mishandle; UNC shares keep it.
+    let display = canonical.to_string_lossy();
+    Ok(match display.strip_prefix(r"\\?\") {
+        Some(rest) if !rest.starts_with("UNC") => PathBuf::from(rest),
+        _ => canonical,
+    })
+}
+
+// Output fragments of requests/urllib3/hf_hub/modelscope when the source itself is unreachable.
+const NETWORK_FAILURE_SIGNATURES: [&str; 14] = [
+    "ConnectionError",
+    "ConnectTimeout",
+    "ReadTimeout",
+    "Max retries exceeded",
+    "Connection reset",
+    "Connection refused",
+    "timed out",
+    "Temporary failure in name resolution",
+    "Name or service not known",
+    "getaddrinfo failed",
+    "Network is unreachable",
+    "SSLError",
+    "502 Bad Gateway",
+    "503 Service",
+];
+
+fn is_network_failure(log: &str) -> bool {
+    NETWORK_FAILURE_SIGNATURES
+        .iter()
+        .any(|signature| log.contains(signature))
+}
+
+const RATE_LIMIT_SIGNATURES: [&str; 4] = [
+    "429 Client Error",
+    "Too Many Requests",
+    "rate limit",
+    "Rate limit",
+];
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+const RATE_LIMIT_BASE_BACKOFF: Duration = Duration::from_secs(30);
+const RATE_LIMIT_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+fn is_rate_limited(log: &str) -> bool {
+    RATE_LIMIT_SIGNATURES
+        .iter()
+        .any(|signature| log.contains(signature))
+}
+
+/// Extracts the wait the server asked for, e.g. `Retry-After: 60` or `'Retry-After': '60'`.
+fn parse_retry_after(log: &str) -> Option<Duration> {
+    let lower = log.to_lowercase();
+    let rest = &lower[lower.find("retry-after")? + "retry-after".len()..];
+    let digits: String = rest
+        .trim_start_matches(|c: char| !c.is_ascii_digit() && c != '\n')
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok().map(Duration::from_secs)
+}
+
+/// Runs one source's download, waiting and retrying when the hub answers with 429s.
+async fn run_model_download_with_backoff(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    model_source: ModelSource,
+    local_dir: &str,
+    use_hf_mirror: bool,
+    mode: DownloadMode,
+) -> Result<(), String> {
+    let mut attempt = 0;
+    loop {
+        let error = match run_model_download(
+            app_handle,
+            target_dir,
+            model_source,
+            local_dir,
+            use_hf_mirror,
+            mode,
+        )
+        .await
+        {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+        if !is_rate_limited(&error) || attempt >= MAX_RATE_LIMIT_RETRIES {
+            return Err(error);
+        }
+
+        let wait = parse_retry_after(&error)
+            .unwrap_or(RATE_LIMIT_BASE_BACKOFF * 2u32.pow(attempt))
+            .min(RATE_LIMIT_MAX_BACKOFF);
+        attempt += 1;
+        emit_core_deploy_log(
+            app_handle,
+            "download_model",
+            "stdout",
+            &format!(
+                "{} 请求过于频繁 (429)，{} 秒后重试 ({}/{})...",
+                model_source.label(),
+                wait.as_secs(),
+                attempt,
+                MAX_RATE_LIMIT_RETRIES
+            ),
+        );
+        sleep(wait).await;
+    }
+}
 
+async fn run_model_download(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    model_source: ModelSource,
+    local_dir: &str,
+    use_hf_mirror: bool,
+    mode: DownloadMode,
+) -> Result<(), String> {
+    let settings = load_settings(app_handle);
+    let accelerate = settings.hf_transfer_enabled;
     let (tool_spec, tool_name) = match model_source {
-        ModelSource::HuggingFace => ("huggingface-hub[cli,hf_xet]", "hf"),
+        ModelSource::HuggingFace => (hf_tool_spec(accelerate), "hf"),
         ModelSource::ModelScope => ("modelscope", "modelscope"),
     };
 
     let mut install_cmd = new_command("uv");
     install_cmd.arg("tool").arg("install").arg(tool_spec);
-    run_command_with_streaming(&app_handle, "install_model_tool", install_cmd).await?;
+    apply_uv_cache_dir(app_handle, &mut install_cmd);
+    run_command_with_streaming(app_handle, "install_model_tool", install_cmd).await?;
 
     let mut command = new_command("uv");
     command
         .arg("tool")
         .arg("run")
         .arg(tool_name)
-        .current_dir(&target_dir);
+        .current_dir(target_dir);
 
     match model_source {
         ModelSource::HuggingFace => {
-            command.args(["download", "IndexTeam/IndexTTS-2"]);
-            command.arg("--local-dir").arg(&local_dir);
+            command.args(["download", MODEL_REPO_ID]);
+            // Cache modes leave the files in the hub cache; they are linked in afterwards.
+            if !mode.uses_hf_cache() {
+                command.arg("--local-dir").arg(local_dir);
+            }
 
             if use_hf_mirror {
                 command.env("HF_ENDPOINT", "https://hf-mirror.com");
             }
+
+            let accelerated = accelerate && hf_transfer_installed().await;
+            if accelerated {
+                command.env("HF_HUB_ENABLE_HF_TRANSFER", "1");
+            }
+            emit_core_deploy_log(
+                app_handle,
+                "download_model",
+                "stdout",
+                if accelerated {
+                    "已启用 hf_transfer 加速下载。"
+                } else if accelerate {
+                    "hf_transfer 不可用，使用标准下载。"
+                } else {
+                    "未启用 hf_transfer 加速，使用标准下载。"
+                },
+            );
         }
         ModelSource::ModelScope => {
-            command
-                .args(["download", "--model", "IndexTeam/IndexTTS-2"]);
-            command.arg("--local_dir").arg(&local_dir);
+            command.args(["download", "--model", MODEL_REPO_ID]);
+            command.arg("--local_dir").arg(local_dir);
         }
     }
+    // Both CLIs take the same flag for parallel file downloads.
+    if let Some(workers) = settings.download_concurrency {
+        command.arg("--max-workers").arg(workers.to_string());
+    }
 
-    run_command_with_streaming(&app_handle, "download_model", command).await?;
+    let tracker = ModelFileTracker::new(app_handle.clone(), &MODEL_CORE_FILES, model_source);
+    let hook: LineHook = {
+        let tracker = tracker.clone();
+        Arc::new(move |line: &str| tracker.observe(line))
+    };
+    let result =
+        run_command_with_line_hook(app_handle, "download_model", command, Some(hook)).await;
+    tracker.finish(result.is_ok());
+    result
+}
+
+/// Downloads the model from the preferred source and, unless `model_source` pins one,
+/// retries from the other source when the first fails for network reasons.
+pub async fn download_model_with_failover(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    network_environment: &str,
+    model_save_path: Option<String>,
+    model_source: Option<String>,
+    download_mode: Option<String>,
+) -> Result<ModelDownloadOutcome, String> {
+    let target_dir = &clean_path_arg(target_dir);
+    let _dir_lock = acquire_dir_lock(target_dir, "download_index_tts_model")?;
+
+    let mode = download_mode
+        .as_deref()
+        .map(DownloadMode::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let mut pinned = model_source
+        .as_deref()
+        .map(ModelSource::parse)
+        .transpose()?;
+    if mode.uses_hf_cache() {
+        // The HuggingFace mirror stands in for ModelScope; failing over would lose the cache.
+        let source = pinned.unwrap_or(ModelSource::HuggingFace);
+        mode.check_source(source)?;
+        pinned = Some(source);
+    }
+    let primary = pinned.unwrap_or(if network_environment == "mainland_china" {
+        ModelSource::ModelScope
+    } else {
+        ModelSource::HuggingFace
+    });
+
+    let use_hf_mirror = network_environment == "mainland_china";
+    let local_dir = model_save_path
+        .map(|path| clean_path_arg(&path))
+        .unwrap_or_else(|| "checkpoints".to_string());
+    let local_dir = prepare_model_dir(target_dir, &local_dir)?
+        .to_string_lossy()
+        .to_string();
+    ensure_free_space(
+        Path::new(&local_dir),
+        required_free_disk_gb(app_handle),
+        "the IndexTTS-2 model",
+    )?;
+
+    let primary_result = run_model_download_with_backoff(
+        app_handle,
+        target_dir,
+        primary,
+        &local_dir,
+        use_hf_mirror,
+        mode,
+    )
+    .await;
+    let error = match primary_result {
+        Ok(()) => {
+            return finish_model_download(app_handle, target_dir, primary, false, local_dir, mode)
+        }
+        Err(error) => error,
+    };
+    let rate_limited = is_rate_limited(&error);
+    if pinned.is_some() && rate_limited {
+        return Err(format!(
+            "{}\n{} is still rate limiting downloads. Try again later or switch the model source to {}.",
+            error,
+            primary.label(),
+            primary.alternate().label()
+        ));
+    }
+    if pinned.is_some() || !(rate_limited || is_network_failure(&error)) {
+        return Err(error);
+    }
+
+    let fallback = primary.alternate();
+    emit_core_deploy_log(
+        app_handle,
+        "download_model",
+        "stdout",
+        &format!(
+            "{} {}，切换到 {} 重新下载模型。",
+            primary.label(),
+            if rate_limited {
+                "持续限流"
+            } else {
+                "网络连接失败"
+            },
+            fallback.label()
+        ),
+    );
+    run_model_download_with_backoff(
+        app_handle,
+        target_dir,
+        fallback,
+        &local_dir,
+        use_hf_mirror,
+        mode,
+    )
+    .await
+    .map_err(|fallback_error| {
+        format!(
+            "{}\nRetry from {} also failed: {}",
+            error,
+            fallback.label(),
+            fallback_error
+        )
+    })?;
+    finish_model_download(app_handle, target_dir, fallback, true, local_dir, mode)
+}
+
+/// `download_mode` is "local_dir" (default), "cache_symlink" or "cache_only"; the cache
+/// modes need the HuggingFace source. The deploy log reports where webui.py should point.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_index_tts_model(
+    app_handle: AppHandle,
+    target_dir: String,
+    network_environment: String,
+    model_save_path: Option<String>,
+    model_source: Option<String>,
+    download_mode: Option<String>,
+) -> Result<String, String> {
+    download_model_with_failover(
+        &app_handle,
+        &target_dir,
+        &network_environment,
+        model_save_path,
+        model_source,
+        download_mode,
+    )
+    .await?;
     Ok("SUCCESS".to_string())
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub struct EnsureModelResult {
+    pub model_dir: String,
+    pub downloaded: bool,
+    pub outcome: Option<ModelDownloadOutcome>,
+}
+
+/// Downloads the model only when its core files are missing; returns right away when
+/// they are already in place. Uses the active model directory unless `model_save_path`
+/// is given.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn ensure_model(
+    app_handle: AppHandle,
+    target_dir: String,
+    network_environment: String,
+    model_save_path: Option<String>,
+) -> Result<EnsureModelResult, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    let configured = app_handle.state::<EngineManager>().lock().model_dir.clone();
+    let model_save_path = model_save_path
+        .filter(|path| !path.trim().is_empty())
+        .or_else(|| (!configured.is_empty()).then_some(configured))
+        .unwrap_or_else(|| "checkpoints".to_string());
+    let model_dir = prepare_model_dir(&target_dir, &clean_path_arg(&model_save_path))?;
+    if model_has_core_files(&model_dir) {
+        return Ok(EnsureModelResult {
+            model_dir: model_dir.to_string_lossy().to_string(),
+            downloaded: false,
+            outcome: None,
+        });
+    }
+
+    let outcome = download_model_with_failover(
+        &app_handle,
+        &target_dir,
+        &network_environment,
+        Some(model_save_path),
+        None,
+        None,
+    )
+    .await?;
+    Ok(EnsureModelResult {
+        model_dir: outcome.model_dir.clone(),
+        downloaded: true,
+        outcome: Some(outcome),
+    })
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GpuInfo {
     pub has_cuda: bool,
     pub name: Option<String>,
     pub vram_gb: Option<f64>,
+    pub free_vram_gb: Option<f64>,
     pub recommended_fp16: bool, // Based on some criteria, e.g., VRAM > 8GB
 }
 
@@ -460,23 +1401,29 @@ pub async fn run_gpu_check(repo_dir: String) -> Result<GpuInfo, String> {
         .and_then(|line| line.split(':').nth(1))
         .map(|s| s.trim().to_string());
 
+    // "VRAM: 24.0 GB" is the total; newer scripts add "Free VRAM: 20.5 GB".
+    let gb_after_colon = |line: &str| {
+        line.split(':')
+            .nth(1)
+            .and_then(|s| s.split_whitespace().next())
+            .and_then(|s| s.parse::<f64>().ok())
+    };
     let vram_gb = stdout
         .lines()
-        .find(|line| line.contains("VRAM:"))
-        .and_then(|line| {
-            line.split(':')
-                .nth(1)
-                .and_then(|s| s.trim().split_whitespace().next())
-                .and_then(|s| s.parse::<f64>().ok())
-        });
+        .find(|line| line.contains("VRAM:") && !line.to_lowercase().contains("free"))
+        .and_then(gb_after_colon);
+    let free_vram_gb = stdout
+        .lines()
+        .find(|line| line.contains("VRAM") && line.to_lowercase().contains("free"))
+        .and_then(gb_after_colon);
 
-    // Simple recommendation for FP16: if VRAM is detected and > 8GB
-    let recommended_fp16 = vram_gb.map_or(false, |vram| vram > 8.0);
+    let recommended_fp16 = recommend_fp16(vram_gb, free_vram_gb);
 
     Ok(GpuInfo {
         has_cuda,
         name,
         vram_gb,
+        free_vram_gb,
         recommended_fp16,
     })
 }
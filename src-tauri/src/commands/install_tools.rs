@@ -1,10 +1,313 @@
 // src-tauri/src/commands/install_tools.rs
 
 use super::command_utils::new_command;
+use super::index_tts::{stream_command, LogChannel, StreamedExit};
+use super::log_files::{append_log_line, DEPLOY_LOG};
+use serde::Serialize;
 use std::env;
+use tauri::{AppHandle, Emitter};
+use tokio::process::Command;
+
+const INSTALL_TOOLS_EVENT: &str = "install-tools-log";
+
+// winget returns HRESULT-style exit codes, see
+// https://github.com/microsoft/winget-cli/blob/master/doc/windows/package-manager/winget/returnCodes.md
+const WINGET_UPDATE_NOT_APPLICABLE: u32 = 0x8A15_002B;
+const WINGET_PACKAGE_ALREADY_INSTALLED: u32 = 0x8A15_0061;
+const WINGET_REBOOT_REQUIRED_TO_FINISH: u32 = 0x8A15_0109;
+const WINGET_REBOOT_REQUIRED_FOR_INSTALL: u32 = 0x8A15_010A;
+const WINGET_REBOOT_INITIATED: u32 = 0x8A15_010B;
+const WINGET_INSTALL_ALREADY_INSTALLED: u32 = 0x8A15_010D;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum InstallOutcome {
+    Installed,
+    AlreadyInstalled,
+    RebootRequired,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InstallToolsLogEvent {
+    pub step: String,
+    pub stream: String,
+    pub line: String,
+}
+
+/// Emits one line of installer output on `install-tools-log` and keeps it in the
+/// deploy log file next to the deploy steps.
+pub fn emit_install_tools_log(app_handle: &AppHandle, step: &str, stream: &str, line: &str) {
+    append_log_line(
+        app_handle,
+        DEPLOY_LOG,
+        &format!("{}/{}", step, stream),
+        line,
+    );
+    let _ = app_handle.emit(
+        INSTALL_TOOLS_EVENT,
+        InstallToolsLogEvent {
+            step: step.to_string(),
+            stream: stream.to_string(),
+            line: line.to_string(),
+        },
+    );
+}
+
+/// Runs an installer with its output streamed live; Homebrew and winget can take
+/// minutes without printing a final result.
+async fn stream_install(
+    app_handle: &AppHandle,
+    step: &str,
+    command: Command,
+) -> Result<StreamedExit, String> {
+    stream_command(app_handle, step, command, LogChannel::InstallTools, None).await
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InstallReport {
+    pub outcome: InstallOutcome,
+    // winget, brew, apt-get, ... or the astral install script for uv.
+    pub package_manager: String,
+    pub message: String,
+}
+
+impl InstallReport {
+    fn new(outcome: InstallOutcome, label: &str, package_manager: &str) -> Self {
+        let message = match outcome {
+            InstallOutcome::Installed => format!("Installed {} with {}.", label, package_manager),
+            InstallOutcome::AlreadyInstalled => {
+                format!("{} is already installed ({}).", label, package_manager)
+            }
+            InstallOutcome::RebootRequired => format!(
+                "Installed {} with {}; restart the computer to finish.",
+                label, package_manager
+            ),
+            InstallOutcome::Failed => {
+                format!("{} installation with {} failed.", label, package_manager)
+            }
+        };
+        InstallReport {
+            outcome,
+            package_manager: package_manager.to_string(),
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinuxPackageManager {
+    AptGet,
+    Dnf,
+    Pacman,
+    Zypper,
+}
+
+const LINUX_PACKAGE_MANAGERS: [LinuxPackageManager; 4] = [
+    LinuxPackageManager::AptGet,
+    LinuxPackageManager::Dnf,
+    LinuxPackageManager::Pacman,
+    LinuxPackageManager::Zypper,
+];
+
+impl LinuxPackageManager {
+    fn program(self) -> &'static str {
+        match self {
+            LinuxPackageManager::AptGet => "apt-get",
+            LinuxPackageManager::Dnf => "dnf",
+            LinuxPackageManager::Pacman => "pacman",
+            LinuxPackageManager::Zypper => "zypper",
+        }
+    }
+
+    /// Arguments for a non-interactive install of `packages`.
+    fn install_args(self, packages: &[&str]) -> Vec<String> {
+        let base: &[&str] = match self {
+            LinuxPackageManager::AptGet | LinuxPackageManager::Dnf => &["install", "-y"],
+            LinuxPackageManager::Pacman => &["-S", "--noconfirm", "--needed"],
+            LinuxPackageManager::Zypper => &["--non-interactive", "install"],
+        };
+        base.iter()
+            .chain(packages)
+            .map(|arg| arg.to_string())
+            .collect()
+    }
+}
+
+async fn detect_linux_package_manager() -> Option<LinuxPackageManager> {
+    for manager in LINUX_PACKAGE_MANAGERS {
+        if new_command(manager.program())
+            .arg("--version")
+            .output()
+            .await
+            .is_ok_and(|output| output.status.success())
+        {
+            return Some(manager);
+        }
+    }
+    None
+}
+
+#[cfg(unix)]
+fn is_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+#[cfg(not(unix))]
+fn is_root() -> bool {
+    false
+}
+
+/// What to put in front of a command to run it as root without a password prompt:
+/// nothing when the app already is root, `sudo -n` when sudo has cached credentials or
+/// NOPASSWD. `None` means only an interactive sudo would work, which would hang here.
+async fn root_prefix() -> Option<Vec<String>> {
+    if is_root() {
+        return Some(Vec::new());
+    }
+    new_command("sudo")
+        .args(["-n", "true"])
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+        .then(|| vec!["sudo".to_string(), "-n".to_string()])
+}
+
+async fn run_as_root(
+    app_handle: &AppHandle,
+    step: &str,
+    prefix: &[String],
+    argv: &[String],
+) -> Result<StreamedExit, String> {
+    let full: Vec<&String> = prefix.iter().chain(argv).collect();
+    let mut command = new_command(full[0]);
+    command.args(&full[1..]);
+    stream_install(app_handle, step, command).await
+}
+
+/// Installs packages with whichever of apt-get, dnf, pacman or zypper is present.
+async fn linux_package_install(
+    app_handle: &AppHandle,
+    step: &str,
+    label: &str,
+    packages: fn(LinuxPackageManager) -> &'static [&'static str],
+) -> Result<InstallReport, String> {
+    let manager = detect_linux_package_manager().await.ok_or_else(|| {
+        format!(
+            "No supported package manager (apt-get, dnf, pacman, zypper) found. Please install {} manually.",
+            label
+        )
+    })?;
+    let program = manager.program();
+    let install_args = manager.install_args(packages(manager));
+    let Some(prefix) = root_prefix().await else {
+        return Err(format!(
+            "Installing {} with {} needs root privileges, and sudo would ask for a password. Run `sudo {} {}` in a terminal, then check again.",
+            label,
+            program,
+            program,
+            install_args.join(" ")
+        ));
+    };
+
+    let mut argv: Vec<String> = Vec::new();
+    if manager == LinuxPackageManager::AptGet {
+        // sudo resets the environment, so the debconf setting goes through `env`.
+        argv.extend(["env", "DEBIAN_FRONTEND=noninteractive"].map(String::from));
+        // A fresh system may not have package lists yet.
+        let mut update = argv.clone();
+        update.extend([program, "update"].map(String::from));
+        let exit = run_as_root(app_handle, step, &prefix, &update).await?;
+        if !exit.status.success() {
+            return Err(format!(
+                "{} update failed before installing {}: {}",
+                program, label, exit.stderr
+            ));
+        }
+    }
+    argv.push(program.to_string());
+    argv.extend(install_args);
+
+    let exit = run_as_root(app_handle, step, &prefix, &argv).await?;
+    if !exit.status.success() {
+        return Err(format!(
+            "{} {} installation failed: {}",
+            program, label, exit.stderr
+        ));
+    }
+    Ok(InstallReport::new(
+        InstallOutcome::Installed,
+        label,
+        program,
+    ))
+}
+
+/// The official per-user uv installer; it needs no root and puts uv in ~/.local/bin.
+async fn install_uv_with_script(app_handle: &AppHandle) -> Result<InstallReport, String> {
+    let mut command = new_command("sh");
+    command
+        .arg("-c")
+        .arg("curl -LsSf https://astral.sh/uv/install.sh | sh");
+    let curl_install = stream_install(app_handle, "install_uv", command).await?;
+
+    if !curl_install.status.success() {
+        return Err(format!(
+            "Curl script uv installation failed: {}",
+            curl_install.stderr
+        ));
+    }
+    Ok(InstallReport::new(
+        InstallOutcome::Installed,
+        "uv",
+        "the astral install script",
+    ))
+}
+
+/// Maps a winget exit code onto an install outcome.
+fn classify_winget_exit_code(code: Option<i32>) -> InstallOutcome {
+    let Some(code) = code else {
+        return InstallOutcome::Failed;
+    };
+
+    match code as u32 {
+        0 => InstallOutcome::Installed,
+        WINGET_UPDATE_NOT_APPLICABLE
+        | WINGET_PACKAGE_ALREADY_INSTALLED
+        | WINGET_INSTALL_ALREADY_INSTALLED => InstallOutcome::AlreadyInstalled,
+        WINGET_REBOOT_REQUIRED_TO_FINISH
+        | WINGET_REBOOT_REQUIRED_FOR_INSTALL
+        | WINGET_REBOOT_INITIATED => InstallOutcome::RebootRequired,
+        _ => InstallOutcome::Failed,
+    }
+}
+
+/// Runs `winget install` for a package and turns its exit code into an outcome.
+async fn winget_install(
+    app_handle: &AppHandle,
+    step: &str,
+    args: &[&str],
+    label: &str,
+) -> Result<InstallOutcome, String> {
+    let mut command = new_command("winget");
+    command.args(args);
+    let output = stream_install(app_handle, step, command).await?;
+
+    match classify_winget_exit_code(output.status.code()) {
+        InstallOutcome::Failed => Err(format!(
+            "Winget {} installation failed (exit code {}): {}",
+            label,
+            output
+                .status
+                .code()
+                .map(|code| format!("0x{:08X}", code as u32))
+                .unwrap_or_else(|| "unknown".to_string()),
+            output.stderr
+        )),
+        outcome => Ok(outcome),
+    }
+}
 
 #[tauri::command]
-pub async fn install_git_and_lfs() -> Result<String, String> {
+pub async fn install_git_and_lfs(app_handle: AppHandle) -> Result<InstallReport, String> {
     let os = env::consts::OS;
 
     match os {
@@ -14,23 +317,15 @@ pub async fn install_git_and_lfs() -> Result<String, String> {
                 .arg("--version")
                 .output()
                 .await
-                .map_or(false, |output| output.status.success());
+                .is_ok_and(|output| output.status.success());
 
             if winget_installed {
-                let git_install_cmd = new_command("winget")
-                    .args(["install", "--id", "Git.Git", "-e", "--source", "winget"])
-                    .output()
-                    .await
-                    .map_err(|e| format!("Failed to execute winget for Git: {}", e))?;
-
-                if !git_install_cmd.status.success() {
-                    return Err(format!("Winget Git installation failed: {}", String::from_utf8_lossy(&git_install_cmd.stderr)));
-                }
-
                 // Git LFS is usually included or installed with Git for Windows,
                 // but if not, winget might have a package for it, or it needs manual handling.
                 // For now, assume Git.Git handles Git LFS.
-                Ok("SUCCESS".to_string())
+                winget_install(&app_handle, "install_git_and_lfs", &["install", "--id", "Git.Git", "-e", "--source", "winget"], "Git")
+                    .await
+                    .map(|outcome| InstallReport::new(outcome, "Git", "winget"))
             } else {
                 Err("Winget not found. Please install Git and Git LFS manually from https://git-scm.com/download/win".to_string())
             }
@@ -41,29 +336,33 @@ pub async fn install_git_and_lfs() -> Result<String, String> {
                 .arg("--version")
                 .output()
                 .await
-                .map_or(false, |output| output.status.success());
+                .is_ok_and(|output| output.status.success());
 
             if brew_installed {
-                let git_install_cmd = new_command("brew")
-                    .args(["install", "git", "git-lfs"])
-                    .output()
-                    .await
-                    .map_err(|e| format!("Failed to execute brew for Git and Git LFS: {}", e))?;
+                let mut command = new_command("brew");
+                command.args(["install", "git", "git-lfs"]);
+                let git_install = stream_install(&app_handle, "install_git_and_lfs", command).await?;
 
-                if !git_install_cmd.status.success() {
-                    return Err(format!("Homebrew Git and Git LFS installation failed: {}", String::from_utf8_lossy(&git_install_cmd.stderr)));
+                if !git_install.status.success() {
+                    return Err(format!("Homebrew Git and Git LFS installation failed: {}", git_install.stderr));
                 }
-                Ok("SUCCESS".to_string())
+                Ok(InstallReport::new(InstallOutcome::Installed, "Git and Git LFS", "brew"))
             } else {
                 Err("Homebrew not found. Please install Git and Git LFS manually, or install Homebrew first from https://brew.sh".to_string())
             }
         },
+        "linux" => {
+            linux_package_install(&app_handle, "install_git_and_lfs", "Git and Git LFS", |_| {
+                &["git", "git-lfs"]
+            })
+            .await
+        }
         _ => Err(format!("Automatic Git and Git LFS installation is not supported on {} yet. Please install manually.", os)),
     }
 }
 
 #[tauri::command]
-pub async fn install_uv() -> Result<String, String> {
+pub async fn install_uv(app_handle: AppHandle) -> Result<InstallReport, String> {
     let os = env::consts::OS;
 
     match os {
@@ -72,22 +371,17 @@ pub async fn install_uv() -> Result<String, String> {
                 .arg("--version")
                 .output()
                 .await
-                .map_or(false, |output| output.status.success());
+                .is_ok_and(|output| output.status.success());
 
             if winget_installed {
-                let uv_install_cmd = new_command("winget")
-                    .args(["install", "--id", "astral-sh.uv", "-e"])
-                    .output()
-                    .await
-                    .map_err(|e| format!("Failed to execute winget for uv: {}", e))?;
-
-                if !uv_install_cmd.status.success() {
-                    return Err(format!(
-                        "Winget uv installation failed: {}",
-                        String::from_utf8_lossy(&uv_install_cmd.stderr)
-                    ));
-                }
-                Ok("SUCCESS".to_string())
+                winget_install(
+                    &app_handle,
+                    "install_uv",
+                    &["install", "--id", "astral-sh.uv", "-e"],
+                    "uv",
+                )
+                .await
+                .map(|outcome| InstallReport::new(outcome, "uv", "winget"))
             } else {
                 Err("Winget not found. Please install uv manually from https://docs.astral.sh/uv/install".to_string())
             }
@@ -97,40 +391,32 @@ pub async fn install_uv() -> Result<String, String> {
                 .arg("--version")
                 .output()
                 .await
-                .map_or(false, |output| output.status.success());
+                .is_ok_and(|output| output.status.success());
 
             if brew_installed {
-                let uv_install_cmd = new_command("brew")
-                    .args(["install", "uv"])
-                    .output()
-                    .await
-                    .map_err(|e| format!("Failed to execute brew for uv: {}", e))?;
+                let mut command = new_command("brew");
+                command.args(["install", "uv"]);
+                let uv_install = stream_install(&app_handle, "install_uv", command).await?;
 
-                if !uv_install_cmd.status.success() {
+                if !uv_install.status.success() {
                     return Err(format!(
                         "Homebrew uv installation failed: {}",
-                        String::from_utf8_lossy(&uv_install_cmd.stderr)
+                        uv_install.stderr
                     ));
                 }
-                Ok("SUCCESS".to_string())
+                Ok(InstallReport::new(InstallOutcome::Installed, "uv", "brew"))
             } else {
                 // Fallback to curl script for macOS if brew is not installed
-                let curl_install_cmd = new_command("sh")
-                    .arg("-c")
-                    .arg("curl -LsSf https://astral.sh/uv/install.sh | sh")
-                    .output()
-                    .await
-                    .map_err(|e| format!("Failed to execute curl script for uv: {}", e))?;
-
-                if !curl_install_cmd.status.success() {
-                    return Err(format!(
-                        "Curl script uv installation failed: {}",
-                        String::from_utf8_lossy(&curl_install_cmd.stderr)
-                    ));
-                }
-                Ok("SUCCESS".to_string())
+                install_uv_with_script(&app_handle).await
             }
         }
+        // Only Arch packages uv; elsewhere the official script installs it per user.
+        "linux" => match detect_linux_package_manager().await {
+            Some(LinuxPackageManager::Pacman) if root_prefix().await.is_some() => {
+                linux_package_install(&app_handle, "install_uv", "uv", |_| &["uv"]).await
+            }
+            _ => install_uv_with_script(&app_handle).await,
+        },
         _ => Err(format!(
             "Automatic uv installation is not supported on {} yet. Please install manually.",
             os
@@ -139,7 +425,7 @@ pub async fn install_uv() -> Result<String, String> {
 }
 
 #[tauri::command]
-pub async fn install_python() -> Result<String, String> {
+pub async fn install_python(app_handle: AppHandle) -> Result<InstallReport, String> {
     let os = env::consts::OS;
 
     match os {
@@ -148,22 +434,18 @@ pub async fn install_python() -> Result<String, String> {
                 .arg("--version")
                 .output()
                 .await
-                .map_or(false, |output| output.status.success());
+                .is_ok_and(|output| output.status.success());
 
             if winget_installed {
-                let python_install_cmd = new_command("winget")
-                    .args(["install", "--id", "Python.Python.3.10", "-e"]) // Targeting Python 3.10
-                    .output()
-                    .await
-                    .map_err(|e| format!("Failed to execute winget for Python: {}", e))?;
-
-                if !python_install_cmd.status.success() {
-                    return Err(format!(
-                        "Winget Python installation failed: {}",
-                        String::from_utf8_lossy(&python_install_cmd.stderr)
-                    ));
-                }
-                Ok("SUCCESS".to_string())
+                // Targeting Python 3.10
+                winget_install(
+                    &app_handle,
+                    "install_python",
+                    &["install", "--id", "Python.Python.3.10", "-e"],
+                    "Python",
+                )
+                .await
+                .map(|outcome| InstallReport::new(outcome, "Python", "winget"))
             } else {
                 Err("Winget not found. Please install Python manually from https://www.python.org/downloads/windows/".to_string())
             }
@@ -173,29 +455,101 @@ pub async fn install_python() -> Result<String, String> {
                 .arg("--version")
                 .output()
                 .await
-                .map_or(false, |output| output.status.success());
+                .is_ok_and(|output| output.status.success());
 
             if brew_installed {
-                let python_install_cmd = new_command("brew")
-                    .args(["install", "python@3.10"]) // Targeting Python 3.10
-                    .output()
-                    .await
-                    .map_err(|e| format!("Failed to execute brew for Python: {}", e))?;
+                let mut command = new_command("brew");
+                command.args(["install", "python@3.10"]); // Targeting Python 3.10
+                let python_install = stream_install(&app_handle, "install_python", command).await?;
 
-                if !python_install_cmd.status.success() {
+                if !python_install.status.success() {
                     return Err(format!(
                         "Homebrew Python installation failed: {}",
-                        String::from_utf8_lossy(&python_install_cmd.stderr)
+                        python_install.stderr
                     ));
                 }
-                Ok("SUCCESS".to_string())
+                Ok(InstallReport::new(
+                    InstallOutcome::Installed,
+                    "Python",
+                    "brew",
+                ))
             } else {
                 Err("Homebrew not found. Please install Python manually from https://www.python.org/downloads/mac-osx/".to_string())
             }
         }
+        // The distribution's Python 3; uv fetches the exact version the project pins.
+        "linux" => {
+            linux_package_install(
+                &app_handle,
+                "install_python",
+                "Python",
+                |manager| match manager {
+                    LinuxPackageManager::AptGet => &["python3", "python3-venv", "python3-pip"],
+                    LinuxPackageManager::Pacman => &["python", "python-pip"],
+                    LinuxPackageManager::Dnf | LinuxPackageManager::Zypper => {
+                        &["python3", "python3-pip"]
+                    }
+                },
+            )
+            .await
+        }
         _ => Err(format!(
             "Automatic Python installation is not supported on {} yet. Please install manually.",
             os
         )),
     }
 }
+
+#[tauri::command]
+pub async fn install_ffmpeg(app_handle: AppHandle) -> Result<InstallOutcome, String> {
+    let os = env::consts::OS;
+
+    match os {
+        "windows" => {
+            let winget_installed = new_command("winget")
+                .arg("--version")
+                .output()
+                .await
+                .is_ok_and(|output| output.status.success());
+
+            if winget_installed {
+                winget_install(
+                    &app_handle,
+                    "install_ffmpeg",
+                    &["install", "--id", "Gyan.FFmpeg", "-e"],
+                    "ffmpeg",
+                )
+                .await
+            } else {
+                Err("Winget not found. Please install ffmpeg manually from https://ffmpeg.org/download.html and add it to PATH".to_string())
+            }
+        }
+        "macos" => {
+            let brew_installed = new_command("brew")
+                .arg("--version")
+                .output()
+                .await
+                .is_ok_and(|output| output.status.success());
+
+            if brew_installed {
+                let mut command = new_command("brew");
+                command.args(["install", "ffmpeg"]);
+                let ffmpeg_install = stream_install(&app_handle, "install_ffmpeg", command).await?;
+
+                if !ffmpeg_install.status.success() {
+                    return Err(format!(
+                        "Homebrew ffmpeg installation failed: {}",
+                        ffmpeg_install.stderr
+                    ));
+                }
+                Ok(InstallOutcome::Installed)
+            } else {
+                Err("Homebrew not found. Please install ffmpeg manually, or install Homebrew first from https://brew.sh".to_string())
+            }
+        }
+        _ => Err(format!(
+            "Automatic ffmpeg installation is not supported on {} yet. Please install it with your package manager.",
+            os
+        )),
+    }
+}
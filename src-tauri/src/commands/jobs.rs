@@ -0,0 +1,273 @@
+// src-tauri/src/commands/jobs.rs
+
+use super::deploy::{deploy_index_tts, resume_deploy};
+use super::deployments::unix_timestamp;
+use super::index_tts::{
+    clone_index_tts_repo, download_model_with_failover, init_git_lfs, setup_index_tts_env,
+};
+use super::step_cancel::cancel_job_processes;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const JOB_STATUS_EVENT: &str = "job-status";
+
+tokio::task_local! {
+    static CURRENT_JOB: Option<String>;
+}
+
+/// Id of the job the current task runs under, used to tag log and progress events.
+pub fn current_job_id() -> Option<String> {
+    CURRENT_JOB.try_with(|job_id| job_id.clone()).ok().flatten()
+}
+
+/// Runs `future` under `job_id`, for tasks spawned on behalf of a job (e.g. log readers).
+pub fn scope_job<F: Future>(job_id: Option<String>, future: F) -> impl Future<Output = F::Output> {
+    CURRENT_JOB.scope(job_id, future)
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct JobParams {
+    pub target_dir: String,
+    pub network_environment: String,
+    pub model_save_path: Option<String>,
+    pub model_source: Option<String>,
+    // "local_dir", "cache_symlink" or "cache_only", for download_model jobs.
+    pub download_mode: Option<String>,
+    pub skip_lfs: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct JobStatus {
+    pub job_id: String,
+    pub kind: String,
+    pub state: JobState,
+    pub result: Option<Value>,
+    pub error: Option<Value>,
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+}
+
+struct JobEntry {
+    status: JobStatus,
+    handle: Option<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<HashMap<String, JobEntry>>,
+    next_id: AtomicU64,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, JobEntry>> {
+        self.jobs.lock().expect("Job registry mutex poisoned")
+    }
+
+    fn next_job_id(&self) -> String {
+        let seq = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        format!("job-{}-{}", unix_timestamp(), seq)
+    }
+
+    /// Records the outcome unless the job was cancelled in the meantime.
+    fn finish(&self, job_id: &str, outcome: Result<Value, Value>) -> Option<JobStatus> {
+        let mut jobs = self.lock();
+        let entry = jobs.get_mut(job_id)?;
+        if entry.status.state != JobState::Running {
+            return None;
+        }
+        entry.handle = None;
+        entry.status.finished_at = Some(unix_timestamp());
+        match outcome {
+            Ok(result) => {
+                entry.status.state = JobState::Succeeded;
+                entry.status.result = Some(result);
+            }
+            Err(error) => {
+                entry.status.state = JobState::Failed;
+                entry.status.error = Some(error);
+            }
+        }
+        Some(entry.status.clone())
+    }
+
+    /// Aborts every running job; used when the app exits.
+    pub fn abort_all(&self) {
+        for entry in self.lock().values_mut() {
+            if let Some(handle) = entry.handle.take() {
+                handle.abort();
+                entry.status.state = JobState::Cancelled;
+                entry.status.finished_at = Some(unix_timestamp());
+            }
+        }
+    }
+}
+
+fn to_json<T: Serialize, E: Serialize>(result: Result<T, E>) -> Result<Value, Value> {
+    match result {
+        Ok(value) => Ok(serde_json::to_value(value).unwrap_or(Value::Null)),
+        Err(error) => Err(serde_json::to_value(error).unwrap_or(Value::Null)),
+    }
+}
+
+async fn run_job(app_handle: AppHandle, kind: &str, params: JobParams) -> Result<Value, Value> {
+    let JobParams {
+        target_dir,
+        network_environment,
+        model_save_path,
+        model_source,
+        download_mode,
+        skip_lfs,
+    } = params;
+    match kind {
+        "clone_repo" => {
+            to_json(clone_index_tts_repo(app_handle, target_dir, None, skip_lfs, None, None).await)
+        }
+        "init_lfs" => to_json(init_git_lfs(app_handle, target_dir).await),
+        "setup_env" => {
+            to_json(setup_index_tts_env(app_handle, target_dir, network_environment, None).await)
+        }
+        "download_model" => to_json(
+            download_model_with_failover(
+                &app_handle,
+                &target_dir,
+                &network_environment,
+                model_save_path,
+                model_source,
+                download_mode,
+            )
+            .await,
+        ),
+        "deploy" => to_json(
+            deploy_index_tts(app_handle, target_dir, network_environment, model_save_path).await,
+        ),
+        "resume_deploy" => to_json(resume_deploy(app_handle, target_dir).await),
+        other => Err(Value::String(format!("Unknown job kind '{}'.", other))),
+    }
+}
+
+const JOB_KINDS: [&str; 6] = [
+    "clone_repo",
+    "init_lfs",
+    "setup_env",
+    "download_model",
+    "deploy",
+    "resume_deploy",
+];
+
+/// Starts a long-running operation in the background and returns its job id right away.
+/// Progress and logs are emitted as usual, tagged with the job id; the final state is
+/// emitted on `job-status`.
+#[tauri::command(rename_all = "snake_case")]
+pub fn start_job(
+    app_handle: AppHandle,
+    kind: String,
+    params: JobParams,
+    registry: State<'_, JobRegistry>,
+) -> Result<String, String> {
+    if !JOB_KINDS.contains(&kind.as_str()) {
+        return Err(format!(
+            "Unknown job kind '{}'. Expected one of: {}.",
+            kind,
+            JOB_KINDS.join(", ")
+        ));
+    }
+
+    let job_id = registry.next_job_id();
+    let status = JobStatus {
+        job_id: job_id.clone(),
+        kind: kind.clone(),
+        state: JobState::Running,
+        result: None,
+        error: None,
+        started_at: unix_timestamp(),
+        finished_at: None,
+    };
+    registry.lock().insert(
+        job_id.clone(),
+        JobEntry {
+            status: status.clone(),
+            handle: None,
+        },
+    );
+    let _ = app_handle.emit(JOB_STATUS_EVENT, status);
+
+    let task_app = app_handle.clone();
+    let task_job_id = job_id.clone();
+    let handle = tauri::async_runtime::spawn(scope_job(Some(job_id.clone()), async move {
+        let outcome = run_job(task_app.clone(), &kind, params).await;
+        let registry = task_app.state::<JobRegistry>();
+        if let Some(status) = registry.finish(&task_job_id, outcome) {
+            let _ = task_app.emit(JOB_STATUS_EVENT, status);
+        }
+    }));
+
+    if let Some(entry) = registry.lock().get_mut(&job_id) {
+        if entry.status.state == JobState::Running {
+            entry.handle = Some(handle);
+        }
+    }
+    Ok(job_id)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_job_status(
+    job_id: String,
+    registry: State<'_, JobRegistry>,
+) -> Result<JobStatus, String> {
+    registry
+        .lock()
+        .get(&job_id)
+        .map(|entry| entry.status.clone())
+        .ok_or_else(|| format!("Unknown job '{}'.", job_id))
+}
+
+/// Aborts a running job. Child processes spawned by the job are killed with it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_job(
+    app_handle: AppHandle,
+    job_id: String,
+    registry: State<'_, JobRegistry>,
+) -> Result<JobStatus, String> {
+    // Marked cancelled first so the job's own "was cancelled" failure, caused by the
+    // kill below, is not recorded over it.
+    let (status, handle) = {
+        let mut jobs = registry.lock();
+        let entry = jobs
+            .get_mut(&job_id)
+            .ok_or_else(|| format!("Unknown job '{}'.", job_id))?;
+        if entry.status.state != JobState::Running {
+            return Err(format!("Job '{}' has already finished.", job_id));
+        }
+        entry.status.state = JobState::Cancelled;
+        entry.status.finished_at = Some(unix_timestamp());
+        (entry.status.clone(), entry.handle.take())
+    };
+    // Aborting the task only kills the direct child (`kill_on_drop`); git-lfs, python
+    // and pip underneath it run in its process group and have to be killed as a tree.
+    cancel_job_processes(&app_handle, &job_id).await;
+    if let Some(handle) = handle {
+        handle.abort();
+    }
+    let _ = app_handle.emit(JOB_STATUS_EVENT, status.clone());
+    Ok(status)
+}
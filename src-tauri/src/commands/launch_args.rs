@@ -0,0 +1,260 @@
+// src-tauri/src/commands/launch_args.rs
+
+use super::command_utils::{clean_path_arg, new_command};
+use super::server::ServerChildProcess;
+use super::server_config::{load_server_config, LaunchSettings};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const LAUNCH_ARGS_EVENT: &str = "launch-args-invalid";
+const WEBUI_SCRIPT: &str = "webui.py";
+// `--help` still has to import webui.py's top-level modules.
+const HELP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LaunchArgsCheck {
+    pub ok: bool,
+    // "webui.py" when the argparse calls were read from source, "--help" otherwise.
+    pub source: String,
+    pub supported_flags: Vec<String>,
+    pub checked_args: Vec<String>,
+    pub unsupported: Vec<String>,
+    // Options webui.py marks `required=True` that the args don't pass.
+    pub missing_required: Vec<String>,
+    // Saved launch presets that would pass a flag webui.py no longer accepts.
+    pub affected_presets: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct WebuiFlag {
+    names: Vec<String>,
+    required: bool,
+}
+
+/// Returns the body of each `add_argument(...)` call, up to the matching parenthesis.
+fn add_argument_calls(source: &str) -> Vec<&str> {
+    let mut calls = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("add_argument(") {
+        let body = &rest[start + "add_argument(".len()..];
+        let mut depth = 1;
+        let mut end = body.len();
+        for (index, c) in body.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = index;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        calls.push(&body[..end]);
+        rest = &body[end..];
+    }
+    calls
+}
+
+/// The leading string literals of an `add_argument` call that are option names.
+fn option_names(call: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for part in call.split(',') {
+        let literal = part.trim().trim_matches(['"', '\'']);
+        if part.trim().len() == literal.len() || !literal.starts_with('-') {
+            break;
+        }
+        names.push(literal.to_string());
+    }
+    names
+}
+
+/// Reads webui.py's argparse options without running it.
+fn parse_webui_flags(source: &str) -> Vec<WebuiFlag> {
+    add_argument_calls(source)
+        .into_iter()
+        .filter_map(|call| {
+            let names = option_names(call);
+            if names.is_empty() {
+                return None;
+            }
+            let compact: String = call.chars().filter(|c| !c.is_whitespace()).collect();
+            Some(WebuiFlag {
+                names,
+                required: compact.contains("required=True"),
+            })
+        })
+        .collect()
+}
+
+/// Picks the option names out of argparse's `--help` text.
+fn parse_help_flags(help: &str) -> Vec<WebuiFlag> {
+    help.lines()
+        .map(str::trim_start)
+        .filter(|line| line.starts_with('-'))
+        .map(|line| WebuiFlag {
+            names: line
+                .split_whitespace()
+                .map(|word| word.trim_end_matches(','))
+                .take_while(|word| word.starts_with('-'))
+                .map(|word| word.split('=').next().unwrap_or(word).to_string())
+                .collect(),
+            required: false,
+        })
+        .filter(|flag| !flag.names.is_empty())
+        .collect()
+}
+
+async fn help_flags(target_dir: &str) -> Result<Vec<WebuiFlag>, String> {
+    let mut command = new_command("uv");
+    command
+        .args(["run", "--no-sync", "python", WEBUI_SCRIPT, "--help"])
+        .current_dir(target_dir)
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(HELP_TIMEOUT, command.output())
+        .await
+        .map_err(|_| {
+            format!(
+                "webui.py --help did not finish within {}s.",
+                HELP_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| format!("Failed to run webui.py --help: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "webui.py --help failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(parse_help_flags(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Option names in `args`, with `--opt=value` reduced to `--opt`. Negative numbers are
+/// values, not options.
+fn flags_in_args(args: &[String]) -> Vec<String> {
+    args.iter()
+        .filter(|arg| arg.starts_with('-') && arg.parse::<f64>().is_err())
+        .map(|arg| arg.split('=').next().unwrap_or(arg).to_string())
+        .collect()
+}
+
+/// argparse accepts an exact option name or an unambiguous prefix of a long one.
+fn is_accepted(flag: &str, supported: &[String]) -> bool {
+    if supported.iter().any(|name| name == flag) {
+        return true;
+    }
+    flag.starts_with("--")
+        && supported
+            .iter()
+            .filter(|name| name.starts_with(flag))
+            .count()
+            == 1
+}
+
+/// The webui.py options the launcher passes for these settings.
+pub fn settings_flags(settings: &LaunchSettings) -> Vec<String> {
+    let mut flags = vec!["--host".to_string(), "--port".to_string()];
+    if settings.precision.as_deref() == Some("fp16") {
+        flags.push("--fp16".to_string());
+    }
+    if settings.device.as_deref() == Some("cuda") {
+        flags.push("--cuda_kernel".to_string());
+    }
+    flags.push("--model_dir".to_string());
+    flags
+}
+
+/// Checks `args` (by default the last launch's, or the launcher's base flags) and every
+/// saved preset against the options the current webui.py accepts.
+pub async fn check_launch_args(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    args: Option<Vec<String>>,
+) -> Result<LaunchArgsCheck, String> {
+    let script = Path::new(target_dir).join(WEBUI_SCRIPT);
+    let source = fs::read_to_string(&script)
+        .map_err(|e| format!("Failed to read {}: {}", script.display(), e))?;
+    let mut flags = parse_webui_flags(&source);
+    let mut from = WEBUI_SCRIPT;
+    if flags.is_empty() {
+        flags = help_flags(target_dir).await?;
+        from = "--help";
+    }
+    let mut supported: Vec<String> = flags.iter().flat_map(|flag| flag.names.clone()).collect();
+    supported.sort();
+    supported.dedup();
+
+    let checked_args = args.unwrap_or_else(|| {
+        app_handle
+            .state::<ServerChildProcess>()
+            .launch_record()
+            .map(|record| record.webui_args)
+            .unwrap_or_else(|| settings_flags(&LaunchSettings::default()))
+    });
+    let passed = flags_in_args(&checked_args);
+    let mut unsupported: Vec<String> = passed
+        .iter()
+        .filter(|flag| !is_accepted(flag, &supported))
+        .cloned()
+        .collect();
+    unsupported.dedup();
+    let missing_required: Vec<String> = flags
+        .iter()
+        .filter(|flag| flag.required)
+        .filter(|flag| {
+            !flag
+                .names
+                .iter()
+                .any(|name| passed.iter().any(|arg| name.starts_with(arg.as_str())))
+        })
+        .map(|flag| flag.names[0].clone())
+        .collect();
+    let affected_presets: Vec<String> = load_server_config(app_handle)
+        .presets
+        .into_iter()
+        .filter(|(_, settings)| {
+            settings_flags(settings)
+                .iter()
+                .any(|flag| !is_accepted(flag, &supported))
+        })
+        .map(|(name, _)| name)
+        .collect();
+
+    Ok(LaunchArgsCheck {
+        ok: unsupported.is_empty() && missing_required.is_empty() && affected_presets.is_empty(),
+        source: from.to_string(),
+        supported_flags: supported,
+        checked_args,
+        unsupported,
+        missing_required,
+        affected_presets,
+    })
+}
+
+/// Re-checks the launch configuration after an update and tells the UI when it no
+/// longer matches webui.py.
+pub async fn notify_if_launch_args_invalid(app_handle: &AppHandle, target_dir: &str) {
+    if let Ok(check) = check_launch_args(app_handle, target_dir, None).await {
+        if !check.ok {
+            let _ = app_handle.emit(LAUNCH_ARGS_EVENT, check);
+        }
+    }
+}
+
+/// Reports which of `args` (or the last launch's arguments) and which saved presets
+/// pass options the current webui.py no longer accepts, and which required options
+/// are missing.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn validate_launch_args(
+    app_handle: AppHandle,
+    target_dir: String,
+    args: Option<Vec<String>>,
+) -> Result<LaunchArgsCheck, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    check_launch_args(&app_handle, &target_dir, args).await
+}
@@ -0,0 +1,102 @@
+// src-tauri/src/commands/lfs.rs
+
+use super::command_utils::new_command;
+use serde::Serialize;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const LFS_POINTER_HEADER: &[u8] = b"version https://git-lfs.github.com/spec/v1";
+// Pointer files are ~130 bytes; anything much larger is real content.
+const MAX_POINTER_BYTES: u64 = 1024;
+// Written into .git by a `skip_lfs` clone; pointers are expected until it is removed.
+const LFS_SKIPPED_MARKER: &str = "indextts-hub-lfs-skipped";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LfsStatus {
+    pub resolved: bool,
+    pub tracked_files: usize,
+    pub pointer_files: Vec<String>,
+    // The repo was cloned with `skip_lfs`; model weights come from the model download.
+    pub smudge_skipped: bool,
+}
+
+fn lfs_skipped_marker(target_dir: &str) -> std::path::PathBuf {
+    Path::new(target_dir).join(".git").join(LFS_SKIPPED_MARKER)
+}
+
+pub fn lfs_smudge_skipped(target_dir: &str) -> bool {
+    lfs_skipped_marker(target_dir).is_file()
+}
+
+/// Records (or clears) that the checkout deliberately left LFS files as pointers.
+pub fn set_lfs_smudge_skipped(target_dir: &str, skipped: bool) -> Result<(), String> {
+    let marker = lfs_skipped_marker(target_dir);
+    let result = if skipped {
+        std::fs::write(&marker, b"GIT_LFS_SKIP_SMUDGE=1\n")
+    } else if marker.exists() {
+        std::fs::remove_file(&marker)
+    } else {
+        Ok(())
+    };
+    result.map_err(|e| format!("Failed to update {}: {}", marker.display(), e))
+}
+
+/// True when the file on disk is still an LFS pointer stub instead of its content.
+pub fn is_lfs_pointer(path: &Path) -> bool {
+    let Ok(meta) = path.metadata() else {
+        return false;
+    };
+    if !meta.is_file() || meta.len() > MAX_POINTER_BYTES {
+        return false;
+    }
+    let mut header = vec![0u8; LFS_POINTER_HEADER.len()];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|_| header == LFS_POINTER_HEADER)
+}
+
+/// Paths (relative to the repo) of every file tracked by LFS.
+pub async fn lfs_tracked_files(target_dir: &str) -> Result<Vec<String>, String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(["lfs", "ls-files", "--name-only"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute git lfs ls-files: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git lfs ls-files failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub async fn lfs_status(target_dir: &str) -> Result<LfsStatus, String> {
+    let tracked = lfs_tracked_files(target_dir).await?;
+    let repo_path = Path::new(target_dir);
+    let pointer_files: Vec<String> = tracked
+        .iter()
+        .filter(|file| is_lfs_pointer(&repo_path.join(file)))
+        .cloned()
+        .collect();
+    Ok(LfsStatus {
+        resolved: pointer_files.is_empty(),
+        tracked_files: tracked.len(),
+        pointer_files,
+        smudge_skipped: lfs_smudge_skipped(target_dir),
+    })
+}
+
+/// Lists LFS-tracked files that are still pointer stubs in the working tree.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_lfs_resolved(target_dir: String) -> Result<LfsStatus, String> {
+    lfs_status(&target_dir).await
+}
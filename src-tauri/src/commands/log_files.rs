@@ -0,0 +1,228 @@
+// src-tauri/src/commands/log_files.rs
+
+use super::deployments::unix_timestamp;
+use super::tasks::spawn_tracked;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::time::sleep;
+
+const LOG_FILE_LINE_EVENT: &str = "log-file-line";
+// The current file is moved to `<name>.1` once it grows past this size.
+const LOG_ROTATE_BYTES: u64 = 5 * 1024 * 1024;
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_TAIL_LINES: usize = 500;
+
+pub const DEPLOY_LOG: &str = "deploy";
+pub const SERVER_LOG: &str = "server";
+// App lifecycle messages (startup warnings, shutdown) that have no window to go to.
+pub const APP_LOG: &str = "app";
+const LOG_NAMES: [&str; 3] = [DEPLOY_LOG, SERVER_LOG, APP_LOG];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LogFileLine {
+    pub which: String,
+    pub line: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LogFileTail {
+    pub which: String,
+    pub path: String,
+    pub lines: Vec<String>,
+}
+
+/// Stop flags of the active `follow_log_file` tasks, keyed by log name.
+#[derive(Default)]
+pub struct LogFollowers(Mutex<HashMap<String, Arc<AtomicBool>>>);
+
+impl LogFollowers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn replace(&self, which: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut followers = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(previous) = followers.insert(which.to_string(), flag.clone()) {
+            previous.store(true, Ordering::SeqCst);
+        }
+        flag
+    }
+
+    fn stop(&self, which: &str) -> bool {
+        let mut followers = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        followers
+            .remove(which)
+            .map(|flag| flag.store(true, Ordering::SeqCst))
+            .is_some()
+    }
+}
+
+fn validate_which(which: &str) -> Result<(), String> {
+    if LOG_NAMES.contains(&which) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown log '{}'. Expected one of: {}.",
+            which,
+            LOG_NAMES.join(", ")
+        ))
+    }
+}
+
+pub fn log_file_path(app_handle: &AppHandle, which: &str) -> Result<PathBuf, String> {
+    let log_dir = app_handle
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve app log directory: {}", e))?;
+    Ok(log_dir.join(format!("{}.log", which)))
+}
+
+fn rotate_if_needed(path: &Path) {
+    let too_big = fs::metadata(path).is_ok_and(|meta| meta.len() >= LOG_ROTATE_BYTES);
+    if too_big {
+        let _ = fs::rename(path, path.with_extension("log.1"));
+    }
+}
+
+/// Appends a line to the persisted log. Failures are ignored: the in-memory stream
+/// stays authoritative and a read-only log dir must not break deploys.
+pub fn append_log_line(app_handle: &AppHandle, which: &str, tag: &str, line: &str) {
+    let Ok(path) = log_file_path(app_handle, which) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    rotate_if_needed(&path);
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{} [{}] {}", unix_timestamp(), tag, line);
+    }
+}
+
+/// Returns the last `max_lines` lines of the persisted deploy, server or app log.
+#[tauri::command(rename_all = "snake_case")]
+pub fn read_log_file(
+    app_handle: AppHandle,
+    which: String,
+    max_lines: Option<usize>,
+) -> Result<LogFileTail, String> {
+    validate_which(&which)?;
+    let path = log_file_path(&app_handle, &which)?;
+    let contents = match fs::read(&path) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read '{}': {}", path.display(), e)),
+    };
+
+    let max_lines = max_lines.unwrap_or(DEFAULT_TAIL_LINES);
+    let lines: Vec<&str> = contents.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    Ok(LogFileTail {
+        which,
+        path: path.display().to_string(),
+        lines: lines[start..].iter().map(|line| line.to_string()).collect(),
+    })
+}
+
+/// Stops any followers and deletes the persisted logs, including rotated copies.
+/// Returns the paths that were removed.
+pub fn remove_log_files(app_handle: &AppHandle) -> Vec<String> {
+    let mut removed = Vec::new();
+    for which in LOG_NAMES {
+        if let Some(followers) = app_handle.try_state::<LogFollowers>() {
+            followers.stop(which);
+        }
+        let Ok(path) = log_file_path(app_handle, which) else {
+            continue;
+        };
+        for file in [path.with_extension("log.1"), path] {
+            if fs::remove_file(&file).is_ok() {
+                removed.push(file.display().to_string());
+            }
+        }
+    }
+    removed
+}
+
+fn file_identity(file: &File) -> (u64, Option<SystemTime>) {
+    file.metadata()
+        .map(|meta| (meta.len(), meta.created().ok()))
+        .unwrap_or((0, None))
+}
+
+/// Streams lines appended to the persisted log as `log-file-line` events, starting at
+/// the current end of the file. Continues with the new file after a rotation.
+#[tauri::command(rename_all = "snake_case")]
+pub fn follow_log_file(
+    app_handle: AppHandle,
+    which: String,
+    followers: State<'_, LogFollowers>,
+) -> Result<String, String> {
+    validate_which(&which)?;
+    let path = log_file_path(&app_handle, &which)?;
+    let stop = followers.replace(&which);
+
+    let task_app = app_handle.clone();
+    let task_name = format!("follow_log_file:{}", which);
+    spawn_tracked(&task_app, &task_name, async move {
+        let mut position = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        let mut created = fs::metadata(&path)
+            .ok()
+            .and_then(|meta| meta.created().ok());
+        let mut carry = String::new();
+
+        while !stop.load(Ordering::SeqCst) {
+            sleep(FOLLOW_POLL_INTERVAL).await;
+            let Ok(mut file) = File::open(&path) else {
+                continue;
+            };
+            let (len, file_created) = file_identity(&file);
+            // A smaller or newly created file means the old one was rotated away.
+            if len < position || (file_created.is_some() && file_created != created) {
+                position = 0;
+                carry.clear();
+                created = file_created;
+            }
+            if len == position || file.seek(SeekFrom::Start(position)).is_err() {
+                continue;
+            }
+
+            let mut appended = Vec::new();
+            if file.read_to_end(&mut appended).is_err() {
+                continue;
+            }
+            position += appended.len() as u64;
+            carry.push_str(&String::from_utf8_lossy(&appended));
+            while let Some(pos) = carry.find('\n') {
+                let line = carry[..pos].trim_end_matches('\r').to_string();
+                carry.drain(..=pos);
+                let _ = app_handle.emit(
+                    LOG_FILE_LINE_EVENT,
+                    LogFileLine {
+                        which: which.clone(),
+                        line,
+                    },
+                );
+            }
+        }
+    });
+
+    Ok("SUCCESS".to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn stop_following_log_file(
+    which: String,
+    followers: State<'_, LogFollowers>,
+) -> Result<bool, String> {
+    validate_which(&which)?;
+    Ok(followers.stop(&which))
+}
@@ -0,0 +1,229 @@
+// src-tauri/src/commands/log_snapshot.rs
+
+use super::command_history::CommandHistory;
+use super::deployments::unix_timestamp;
+use super::server::ServerChildProcess;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+const REDACTED: &str = "***";
+// Keys whose values are masked in `key=value`, `key: value` and `--key value` forms.
+const SENSITIVE_KEYS: [&str; 7] = [
+    "token", "password", "passwd", "secret", "api_key", "apikey", "auth",
+];
+// HuggingFace access tokens are `hf_` followed by ~34 alphanumerics.
+const HF_TOKEN_PREFIX: &str = "hf_";
+const HF_TOKEN_MIN_LEN: usize = 20;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LogSnapshot {
+    pub path: String,
+    pub lines: usize,
+    pub crashed: bool,
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.trim_start_matches('-').to_lowercase();
+    SENSITIVE_KEYS
+        .iter()
+        .any(|sensitive| key.contains(sensitive))
+}
+
+/// Masks the `user:password@` part of URLs.
+fn redact_url_credentials(word: &str) -> Option<String> {
+    let scheme_end = word.find("://")? + 3;
+    let authority_end = word[scheme_end..]
+        .find('/')
+        .map_or(word.len(), |pos| scheme_end + pos);
+    let at = word[scheme_end..authority_end].rfind('@')? + scheme_end;
+    Some(format!(
+        "{}{}{}",
+        &word[..scheme_end],
+        REDACTED,
+        &word[at..]
+    ))
+}
+
+fn redact_word(word: &str, previous: Option<&str>) -> String {
+    if previous.is_some_and(|previous| {
+        previous.eq_ignore_ascii_case("bearer")
+            || (previous.starts_with("--") && is_sensitive_key(previous))
+    }) {
+        return REDACTED.to_string();
+    }
+    let bare = word.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '_');
+    if bare.starts_with(HF_TOKEN_PREFIX) && bare.len() >= HF_TOKEN_MIN_LEN {
+        return word.replace(bare, &format!("{}{}", HF_TOKEN_PREFIX, REDACTED));
+    }
+    if let Some(redacted) = redact_url_credentials(word) {
+        return redacted;
+    }
+    for separator in ['=', ':'] {
+        if let Some((key, value)) = word.split_once(separator) {
+            if !value.is_empty() && is_sensitive_key(key) {
+                return format!("{}{}{}", key, separator, REDACTED);
+            }
+        }
+    }
+    word.to_string()
+}
+
+/// Redacts a command's arguments one by one, so `--token <value>` pairs are caught even
+/// when the value is a separate argument.
+pub fn redact_args(args: &[String]) -> Vec<String> {
+    let mut previous: Option<&str> = None;
+    args.iter()
+        .map(|arg| {
+            let redacted = if arg.contains(char::is_whitespace) {
+                redact_line(arg)
+            } else {
+                redact_word(arg, previous)
+            };
+            previous = Some(arg);
+            redacted
+        })
+        .collect()
+}
+
+/// Masks tokens, passwords and URL credentials in a log line, keeping its spacing.
+pub fn redact_line(line: &str) -> String {
+    let mut redacted = String::with_capacity(line.len());
+    let mut previous: Option<&str> = None;
+    for chunk in line.split_inclusive(char::is_whitespace) {
+        let word = chunk.trim_end();
+        redacted.push_str(&redact_word(word, previous));
+        redacted.push_str(&chunk[word.len()..]);
+        if !word.is_empty() {
+            previous = Some(word);
+        }
+    }
+    redacted
+}
+
+/// Writes the recent server output, the last launch parameters and the crash exit code
+/// (if any) to a text file with secrets redacted. `output_path` may be a directory, in
+/// which case a timestamped file name is used.
+#[tauri::command(rename_all = "snake_case")]
+pub fn export_server_log_snapshot(
+    output_path: String,
+    last_n: Option<usize>,
+    state: State<'_, ServerChildProcess>,
+    history: State<'_, CommandHistory>,
+) -> Result<LogSnapshot, String> {
+    let timestamp = unix_timestamp();
+    let path = if Path::new(&output_path).is_dir() {
+        Path::new(&output_path).join(format!("indextts-server-log-{}.txt", timestamp))
+    } else {
+        Path::new(&output_path).to_path_buf()
+    };
+
+    let tail = state.log_tail();
+    let lines = &tail[tail.len().saturating_sub(last_n.unwrap_or(tail.len()))..];
+    let crash = state.last_crash();
+
+    let mut report = format!(
+        "IndexTTS Hub {} server log snapshot\nexported_at: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        timestamp
+    );
+    match state.launch_record() {
+        Some(launch) => {
+            report.push_str(&format!(
+                "\n[launch]\ntarget_dir: {}\nhost: {}\nport: {}\ndevice: {}\nprecision: {}\nlog_level: {}\ncpu_threads: {}\ngpu_index: {}\nstarted_at: {}\nargs: {}\n",
+                launch.target_dir,
+                launch.host,
+                launch.port,
+                launch.device,
+                launch.precision.as_deref().unwrap_or("default"),
+                launch.log_level,
+                launch.cpu_threads.map_or("all".to_string(), |threads| threads.to_string()),
+                launch.gpu_index.map_or("default".to_string(), |index| index.to_string()),
+                launch.started_at,
+                redact_line(&launch.webui_args.join(" "))
+            ));
+        }
+        None => report.push_str("\n[launch]\nThe server has not been started in this session.\n"),
+    }
+    if let Some(crash) = &crash {
+        report.push_str(&format!(
+            "\n[crash]\nexit_code: {}\ncrashed_at: {}\nmemory_limit_hit: {}\n",
+            crash
+                .exit_code
+                .map_or("unknown".to_string(), |code| code.to_string()),
+            crash.crashed_at,
+            crash.memory_limit_hit
+        ));
+        if let Some(gpu) = &crash.gpu {
+            report.push_str(&format!(
+                "\n[gpu]\ndriver_version: {}\ncuda_version: {}\ncaptured_at: {}\n",
+                gpu.driver_version.as_deref().unwrap_or("unknown"),
+                gpu.cuda_version.as_deref().unwrap_or("unknown"),
+                gpu.captured_at
+            ));
+            if let Some(error) = &gpu.usage.error {
+                report.push_str(&format!("error: {}\n", error));
+            }
+            for device in &gpu.usage.devices {
+                let processes: Vec<String> = device
+                    .processes
+                    .iter()
+                    .map(|process| {
+                        format!(
+                            "{} (pid {}, {} MB)",
+                            process.name,
+                            process.pid,
+                            process
+                                .used_memory_mb
+                                .map_or("?".to_string(), |mb| mb.to_string())
+                        )
+                    })
+                    .collect();
+                report.push_str(&format!(
+                    "gpu {}: {} used {} / {} MB, processes: {}\n",
+                    device.index,
+                    device.name,
+                    device
+                        .memory_used_mb
+                        .map_or("?".to_string(), |mb| mb.to_string()),
+                    device
+                        .memory_total_mb
+                        .map_or("?".to_string(), |mb| mb.to_string()),
+                    if processes.is_empty() {
+                        "none".to_string()
+                    } else {
+                        processes.join(", ")
+                    }
+                ));
+            }
+        }
+    }
+    let commands = history.all();
+    if !commands.is_empty() {
+        report.push_str("\n[commands]\n");
+        for command in commands {
+            report.push_str(&format!("{}: {}\n", command.step, command.command_line));
+        }
+    }
+    report.push_str(&format!("\n[output] last {} line(s)\n", lines.len()));
+    for line in lines {
+        report.push_str(&redact_line(line));
+        report.push('\n');
+    }
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, report).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(LogSnapshot {
+        path: path.display().to_string(),
+        lines: lines.len(),
+        crashed: crash.is_some(),
+    })
+}
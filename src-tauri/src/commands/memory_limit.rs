@@ -0,0 +1,218 @@
+// src-tauri/src/commands/memory_limit.rs
+
+use super::command_utils::new_command;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+use std::process::ExitStatus;
+use tokio::process::{Child, Command};
+
+// Printed by Python when an allocation is refused, which is how a Job Object limit
+// shows up on Windows.
+const OOM_MARKERS: [&str; 2] = ["MemoryError", "out of memory"];
+
+/// Keeps the memory limit of a running server in place. On Windows it owns the Job
+/// Object handle; on Linux it names the systemd scope the limit lives in.
+pub struct MemoryLimitGuard {
+    #[cfg(windows)]
+    job: windows_sys::Win32::Foundation::HANDLE,
+    #[cfg(target_os = "linux")]
+    scope_unit: String,
+}
+
+// The job handle is only closed on drop and never used concurrently.
+#[cfg(windows)]
+unsafe impl Send for MemoryLimitGuard {}
+#[cfg(windows)]
+unsafe impl Sync for MemoryLimitGuard {}
+
+#[cfg(windows)]
+impl Drop for MemoryLimitGuard {
+    fn drop(&mut self) {
+        // The job has no kill-on-close flag, so the server keeps running under its limit.
+        unsafe {
+            windows_sys::Win32::Foundation::CloseHandle(self.job);
+        }
+    }
+}
+
+/// Scope the server runs in. One server per app instance, so the app's PID keeps it
+/// unique; the scope is not collected on failure so its OOM result can be read.
+#[cfg(target_os = "linux")]
+fn scope_unit() -> String {
+    format!("indextts-hub-server-{}.scope", std::process::id())
+}
+
+/// Clears a failed scope left by an earlier run so the name can be used again.
+#[cfg(target_os = "linux")]
+fn reset_failed_scope(unit: &str) {
+    let _ = std::process::Command::new("systemctl")
+        .args(["--user", "reset-failed", unit])
+        .output();
+}
+
+#[cfg(target_os = "linux")]
+fn cgroup_scope_available() -> Result<(), String> {
+    if !Path::new("/sys/fs/cgroup/cgroup.controllers").is_file() {
+        return Err("cgroup v2 is not mounted".to_string());
+    }
+    let available = std::process::Command::new("systemd-run")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !available {
+        return Err("systemd-run is not available".to_string());
+    }
+    Ok(())
+}
+
+/// Command for `program`, wrapped in a memory-capped systemd scope on Linux when
+/// `limit_mb` is set. The second value is a warning when the limit can't be applied
+/// and the server runs unbounded. Windows applies the limit after spawning, see
+/// `apply_memory_limit`.
+pub fn memory_limited_command(program: &str, limit_mb: Option<u64>) -> (Command, Option<String>) {
+    let Some(limit_mb) = limit_mb else {
+        return (new_command(program), None);
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        match cgroup_scope_available() {
+            Ok(()) => {
+                let unit = scope_unit();
+                reset_failed_scope(&unit);
+                let mut command = new_command("systemd-run");
+                command
+                    .args(["--user", "--scope", "--quiet"])
+                    .arg(format!("--unit={}", unit))
+                    .arg(format!("--property=MemoryMax={}M", limit_mb))
+                    // Without this the kernel swaps instead of enforcing the cap.
+                    .arg("--property=MemorySwapMax=0")
+                    .arg("--")
+                    .arg(program);
+                (command, None)
+            }
+            Err(reason) => (
+                new_command(program),
+                Some(format!(
+                    "The {} MB memory limit was not applied ({}); the server runs without a cap.",
+                    limit_mb, reason
+                )),
+            ),
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = limit_mb;
+        (new_command(program), None)
+    }
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    {
+        (
+            new_command(program),
+            Some(format!(
+                "Memory limits are not supported on this platform; the {} MB limit was ignored.",
+                limit_mb
+            )),
+        )
+    }
+}
+
+/// Puts the spawned server into a Job Object capped at `limit_mb`. Processes it starts
+/// later (uv -> python) inherit the job.
+#[cfg(windows)]
+pub fn apply_memory_limit(child: &Child, limit_mb: u64) -> Result<MemoryLimitGuard, String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_LIMIT_JOB_MEMORY,
+    };
+
+    let process = child
+        .raw_handle()
+        .ok_or("The server process has already exited.".to_string())?;
+    unsafe {
+        let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+        if job.is_null() {
+            return Err(format!(
+                "Failed to create a job object: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_JOB_MEMORY;
+        info.JobMemoryLimit = (limit_mb as usize) * 1024 * 1024;
+        let applied = SetInformationJobObject(
+            job,
+            JobObjectExtendedLimitInformation,
+            &info as *const _ as *const std::ffi::c_void,
+            std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+        ) != 0
+            && AssignProcessToJobObject(job, process as _) != 0;
+        if !applied {
+            let error = std::io::Error::last_os_error();
+            CloseHandle(job);
+            return Err(format!("Failed to apply the memory limit: {}", error));
+        }
+        Ok(MemoryLimitGuard { job })
+    }
+}
+
+/// The limit was set on the systemd scope the command was wrapped in.
+#[cfg(target_os = "linux")]
+pub fn apply_memory_limit(_child: &Child, _limit_mb: u64) -> Result<MemoryLimitGuard, String> {
+    Ok(MemoryLimitGuard {
+        scope_unit: scope_unit(),
+    })
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn apply_memory_limit(_child: &Child, _limit_mb: u64) -> Result<MemoryLimitGuard, String> {
+    Ok(MemoryLimitGuard {})
+}
+
+impl MemoryLimitGuard {
+    /// Whether the kernel OOM killer ended a process in the server's scope. systemd
+    /// records the scope's `memory.events` oom_kill as the `oom-kill` result, which
+    /// outlives the cgroup itself; the failed scope is cleared once it has been read.
+    /// The OOM killer usually picks python, so uv above it exits normally.
+    #[cfg(target_os = "linux")]
+    pub fn oom_killed(&self) -> bool {
+        let result = std::process::Command::new("systemctl")
+            .args(["--user", "show", "--property=Result", "--value"])
+            .arg(&self.scope_unit)
+            .output();
+        reset_failed_scope(&self.scope_unit);
+        result.is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "oom-kill")
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn oom_killed(&self) -> bool {
+        false
+    }
+}
+
+/// Whether a server that ran under a memory limit most likely died by hitting it:
+/// the cgroup OOM killer (SIGKILL, or an `oom-kill` result on the scope), or Python's
+/// MemoryError when a Job Object refuses an allocation.
+pub fn memory_limit_hit(status: Option<ExitStatus>, oom_killed: bool, log_tail: &[String]) -> bool {
+    oom_killed
+        || status.is_some_and(killed_by_sigkill)
+        || log_tail
+            .iter()
+            .rev()
+            .take(20)
+            .any(|line| OOM_MARKERS.iter().any(|marker| line.contains(marker)))
+}
+
+#[cfg(unix)]
+fn killed_by_sigkill(status: ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal() == Some(libc::SIGKILL)
+}
+
+#[cfg(not(unix))]
+fn killed_by_sigkill(_status: ExitStatus) -> bool {
+    false
+}
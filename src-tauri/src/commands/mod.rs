@@ -1,6 +1,61 @@
+pub mod capabilities;
+pub mod cloud_folder;
+pub mod command_history;
 pub mod command_utils;
+pub mod config_migrations;
+pub mod config_store;
+pub mod connectivity;
+pub mod deploy;
+pub mod deploy_error;
+pub mod deploy_log;
+pub mod deployments;
+pub mod dir_lock;
+pub mod disk_bench;
+pub mod download_accel;
+pub mod download_mode;
+pub mod download_prewarm;
+pub mod download_progress;
+pub mod driver_check;
+pub mod encoding;
+pub mod engine;
+pub mod factory_reset;
+pub mod fd_limits;
+pub mod git_locks;
+pub mod git_settings;
+pub mod gpu_usage;
 pub mod index_tts;
 pub mod install_tools;
+pub mod jobs;
+pub mod launch_args;
+pub mod lfs;
+pub mod log_files;
+pub mod log_snapshot;
+pub mod memory_limit;
+pub mod model_compat;
+pub mod model_layout;
+pub mod network;
+pub mod notifications;
+pub mod panic_report;
+pub mod platform_defaults;
+pub mod processes;
+pub mod python_env;
+pub mod readiness;
+pub mod reference_audio;
+pub mod repo_archive;
+pub mod repo_remote;
+pub mod repo_shrink;
+pub mod resource_monitor;
 pub mod server;
+pub mod server_config;
+pub mod settings;
+pub mod step_cancel;
+pub mod storage_media;
 pub mod system_info;
+pub mod tasks;
 pub mod tool_check;
+pub mod torch_build;
+pub mod tuning;
+pub mod update_scheduler;
+pub mod uv_cache;
+pub mod uv_conflict;
+pub mod wheel_cache;
@@ -0,0 +1,170 @@
+// src-tauri/src/commands/model_compat.rs
+
+use super::deployments::read_project_version;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const MODEL_CONFIG_FILE: &str = "config.yaml";
+// Extensions of weight/tokenizer files a config may point at.
+const MODEL_FILE_EXTENSIONS: [&str; 6] = [".pth", ".pt", ".bin", ".model", ".safetensors", ".npy"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelCodeCompat {
+    pub compatible: bool,
+    pub code_version: Option<String>,
+    pub model_version: Option<String>,
+    // `checkpoints/config.yaml` shipped with the code, when the repo tracks one.
+    pub expected_model_version: Option<String>,
+    // Files the model config references that are not in the model directory.
+    pub missing_files: Vec<String>,
+    pub problems: Vec<String>,
+    pub recommendation: Option<String>,
+}
+
+/// Flattens the scalar entries of a simple YAML mapping into dotted keys
+/// (`dataset.bpe_model`). Lists and multi-line values are ignored.
+fn read_config_scalars(path: &Path) -> Option<BTreeMap<String, String>> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut scalars = BTreeMap::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    for line in content.lines() {
+        let without_comment = line.split(" #").next().unwrap_or(line);
+        let trimmed = without_comment.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let indent = without_comment.len() - without_comment.trim_start().len();
+        parents.retain(|(parent_indent, _)| *parent_indent < indent);
+        let key = key.trim().to_string();
+        let value = value.trim().trim_matches(['"', '\'']);
+        if value.is_empty() {
+            parents.push((indent, key));
+            continue;
+        }
+        let full_key = parents
+            .iter()
+            .map(|(_, parent)| parent.as_str())
+            .chain([key.as_str()])
+            .collect::<Vec<_>>()
+            .join(".");
+        scalars.insert(full_key, value.to_string());
+    }
+    Some(scalars)
+}
+
+fn major_version(version: &str) -> Option<u32> {
+    version.trim().split('.').next()?.parse().ok()
+}
+
+fn referenced_files(config: &BTreeMap<String, String>) -> Vec<String> {
+    config
+        .iter()
+        .filter(|(key, value)| {
+            MODEL_FILE_EXTENSIONS.iter().any(|ext| value.ends_with(ext))
+                || key.ends_with("_path")
+                || key.ends_with("_checkpoint")
+        })
+        .map(|(_, value)| value.clone())
+        // Absolute paths and hub ids (`facebook/w2v-bert-2.0`) are resolved elsewhere.
+        .filter(|value| !Path::new(value).is_absolute() && !value.contains("://"))
+        .collect()
+}
+
+/// Checks that the downloaded model matches the installed index-tts code: the model
+/// config's `version` against the code's, and every file the config references.
+#[tauri::command(rename_all = "snake_case")]
+pub fn check_model_code_compat(
+    target_dir: String,
+    model_dir: Option<String>,
+) -> Result<ModelCodeCompat, String> {
+    let repo_path = Path::new(&target_dir);
+    if !repo_path.join("pyproject.toml").is_file() {
+        return Err(format!("{} is not an index-tts checkout.", target_dir));
+    }
+    let model_dir = model_dir
+        .filter(|dir| !dir.trim().is_empty())
+        .map(|dir| Path::new(&dir).to_path_buf())
+        .unwrap_or_else(|| repo_path.join("checkpoints"));
+    let model_config =
+        read_config_scalars(&model_dir.join(MODEL_CONFIG_FILE)).ok_or_else(|| {
+            format!(
+                "{} has no readable {}. Download the model first.",
+                model_dir.display(),
+                MODEL_CONFIG_FILE
+            )
+        })?;
+
+    let code_version = read_project_version(repo_path);
+    let model_version = model_config.get("version").cloned();
+    // A model dir inside the repo is the shipped config itself; nothing to compare.
+    let shipped_config = repo_path.join("checkpoints").join(MODEL_CONFIG_FILE);
+    let expected_model_version = if model_dir.join(MODEL_CONFIG_FILE) == shipped_config {
+        None
+    } else {
+        read_config_scalars(&shipped_config).and_then(|config| config.get("version").cloned())
+    };
+
+    let mut problems = Vec::new();
+    let expected_major = expected_model_version
+        .as_deref()
+        .or(code_version.as_deref())
+        .and_then(major_version);
+    match (
+        model_version.as_deref().and_then(major_version),
+        expected_major,
+    ) {
+        (Some(model), Some(expected)) if model != expected => problems.push(format!(
+            "The model is version {} but the code expects version {}.",
+            model_version.as_deref().unwrap_or_default(),
+            expected_model_version
+                .as_deref()
+                .or(code_version.as_deref())
+                .unwrap_or_default()
+        )),
+        (None, Some(expected)) if expected >= 2 => problems.push(
+            "The model config has no version field; it is probably an IndexTTS 1.x model."
+                .to_string(),
+        ),
+        _ => {}
+    }
+
+    let missing_files: Vec<String> = referenced_files(&model_config)
+        .into_iter()
+        .filter(|file| !model_dir.join(file).exists())
+        .collect();
+    if !missing_files.is_empty() {
+        problems.push(format!(
+            "The model config references files that are missing: {}.",
+            missing_files.join(", ")
+        ));
+    }
+
+    let compatible = problems.is_empty();
+    let recommendation = (!compatible).then(|| {
+        let model_older = model_version
+            .as_deref()
+            .and_then(major_version)
+            .zip(expected_major)
+            .is_some_and(|(model, expected)| model < expected);
+        if model_older || !missing_files.is_empty() {
+            "Re-download the model so it matches the installed code.".to_string()
+        } else {
+            "Check out an index-tts version that matches this model, or download the model for the installed code.".to_string()
+        }
+    });
+
+    Ok(ModelCodeCompat {
+        compatible,
+        code_version,
+        model_version,
+        expected_model_version,
+        missing_files,
+        problems,
+        recommendation,
+    })
+}
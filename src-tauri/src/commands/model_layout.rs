@@ -0,0 +1,225 @@
+// src-tauri/src/commands/model_layout.rs
+
+use super::command_utils::clean_path_arg;
+use super::deployments::set_deployment_model_dir;
+use super::engine::{persist_engine_config, EngineManager};
+use super::index_tts::{model_has_core_files, MODEL_CORE_FILES};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+// Deep enough for `IndexTeam/IndexTTS-2` and `models--IndexTeam--IndexTTS-2/snapshots/<rev>`.
+const MAX_NESTING_DEPTH: usize = 4;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelLayoutReport {
+    pub model_dir: String,
+    // webui.py finds every core file directly in `model_dir`.
+    pub valid: bool,
+    pub missing: Vec<String>,
+    // Subfolder the download actually put the model files in.
+    pub nested_dir: Option<String>,
+}
+
+/// Where a download was asked to put the model and where its files actually are.
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelLocation {
+    pub requested_dir: String,
+    pub effective_dir: String,
+    pub found: bool,
+    // The files ended up somewhere other than `requested_dir`.
+    pub differs: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelFileMove {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelLayoutRepair {
+    pub moved: Vec<ModelFileMove>,
+    // Entries left in place because `model_dir` already had something with that name.
+    pub skipped: Vec<String>,
+    pub layout: ModelLayoutReport,
+}
+
+/// Breadth-first search for the shallowest subfolder holding every core model file.
+fn find_nested_model_dir(model_dir: &Path) -> Option<PathBuf> {
+    let mut level = vec![model_dir.to_path_buf()];
+    for _ in 0..MAX_NESTING_DEPTH {
+        let mut next = Vec::new();
+        for dir in level {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_dir() || entry.file_name() == ".git" {
+                    continue;
+                }
+                if model_has_core_files(&path) {
+                    return Some(path);
+                }
+                next.push(path);
+            }
+        }
+        level = next;
+    }
+    None
+}
+
+fn layout_report(model_dir: &Path) -> ModelLayoutReport {
+    let missing: Vec<String> = MODEL_CORE_FILES
+        .iter()
+        .filter(|file| !model_dir.join(file).is_file())
+        .map(|file| file.to_string())
+        .collect();
+    let valid = missing.is_empty();
+    ModelLayoutReport {
+        model_dir: model_dir.display().to_string(),
+        valid,
+        missing,
+        nested_dir: if valid {
+            None
+        } else {
+            find_nested_model_dir(model_dir).map(|dir| dir.display().to_string())
+        },
+    }
+}
+
+/// Moves `from` to `to`. Symlinks (HF cache snapshots point into `blobs/`) are replaced
+/// by a copy of their target, since a relative link would break once moved.
+fn relocate(from: &Path, to: &Path) -> Result<(), String> {
+    let is_symlink = fs::symlink_metadata(from).is_ok_and(|meta| meta.file_type().is_symlink());
+    let result = if is_symlink && from.is_file() {
+        fs::copy(from, to).and_then(|_| fs::remove_file(from))
+    } else {
+        fs::rename(from, to)
+    };
+    result.map_err(|e| {
+        format!(
+            "Failed to move {} to {}: {}",
+            from.display(),
+            to.display(),
+            e
+        )
+    })
+}
+
+/// Removes `dir` and its parents up to (not including) `stop` while they are empty.
+fn remove_empty_dirs(dir: &Path, stop: &Path) {
+    let mut current = Some(dir);
+    while let Some(dir) = current.filter(|dir| *dir != stop && dir.starts_with(stop)) {
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        current = dir.parent();
+    }
+}
+
+/// Finds the model files under `requested`, following the extra nesting some download
+/// tools add (`IndexTeam/IndexTTS-2`, HF cache snapshots).
+pub fn locate_model(requested: &Path) -> ModelLocation {
+    let effective = if model_has_core_files(requested) {
+        Some(requested.to_path_buf())
+    } else {
+        find_nested_model_dir(requested)
+    };
+    ModelLocation {
+        requested_dir: requested.display().to_string(),
+        found: effective.is_some(),
+        differs: effective.as_deref().is_some_and(|dir| dir != requested),
+        effective_dir: effective
+            .unwrap_or_else(|| requested.to_path_buf())
+            .display()
+            .to_string(),
+    }
+}
+
+/// Points the engine and the deployment registry at where the model really is.
+pub fn record_model_location(app_handle: &AppHandle, target_dir: &str, location: &ModelLocation) {
+    if !location.found {
+        return;
+    }
+    app_handle.state::<EngineManager>().lock().model_dir = location.effective_dir.clone();
+    let _ = persist_engine_config(app_handle);
+    let _ = set_deployment_model_dir(app_handle, target_dir, &location.effective_dir);
+}
+
+/// Compares the requested model directory (default `checkpoints` in `target_dir`) with
+/// where the model files actually are, and records the real location.
+#[tauri::command(rename_all = "snake_case")]
+pub fn reconcile_model_location(
+    app_handle: AppHandle,
+    target_dir: String,
+    model_save_path: Option<String>,
+) -> Result<ModelLocation, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    let requested = model_save_path
+        .map(|path| clean_path_arg(&path))
+        .filter(|path| !path.is_empty())
+        .unwrap_or_else(|| "checkpoints".to_string());
+    let requested = Path::new(&target_dir).join(requested);
+    if !requested.is_dir() {
+        return Err(format!(
+            "Model directory {} does not exist.",
+            requested.display()
+        ));
+    }
+    let location = locate_model(&requested);
+    record_model_location(&app_handle, &target_dir, &location);
+    Ok(location)
+}
+
+/// Checks that the model files sit directly in `model_dir`, where webui.py looks for
+/// them, and finds them when a download nested them in a subfolder.
+#[tauri::command(rename_all = "snake_case")]
+pub fn check_model_layout(model_dir: String) -> Result<ModelLayoutReport, String> {
+    let path = Path::new(&model_dir);
+    if !path.is_dir() {
+        return Err(format!("Model directory {} does not exist.", model_dir));
+    }
+    Ok(layout_report(path))
+}
+
+/// Moves the contents of a nested model folder up into `model_dir`.
+#[tauri::command(rename_all = "snake_case")]
+pub fn repair_model_layout(model_dir: String) -> Result<ModelLayoutRepair, String> {
+    let root = Path::new(&model_dir);
+    let report = check_model_layout(model_dir.clone())?;
+    let Some(nested) = report.nested_dir.as_deref().map(PathBuf::from) else {
+        return Ok(ModelLayoutRepair {
+            moved: Vec::new(),
+            skipped: Vec::new(),
+            layout: report,
+        });
+    };
+
+    let mut moved = Vec::new();
+    let mut skipped = Vec::new();
+    let entries =
+        fs::read_dir(&nested).map_err(|e| format!("Failed to read {}: {}", nested.display(), e))?;
+    for entry in entries.flatten() {
+        let from = entry.path();
+        let to = root.join(entry.file_name());
+        if to.exists() {
+            skipped.push(to.display().to_string());
+            continue;
+        }
+        relocate(&from, &to)?;
+        moved.push(ModelFileMove {
+            from: from.display().to_string(),
+            to: to.display().to_string(),
+        });
+    }
+    remove_empty_dirs(&nested, root);
+
+    Ok(ModelLayoutRepair {
+        moved,
+        skipped,
+        layout: layout_report(root),
+    })
+}
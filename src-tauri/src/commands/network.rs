@@ -0,0 +1,200 @@
+// src-tauri/src/commands/network.rs
+
+use super::command_utils::new_command;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, TcpListener, UdpSocket};
+
+const DEFAULT_PORT: u16 = 7860;
+
+// Turns the output of the platform's address listing tool into `(interface, address)` pairs.
+type AddressParser = fn(&str) -> Vec<(String, String)>;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NetworkAddress {
+    pub interface: String,
+    pub address: String,
+    pub ipv6: bool,
+    pub loopback: bool,
+    pub link_local: bool,
+    // Address the OS routes outbound traffic through; the best one to share on a LAN.
+    pub primary: bool,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WindowsIpAddress {
+    interface_alias: Option<String>,
+    #[serde(rename = "IPAddress")]
+    ip_address: Option<String>,
+}
+
+/// The local address used for outbound traffic. Connecting a UDP socket only selects
+/// a route; nothing is sent.
+fn primary_ipv4() -> Option<IpAddr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80)).ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+pub fn server_url(address: &IpAddr, port: u16) -> String {
+    match address {
+        IpAddr::V6(v6) => format!("http://[{}]:{}", v6, port),
+        IpAddr::V4(v4) => format!("http://{}:{}", v4, port),
+    }
+}
+
+/// `(interface, address)` pairs from `ip -o addr show`, e.g.
+/// `2: eth0    inet 192.168.1.5/24 brd 192.168.1.255 scope global eth0`.
+fn parse_ip_addr(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let family = fields.get(2)?;
+            if *family != "inet" && *family != "inet6" {
+                return None;
+            }
+            let address = fields.get(3)?.split('/').next()?;
+            Some((fields.get(1)?.to_string(), address.to_string()))
+        })
+        .collect()
+}
+
+/// `(interface, address)` pairs from BSD/macOS `ifconfig` output.
+fn parse_ifconfig(output: &str) -> Vec<(String, String)> {
+    let mut interface = String::new();
+    let mut pairs = Vec::new();
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            interface = line.split(':').next().unwrap_or_default().to_string();
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        if let (Some("inet" | "inet6"), Some(address)) = (fields.next(), fields.next()) {
+            // Drop the `%en0` zone suffix of link-local IPv6 addresses.
+            let address = address.split('%').next().unwrap_or(address);
+            pairs.push((interface.clone(), address.to_string()));
+        }
+    }
+    pairs
+}
+
+fn parse_windows_addresses(output: &str) -> Vec<(String, String)> {
+    // ConvertTo-Json emits a bare object instead of an array for a single entry.
+    let entries: Vec<WindowsIpAddress> = serde_json::from_str(output)
+        .or_else(|_| serde_json::from_str(output).map(|entry| vec![entry]))
+        .unwrap_or_default();
+    entries
+        .into_iter()
+        .filter_map(|entry| Some((entry.interface_alias?, entry.ip_address?)))
+        .collect()
+}
+
+async fn interface_addresses() -> Result<Vec<(String, String)>, String> {
+    let (program, args, parser): (&str, &[&str], AddressParser) = match std::env::consts::OS {
+        "windows" => (
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "Get-NetIPAddress | Select-Object InterfaceAlias,IPAddress | ConvertTo-Json",
+            ],
+            parse_windows_addresses,
+        ),
+        "linux" => ("ip", &["-o", "addr", "show"], parse_ip_addr),
+        _ => ("ifconfig", &[], parse_ifconfig),
+    };
+    let output = new_command(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute {}: {}", program, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(parser(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn is_link_local(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(v4) => v4.is_link_local(),
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xffc0) == 0xfe80,
+    }
+}
+
+/// Whether the server can bind to `host`: the wildcard address, or an address assigned to
+/// one of this machine's interfaces. Host names other than localhost are left to the OS.
+pub fn validate_bind_host(host: &str) -> Result<(), String> {
+    let Ok(address) = host.parse::<IpAddr>() else {
+        return Ok(());
+    };
+    if address.is_unspecified() || address.is_loopback() {
+        return Ok(());
+    }
+    match TcpListener::bind((address, 0)) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrNotAvailable => Err(format!(
+            "{} is not an address of this machine. Pick one from list_network_interfaces.",
+            host
+        )),
+        Err(e) => Err(format!("Cannot bind to {}: {}", host, e)),
+    }
+}
+
+/// URL to share for a server bound to `host`. A wildcard bind is advertised on the
+/// primary LAN address, since 0.0.0.0 itself can't be dialed.
+pub fn advertised_url(host: &str, port: u16) -> String {
+    match host.parse::<IpAddr>() {
+        Ok(address) if address.is_unspecified() => server_url(
+            &primary_ipv4().unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            port,
+        ),
+        Ok(address) => server_url(&address, port),
+        Err(_) => format!("http://{}:{}", host, port),
+    }
+}
+
+/// Lists the addresses the server can bind to, with the URL each would be reachable at.
+#[tauri::command]
+pub async fn list_network_interfaces(port: Option<u16>) -> Result<Vec<NetworkAddress>, String> {
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let primary = primary_ipv4();
+
+    let mut addresses: Vec<NetworkAddress> = Vec::new();
+    for (interface, address) in interface_addresses().await? {
+        let Ok(ip) = address.parse::<IpAddr>() else {
+            continue;
+        };
+        if addresses.iter().any(|existing| existing.address == address) {
+            continue;
+        }
+        addresses.push(NetworkAddress {
+            interface,
+            address,
+            ipv6: ip.is_ipv6(),
+            loopback: ip.is_loopback(),
+            link_local: is_link_local(&ip),
+            primary: primary == Some(ip),
+            url: server_url(&ip, port),
+        });
+    }
+    if !addresses.iter().any(|address| address.loopback) {
+        let loopback = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        addresses.push(NetworkAddress {
+            interface: "loopback".to_string(),
+            address: loopback.to_string(),
+            ipv6: false,
+            loopback: true,
+            link_local: false,
+            primary: false,
+            url: server_url(&loopback, port),
+        });
+    }
+    addresses.sort_by_key(|address| (!address.primary, address.loopback, address.ipv6));
+    Ok(addresses)
+}
@@ -0,0 +1,52 @@
+// src-tauri/src/commands/notifications.rs
+
+use super::settings::{load_settings, save_settings};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+// The frontend turns these into system notifications; they are only emitted when the
+// user opted in via `set_notifications_enabled`.
+const DEPLOY_FINISHED_EVENT: &str = "deploy-finished";
+const SERVER_EVENT: &str = "server-event";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DeployFinishedEvent {
+    pub target_dir: String,
+    pub success: bool,
+    pub duration_secs: f64,
+    pub failed_stage: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerEvent {
+    pub kind: String,
+    pub success: bool,
+    pub duration_secs: f64,
+    pub exit_code: Option<i32>,
+    pub message: String,
+}
+
+fn notifications_enabled(app_handle: &AppHandle) -> bool {
+    load_settings(app_handle).notifications_enabled
+}
+
+pub fn notify_deploy_finished(app_handle: &AppHandle, event: DeployFinishedEvent) {
+    if notifications_enabled(app_handle) {
+        let _ = app_handle.emit(DEPLOY_FINISHED_EVENT, event);
+    }
+}
+
+pub fn notify_server_event(app_handle: &AppHandle, event: ServerEvent) {
+    if notifications_enabled(app_handle) {
+        let _ = app_handle.emit(SERVER_EVENT, event);
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_notifications_enabled(app_handle: AppHandle, enabled: bool) -> Result<bool, String> {
+    let mut settings = load_settings(&app_handle);
+    settings.notifications_enabled = enabled;
+    save_settings(&app_handle, &settings)?;
+    Ok(enabled)
+}
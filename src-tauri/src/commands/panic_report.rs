@@ -0,0 +1,112 @@
+// src-tauri/src/commands/panic_report.rs
+
+use super::deployments::unix_timestamp;
+use serde::Serialize;
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager};
+
+const BACKEND_PANIC_EVENT: &str = "backend-panic";
+const PANIC_REPORT_PREFIX: &str = "panic-";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PanicReport {
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: String,
+    pub timestamp: u64,
+    // Where the full report (with backtrace) was written, if the log dir was writable.
+    pub report_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PanicReportFile {
+    pub path: String,
+    pub timestamp: u64,
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+fn write_report(log_dir: &Path, report: &PanicReport, backtrace: &Backtrace) -> Option<String> {
+    fs::create_dir_all(log_dir).ok()?;
+    let path = log_dir.join(format!("{}{}.log", PANIC_REPORT_PREFIX, report.timestamp));
+    let content = format!(
+        "IndexTTS Hub {} panicked\nthread: {}\nlocation: {}\nmessage: {}\n\nbacktrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        report.thread,
+        report.location.as_deref().unwrap_or("unknown"),
+        report.message,
+        backtrace
+    );
+    fs::write(&path, content).ok()?;
+    Some(path.display().to_string())
+}
+
+/// Writes every panic (message, location and a backtrace) to the app log directory and
+/// emits `backend-panic` so the UI can ask the user to report it. The default hook
+/// still runs afterwards, so stderr output is unchanged.
+pub fn install_panic_hook(app_handle: &AppHandle) {
+    let log_dir = app_handle.path().app_log_dir().ok();
+    let app_handle = app_handle.clone();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let backtrace = Backtrace::force_capture();
+        let mut report = PanicReport {
+            message: panic_message(info),
+            location: info
+                .location()
+                .map(|location| format!("{}:{}", location.file(), location.line())),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            timestamp: unix_timestamp(),
+            report_path: None,
+        };
+        report.report_path = log_dir
+            .as_ref()
+            .and_then(|dir| write_report(dir, &report, &backtrace));
+        let _ = app_handle.emit(BACKEND_PANIC_EVENT, &report);
+        default_hook(info);
+    }));
+}
+
+/// Lists panic reports left in the log directory, newest first, so a report from a
+/// crash that took the whole app down can be offered on the next launch.
+#[tauri::command]
+pub fn list_panic_reports(app_handle: AppHandle) -> Result<Vec<PanicReportFile>, String> {
+    let log_dir = app_handle
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve app log directory: {}", e))?;
+    let Ok(entries) = fs::read_dir(&log_dir) else {
+        return Ok(Vec::new());
+    };
+    let mut reports: Vec<PanicReportFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let timestamp = name
+                .strip_prefix(PANIC_REPORT_PREFIX)?
+                .strip_suffix(".log")?
+                .parse()
+                .ok()?;
+            Some(PanicReportFile {
+                path: entry.path().display().to_string(),
+                timestamp,
+            })
+        })
+        .collect();
+    reports.sort_by_key(|report| std::cmp::Reverse(report.timestamp));
+    Ok(reports)
+}
@@ -0,0 +1,193 @@
+// src-tauri/src/commands/platform_defaults.rs
+
+use super::command_utils::{inherited_locale, new_command};
+use super::config_store::{load_json, save_json};
+use super::deployments::unix_timestamp;
+use super::engine::{persist_engine_config, EngineManager, RunMode};
+use super::gpu_usage::{query_gpu_usage, vram_required_mb};
+use super::log_files::{append_log_line, APP_LOG};
+use super::tasks::spawn_tracked;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+pub const PLATFORM_DEFAULTS_FILE: &str = "platform_defaults.json";
+// Locales (language_REGION) whose users are best served by the domestic mirrors.
+const MAINLAND_CHINA_LOCALES: [&str; 2] = ["zh_cn", "zh_hans_cn"];
+
+/// Starting values for the setup wizard, derived from the hardware and locale. They
+/// are suggestions: they only fill in values the user has not chosen yet.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlatformDefaults {
+    pub run_mode: RunMode,
+    // The same choice as `run_mode`, spelled the way launch settings take it.
+    pub device: String,
+    pub precision: String,
+    pub network_environment: String,
+    pub gpu_name: Option<String>,
+    pub locale: Option<String>,
+    pub reasons: Vec<String>,
+    pub detected_at: u64,
+}
+
+/// The user's locale: the POSIX variables first, then what the desktop reports.
+async fn system_locale() -> Option<String> {
+    if let Some(locale) = inherited_locale().filter(|locale| locale != "C" && locale != "POSIX") {
+        return Some(locale);
+    }
+    let output = if cfg!(target_os = "windows") {
+        new_command("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                "[System.Globalization.CultureInfo]::CurrentCulture.Name",
+            ])
+            .output()
+            .await
+    } else if cfg!(target_os = "macos") {
+        new_command("defaults")
+            .args(["read", "-g", "AppleLocale"])
+            .output()
+            .await
+    } else {
+        return None;
+    };
+    let output = output.ok().filter(|output| output.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|locale| !locale.is_empty())
+}
+
+fn is_mainland_china_locale(locale: &str) -> bool {
+    let normalized = locale
+        .split(['.', '@'])
+        .next()
+        .unwrap_or(locale)
+        .replace('-', "_")
+        .to_lowercase();
+    MAINLAND_CHINA_LOCALES.contains(&normalized.as_str())
+}
+
+/// Derives the run mode, precision and network environment for this machine.
+pub async fn detect_platform_defaults() -> PlatformDefaults {
+    let mut reasons = Vec::new();
+    let mut gpu_name = None;
+
+    let (run_mode, precision) = if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        reasons.push("Apple Silicon detected: run on the GPU through Metal (mps).".to_string());
+        (RunMode::Mps, "fp32")
+    } else {
+        let usage = query_gpu_usage().await;
+        match usage
+            .devices
+            .iter()
+            .max_by_key(|device| device.memory_total_mb)
+        {
+            Some(device) => {
+                gpu_name = Some(device.name.clone());
+                let total_mb = device.memory_total_mb.unwrap_or(0);
+                // fp16 roughly halves the VRAM the model needs.
+                let precision = if vram_required_mb("fp32").is_ok_and(|needed| total_mb >= needed) {
+                    "fp32"
+                } else {
+                    "fp16"
+                };
+                reasons.push(format!(
+                    "NVIDIA GPU detected ({}, {} MB): run on CUDA with {}.",
+                    device.name, total_mb, precision
+                ));
+                (RunMode::Cuda, precision)
+            }
+            None => {
+                reasons.push("No supported GPU detected: run on the CPU.".to_string());
+                (RunMode::Cpu, "fp32")
+            }
+        }
+    };
+
+    let locale = system_locale().await;
+    let network_environment = match &locale {
+        Some(locale) if is_mainland_china_locale(locale) => {
+            reasons.push(format!(
+                "Locale {} suggests mainland China: use the domestic mirrors.",
+                locale
+            ));
+            "mainland_china"
+        }
+        _ => "overseas",
+    };
+
+    PlatformDefaults {
+        run_mode,
+        device: match run_mode {
+            RunMode::Cpu => "cpu",
+            RunMode::Cuda => "cuda",
+            RunMode::Mps => "mps",
+        }
+        .to_string(),
+        precision: precision.to_string(),
+        network_environment: network_environment.to_string(),
+        gpu_name,
+        locale,
+        reasons,
+        detected_at: unix_timestamp(),
+    }
+}
+
+fn load_platform_defaults(app_handle: &AppHandle) -> Option<PlatformDefaults> {
+    load_json(app_handle, PLATFORM_DEFAULTS_FILE).ok().flatten()
+}
+
+/// On first run, detects the platform defaults, stores them and seeds the engine's run
+/// mode with them. Later runs keep whatever the user has chosen since.
+pub fn init_platform_defaults(app_handle: &AppHandle) {
+    if load_platform_defaults(app_handle).is_some() {
+        return;
+    }
+    let task_app = app_handle.clone();
+    spawn_tracked(app_handle, "platform_defaults", async move {
+        let defaults = detect_platform_defaults().await;
+        if let Err(e) = save_json(&task_app, PLATFORM_DEFAULTS_FILE, &defaults) {
+            append_log_line(
+                &task_app,
+                APP_LOG,
+                "setup",
+                &format!("Failed to save platform defaults: {}", e),
+            );
+        }
+        let seeded = {
+            let engine = task_app.state::<EngineManager>();
+            let mut config = engine.lock();
+            let unset = config.install_dir.is_empty() && config.run_mode == RunMode::default();
+            if unset {
+                config.run_mode = defaults.run_mode;
+            }
+            unset
+        };
+        if seeded {
+            if let Err(e) = persist_engine_config(&task_app) {
+                append_log_line(
+                    &task_app,
+                    APP_LOG,
+                    "setup",
+                    &format!("Failed to save the detected run mode: {}", e),
+                );
+            }
+        }
+    });
+}
+
+/// Suggested run mode, precision and network environment for prefilling the setup
+/// wizard. Returns the values stored on first run unless `refresh` re-detects them.
+#[tauri::command]
+pub async fn suggest_defaults(
+    app_handle: AppHandle,
+    refresh: Option<bool>,
+) -> Result<PlatformDefaults, String> {
+    if !refresh.unwrap_or(false) {
+        if let Some(defaults) = load_platform_defaults(&app_handle) {
+            return Ok(defaults);
+        }
+    }
+    let defaults = detect_platform_defaults().await;
+    save_json(&app_handle, PLATFORM_DEFAULTS_FILE, &defaults)?;
+    Ok(defaults)
+}
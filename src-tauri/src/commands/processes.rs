@@ -0,0 +1,142 @@
+// src-tauri/src/commands/processes.rs
+
+use super::deployments::load_deployments;
+use super::engine::EngineManager;
+use super::server::ServerChildProcess;
+use serde::Serialize;
+use std::path::Path;
+use sysinfo::{Pid, System};
+use tauri::{AppHandle, Manager};
+
+// Command-line fragments that identify an index-tts process regardless of where it lives.
+const INDEX_TTS_MARKERS: [&str; 3] = ["webui.py", "index-tts", "indextts"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RelatedProcess {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub command: String,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+    // The server started by this app, or one of its children.
+    pub managed: bool,
+}
+
+/// Deployment folders a stray process may reference: the registry plus the configured dir.
+fn known_dirs(app_handle: &AppHandle) -> Vec<String> {
+    let mut dirs: Vec<String> = load_deployments(app_handle)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|deployment| deployment.target_dir)
+        .collect();
+    let install_dir = app_handle
+        .state::<EngineManager>()
+        .lock()
+        .install_dir
+        .clone();
+    if !install_dir.is_empty() && !dirs.contains(&install_dir) {
+        dirs.push(install_dir);
+    }
+    dirs
+}
+
+pub fn is_descendant_of(sys: &System, pid: Pid, ancestor: Pid) -> bool {
+    let mut current = Some(pid);
+    while let Some(pid) = current {
+        if pid == ancestor {
+            return true;
+        }
+        current = sys.process(pid).and_then(|process| process.parent());
+    }
+    false
+}
+
+fn scan_related(app_handle: &AppHandle, sys: &System) -> Vec<RelatedProcess> {
+    let own_pid = sysinfo::get_current_pid().ok();
+    let managed_pid = app_handle
+        .state::<ServerChildProcess>()
+        .lock()
+        .as_ref()
+        .and_then(|child| child.id())
+        .map(Pid::from_u32);
+    let dirs = known_dirs(app_handle);
+
+    let mut related: Vec<RelatedProcess> = sys
+        .processes()
+        .iter()
+        .filter(|(pid, _)| Some(**pid) != own_pid)
+        .filter_map(|(pid, process)| {
+            let command = process.cmd().join(" ");
+            let lower = command.to_lowercase();
+            // The app itself is "indextts-hub"; don't flag it or its webview helpers.
+            let references_index_tts = INDEX_TTS_MARKERS.iter().any(|marker| {
+                lower
+                    .match_indices(marker)
+                    .any(|(at, _)| !lower[at..].starts_with("indextts-hub"))
+            });
+            let references_dir = dirs.iter().any(|dir| {
+                command.contains(dir.as_str())
+                    || process
+                        .cwd()
+                        .is_some_and(|cwd| cwd.starts_with(Path::new(dir)))
+            });
+            if !references_index_tts && !references_dir {
+                return None;
+            }
+            Some(RelatedProcess {
+                pid: pid.as_u32(),
+                parent_pid: process.parent().map(|parent| parent.as_u32()),
+                name: process.name().to_string(),
+                command,
+                cpu_percent: process.cpu_usage(),
+                memory_bytes: process.memory(),
+                managed: managed_pid.is_some_and(|managed| is_descendant_of(sys, *pid, managed)),
+            })
+        })
+        .collect();
+    related.sort_by_key(|process| (!process.managed, process.pid));
+    related
+}
+
+/// Lists python/uv/gradio processes that belong to an index-tts deployment, including
+/// strays left behind by crashes, with their CPU and memory use.
+#[tauri::command]
+pub async fn list_related_processes(app_handle: AppHandle) -> Result<Vec<RelatedProcess>, String> {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    // CPU usage is measured between two refreshes.
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    sys.refresh_processes();
+    Ok(scan_related(&app_handle, &sys))
+}
+
+/// Kills a process reported by `list_related_processes`. Any other PID is refused, and
+/// the managed server has to be stopped with `stop_index_tts_server`.
+#[tauri::command]
+pub fn kill_process(app_handle: AppHandle, pid: u32) -> Result<bool, String> {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    let Some(target) = scan_related(&app_handle, &sys)
+        .into_iter()
+        .find(|process| process.pid == pid)
+    else {
+        return Err(format!(
+            "Process {} is not a recognized index-tts process.",
+            pid
+        ));
+    };
+    if target.managed {
+        return Err(format!(
+            "Process {} belongs to the running server; stop it with stop_index_tts_server.",
+            pid
+        ));
+    }
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or_else(|| format!("Process {} has already exited.", pid))?;
+    if !process.kill() {
+        return Err(format!("Failed to kill process {} ({}).", pid, target.name));
+    }
+    Ok(true)
+}
@@ -0,0 +1,464 @@
+// src-tauri/src/commands/python_env.rs
+
+use super::command_utils::new_command;
+use super::server_config::{validate_launch_settings, LaunchSettings};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+// Modules webui.py needs before it gets to loading the model.
+const CORE_IMPORTS: [&str; 5] = [
+    "torch",
+    "torchaudio",
+    "transformers",
+    "gradio",
+    "indextts.infer_v2",
+];
+// Importing torch cold can take a while on slow disks.
+const IMPORT_CHECK_TIMEOUT: Duration = Duration::from_secs(180);
+
+const IMPORT_CHECK_SCRIPT: &str = r#"
+import importlib
+import json
+import sys
+
+results = {}
+for name in sys.argv[1:]:
+    try:
+        importlib.import_module(name)
+        results[name] = None
+    except BaseException as e:
+        results[name] = "%s: %s" % (type(e).__name__, e)
+print(json.dumps(results))
+"#;
+
+// Creates a tensor of the requested dtype on the device and runs a matmul on it, which
+// is where CPU builds without half-precision kernels fail.
+const PRECISION_PROBE_SCRIPT: &str = r#"
+import json
+import sys
+
+device, precision = sys.argv[1], sys.argv[2]
+result = {"torch_version": None, "cuda_version": None, "error": None}
+try:
+    import torch
+    result["torch_version"] = torch.__version__
+    result["cuda_version"] = torch.version.cuda
+    dtype = torch.float16 if precision == "fp16" else torch.float32
+    x = torch.ones((4, 4), dtype=dtype, device=device)
+    (x @ x).sum().item()
+except BaseException as e:
+    result["error"] = "%s: %s" % (type(e).__name__, e)
+print(json.dumps(result))
+"#;
+
+const INTERPRETER_SCRIPT: &str = r#"
+import json
+import platform
+import sys
+
+print(json.dumps({
+    "executable": sys.executable,
+    "base_executable": getattr(sys, "_base_executable", sys.executable),
+    "prefix": sys.prefix,
+    "version": platform.python_version(),
+}))
+"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TorchBuildInfo {
+    pub version: String,
+    // Local version tag such as "cu128" or "cpu"; `None` when the wheel has no tag.
+    pub build_tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EnvFreeze {
+    pub target_dir: String,
+    pub packages: Vec<InstalledPackage>,
+    pub torch: Option<TorchBuildInfo>,
+    pub exported_to: Option<String>,
+}
+
+pub async fn list_installed_packages(target_dir: &str) -> Result<Vec<InstalledPackage>, String> {
+    if !Path::new(target_dir).join(".venv").is_dir() {
+        return Err(format!(
+            "No virtual environment found in {}. Please run the environment setup first.",
+            target_dir
+        ));
+    }
+
+    let output = new_command("uv")
+        .args(["pip", "list", "--format", "json"])
+        .current_dir(target_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute uv pip list: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "uv pip list failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse uv pip list output: {}", e))
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PrecisionSupport {
+    pub device: String,
+    pub precision: String,
+    pub supported: bool,
+    pub torch_version: Option<String>,
+    // CUDA version torch was built against; `None` for CPU-only builds.
+    pub cuda_version: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrecisionProbeOutput {
+    torch_version: Option<String>,
+    cuda_version: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportFailure {
+    pub module: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportCheck {
+    pub checked: Vec<String>,
+    pub failed: Vec<ImportFailure>,
+}
+
+impl ImportCheck {
+    pub fn ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Imports index-tts's core modules in the deployment's environment without
+/// loading the model, so a broken install shows up in seconds.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_imports(target_dir: String) -> Result<ImportCheck, String> {
+    if !Path::new(&target_dir).join(".venv").is_dir() {
+        return Err(format!(
+            "No virtual environment found in {}. Please run the environment setup first.",
+            target_dir
+        ));
+    }
+
+    let mut command = new_command("uv");
+    command
+        .args(["run", "--no-sync", "python", "-c", IMPORT_CHECK_SCRIPT])
+        .args(CORE_IMPORTS)
+        .current_dir(&target_dir)
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(IMPORT_CHECK_TIMEOUT, command.output())
+        .await
+        .map_err(|_| {
+            format!(
+                "Import check timed out after {}s.",
+                IMPORT_CHECK_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| format!("Failed to execute uv run: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results: BTreeMap<String, Option<String>> = stdout
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line.trim()).ok())
+        .ok_or_else(|| {
+            format!(
+                "Python could not run the import check: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        })?;
+
+    Ok(ImportCheck {
+        checked: CORE_IMPORTS
+            .iter()
+            .map(|module| module.to_string())
+            .collect(),
+        failed: results
+            .into_iter()
+            .filter_map(|(module, error)| error.map(|error| ImportFailure { module, error }))
+            .collect(),
+    })
+}
+
+fn torch_build_info(packages: &[InstalledPackage]) -> Option<TorchBuildInfo> {
+    let torch = packages
+        .iter()
+        .find(|package| package.name.eq_ignore_ascii_case("torch"))?;
+    Some(TorchBuildInfo {
+        version: torch.version.clone(),
+        build_tag: torch
+            .version
+            .split_once('+')
+            .map(|(_, tag)| tag.to_string()),
+    })
+}
+
+/// Lists the packages installed in the deployment's environment, optionally writing
+/// the result to `export_path` as JSON for bug reports.
+/// Runs a tiny torch computation with `precision` on `device` in the deployment's
+/// environment, so a dtype the installed torch build can't run is caught before the
+/// model load.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_precision_support(
+    target_dir: String,
+    device: String,
+    precision: String,
+    gpu_index: Option<u32>,
+) -> Result<PrecisionSupport, String> {
+    validate_launch_settings(&LaunchSettings {
+        device: Some(device.clone()),
+        precision: Some(precision.clone()),
+        ..LaunchSettings::default()
+    })?;
+    if !Path::new(&target_dir).join(".venv").is_dir() {
+        return Err(format!(
+            "No virtual environment found in {}. Please run the environment setup first.",
+            target_dir
+        ));
+    }
+
+    let mut command = new_command("uv");
+    command
+        .args(["run", "--no-sync", "python", "-c", PRECISION_PROBE_SCRIPT])
+        .args([&device, &precision])
+        .current_dir(&target_dir)
+        .kill_on_drop(true);
+    // Same card selection as the launch.
+    if let (Some(index), "cuda") = (gpu_index, device.as_str()) {
+        command.env("CUDA_VISIBLE_DEVICES", index.to_string());
+    }
+    let output = tokio::time::timeout(IMPORT_CHECK_TIMEOUT, command.output())
+        .await
+        .map_err(|_| {
+            format!(
+                "Precision check timed out after {}s.",
+                IMPORT_CHECK_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| format!("Failed to execute uv run: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let probe: PrecisionProbeOutput = stdout
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line.trim()).ok())
+        .ok_or_else(|| {
+            format!(
+                "Python could not run the precision check: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        })?;
+
+    Ok(PrecisionSupport {
+        supported: probe.error.is_none(),
+        error: probe.error.map(|error| {
+            format!(
+                "torch {} cannot run {} on {}: {}",
+                probe.torch_version.as_deref().unwrap_or("(not installed)"),
+                precision,
+                device,
+                error
+            )
+        }),
+        device,
+        precision,
+        torch_version: probe.torch_version,
+        cuda_version: probe.cuda_version,
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn env_freeze(
+    target_dir: String,
+    export_path: Option<String>,
+) -> Result<EnvFreeze, String> {
+    let mut packages = list_installed_packages(&target_dir).await?;
+    packages.sort_by_key(|package| package.name.to_lowercase());
+
+    let mut freeze = EnvFreeze {
+        torch: torch_build_info(&packages),
+        target_dir,
+        packages,
+        exported_to: None,
+    };
+
+    if let Some(path) = export_path {
+        let json = serde_json::to_string_pretty(&freeze)
+            .map_err(|e| format!("Failed to serialize environment: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        freeze.exported_to = Some(path);
+    }
+
+    Ok(freeze)
+}
+
+#[derive(Debug, Deserialize)]
+struct ResolvedInterpreter {
+    executable: String,
+    base_executable: String,
+    prefix: String,
+    version: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InterpreterConsistency {
+    pub consistent: bool,
+    // From `.venv/pyvenv.cfg`: the directory of the interpreter the venv was built with.
+    pub venv_home: Option<String>,
+    pub venv_home_exists: bool,
+    pub venv_version: Option<String>,
+    // What `uv run python` actually resolves to.
+    pub resolved_executable: Option<String>,
+    pub resolved_base_executable: Option<String>,
+    pub resolved_version: Option<String>,
+    pub resolved_in_venv: bool,
+    pub problems: Vec<String>,
+    pub suggestion: Option<String>,
+}
+
+/// Reads `key = value` pairs from a venv's `pyvenv.cfg`.
+fn read_pyvenv_cfg(venv_dir: &Path) -> Result<BTreeMap<String, String>, String> {
+    let path = venv_dir.join("pyvenv.cfg");
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect())
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+async fn resolve_uv_interpreter(target_dir: &str) -> Result<ResolvedInterpreter, String> {
+    let output = new_command("uv")
+        .args(["run", "--no-sync", "python", "-c", INTERPRETER_SCRIPT])
+        .current_dir(target_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute uv run: {}", e))?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line.trim()).ok())
+        .ok_or_else(|| {
+            format!(
+                "uv run python failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        })
+}
+
+/// Compares the interpreter `uv run python` resolves to with the one recorded in the
+/// venv's `pyvenv.cfg`, catching a venv left behind by a system Python upgrade.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_interpreter_consistency(
+    target_dir: String,
+) -> Result<InterpreterConsistency, String> {
+    let venv_dir = Path::new(&target_dir).join(".venv");
+    if !venv_dir.is_dir() {
+        return Err(format!(
+            "No virtual environment found in {}. Please run the environment setup first.",
+            target_dir
+        ));
+    }
+
+    let cfg = read_pyvenv_cfg(&venv_dir)?;
+    let venv_home = cfg.get("home").cloned();
+    // uv writes `version_info`, the stdlib venv module writes `version`.
+    let venv_version = cfg
+        .get("version_info")
+        .or_else(|| cfg.get("version"))
+        .cloned();
+    let venv_home_exists = venv_home
+        .as_deref()
+        .is_some_and(|home| Path::new(home).is_dir());
+
+    let mut problems = Vec::new();
+    if let Some(home) = venv_home.as_deref().filter(|_| !venv_home_exists) {
+        problems.push(format!(
+            "The venv was built with an interpreter in {}, which no longer exists.",
+            home
+        ));
+    }
+
+    let resolved = match resolve_uv_interpreter(&target_dir).await {
+        Ok(resolved) => Some(resolved),
+        Err(e) => {
+            problems.push(e);
+            None
+        }
+    };
+
+    let resolved_in_venv = resolved
+        .as_ref()
+        .is_some_and(|resolved| same_path(Path::new(&resolved.prefix), &venv_dir));
+    if let Some(resolved) = &resolved {
+        if !resolved_in_venv {
+            problems.push(format!(
+                "uv run python uses {} instead of the project venv {}.",
+                resolved.executable,
+                venv_dir.display()
+            ));
+        }
+        if let Some(venv_version) = &venv_version {
+            if venv_version != &resolved.version {
+                problems.push(format!(
+                    "The venv records Python {} but uv run resolves Python {} ({}).",
+                    venv_version, resolved.version, resolved.base_executable
+                ));
+            }
+        }
+        if let Some(home) = venv_home.as_deref().filter(|_| venv_home_exists) {
+            let base_dir = Path::new(&resolved.base_executable).parent();
+            if !base_dir.is_some_and(|dir| same_path(dir, Path::new(home))) {
+                problems.push(format!(
+                    "The venv was built from {} but uv run resolves the base interpreter {}.",
+                    home, resolved.base_executable
+                ));
+            }
+        }
+    }
+
+    let consistent = problems.is_empty();
+    Ok(InterpreterConsistency {
+        consistent,
+        venv_home,
+        venv_home_exists,
+        venv_version,
+        resolved_executable: resolved.as_ref().map(|r| r.executable.clone()),
+        resolved_base_executable: resolved.as_ref().map(|r| r.base_executable.clone()),
+        resolved_version: resolved.map(|r| r.version),
+        resolved_in_venv,
+        problems,
+        suggestion: (!consistent).then(|| {
+            "Delete the .venv folder and run the environment setup again (uv sync) to rebuild it against the current interpreter.".to_string()
+        }),
+    })
+}
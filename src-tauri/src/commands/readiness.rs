@@ -0,0 +1,204 @@
+// src-tauri/src/commands/readiness.rs
+
+use super::engine::{probe_model_dir, EngineManager};
+use super::gpu_usage::{check_device_available, estimate_vram};
+use super::lfs::lfs_status;
+use super::python_env::{check_imports, check_precision_support};
+use super::storage_media::estimate_model_load;
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ReadinessCheck {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl ReadinessCheck {
+    fn new(name: &str, passed: bool, message: impl Into<String>) -> Self {
+        ReadinessCheck {
+            name: name.to_string(),
+            passed,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LaunchReadiness {
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+    // Problems that slow the launch down without blocking it.
+    pub warnings: Vec<String>,
+}
+
+/// Runs the cheap preconditions for `start_index_tts_server` so problems surface
+/// before the multi-minute model load.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn verify_ready_to_launch(
+    target_dir: String,
+    device: Option<String>,
+    precision: Option<String>,
+    gpu_index: Option<u32>,
+    engine: State<'_, EngineManager>,
+) -> Result<LaunchReadiness, String> {
+    let repo_path = Path::new(&target_dir);
+    let mut checks = Vec::new();
+    let mut warnings = Vec::new();
+
+    let has_webui = repo_path.join("webui.py").is_file();
+    checks.push(ReadinessCheck::new(
+        "repository",
+        has_webui,
+        if has_webui {
+            "webui.py found.".to_string()
+        } else {
+            format!("webui.py not found in {}.", target_dir)
+        },
+    ));
+
+    if repo_path.join(".git").is_dir() {
+        // Unavailable git-lfs is reported elsewhere; only a confirmed stub fails the gate.
+        if let Ok(status) = lfs_status(&target_dir).await {
+            let passed = status.resolved || status.smudge_skipped;
+            checks.push(ReadinessCheck::new(
+                "lfs",
+                passed,
+                if status.resolved {
+                    "All Git LFS files are checked out.".to_string()
+                } else if status.smudge_skipped {
+                    "Thin clone: Git LFS files were not pulled; model weights come from the model download.".to_string()
+                } else {
+                    format!(
+                        "{} file(s) are still Git LFS pointers (e.g. {}); run repair_lfs.",
+                        status.pointer_files.len(),
+                        status.pointer_files[0]
+                    )
+                },
+            ));
+        }
+    }
+
+    let has_venv = repo_path.join(".venv").join("pyvenv.cfg").is_file();
+    checks.push(ReadinessCheck::new(
+        "environment",
+        has_venv,
+        if has_venv {
+            "Virtual environment found."
+        } else {
+            "Virtual environment is missing; run the environment setup."
+        },
+    ));
+
+    let configured_model_dir = engine.lock().model_dir.clone();
+    let model_dir = if configured_model_dir.is_empty() {
+        repo_path.join("checkpoints").to_string_lossy().to_string()
+    } else {
+        configured_model_dir
+    };
+    checks.push(match probe_model_dir(&model_dir).await {
+        Ok(probe) if probe.has_core_files => ReadinessCheck::new(
+            "model",
+            true,
+            probe
+                .warning
+                .unwrap_or_else(|| format!("Model files found in {}.", model_dir)),
+        ),
+        Ok(_) => ReadinessCheck::new(
+            "model",
+            false,
+            format!("Model files are missing from {}.", model_dir),
+        ),
+        Err(e) => ReadinessCheck::new("model", false, e),
+    });
+    if let Some(warning) = estimate_model_load(&model_dir, None).warning {
+        warnings.push(warning);
+    }
+
+    if has_venv {
+        checks.push(match check_imports(target_dir.clone()).await {
+            Ok(result) if result.ok() => {
+                ReadinessCheck::new("imports", true, "Core modules import cleanly.")
+            }
+            Ok(result) => ReadinessCheck::new(
+                "imports",
+                false,
+                result
+                    .failed
+                    .iter()
+                    .map(|failure| format!("{}: {}", failure.module, failure.error))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            Err(e) => ReadinessCheck::new("imports", false, e),
+        });
+    }
+
+    let device_name = device.as_deref().unwrap_or("cpu");
+    checks.push(
+        match check_device_available(&target_dir, device_name, gpu_index).await {
+            Ok(()) => ReadinessCheck::new(
+                "device",
+                true,
+                format!("Device '{}' is available.", device_name),
+            ),
+            Err(e) => ReadinessCheck::new("device", false, e),
+        },
+    );
+
+    if has_venv && (precision.is_some() || device_name != "cpu") {
+        let precision_name = precision.as_deref().unwrap_or("fp32");
+        checks.push(
+            match check_precision_support(
+                target_dir.clone(),
+                device_name.to_string(),
+                precision_name.to_string(),
+                gpu_index,
+            )
+            .await
+            {
+                Ok(support) if support.supported => ReadinessCheck::new(
+                    "precision",
+                    true,
+                    format!(
+                        "torch {} runs {} on {}.",
+                        support.torch_version.unwrap_or_default(),
+                        precision_name,
+                        device_name
+                    ),
+                ),
+                Ok(support) => {
+                    ReadinessCheck::new("precision", false, support.error.unwrap_or_default())
+                }
+                Err(e) => ReadinessCheck::new("precision", false, e),
+            },
+        );
+    }
+
+    if device.as_deref() == Some("cuda") {
+        let precision = precision.as_deref().unwrap_or("fp32");
+        if let Ok(estimate) = estimate_vram(precision, gpu_index).await {
+            if let Some(fits) = estimate.fits {
+                checks.push(ReadinessCheck::new(
+                    "vram",
+                    fits,
+                    estimate.recommendation.unwrap_or_else(|| {
+                        format!(
+                            "About {:.1} GB needed for {}; enough VRAM is free.",
+                            estimate.required_mb as f64 / 1024.0,
+                            precision
+                        )
+                    }),
+                ));
+            }
+        }
+    }
+
+    Ok(LaunchReadiness {
+        ready: checks.iter().all(|check| check.passed),
+        checks,
+        warnings,
+    })
+}
@@ -0,0 +1,337 @@
+// src-tauri/src/commands/reference_audio.rs
+
+use super::command_utils::new_command;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+pub const SUPPORTED_REFERENCE_FORMATS: [&str; 5] = ["wav", "mp3", "flac", "ogg", "m4a"];
+
+const MIN_DURATION_SECS: f64 = 1.0;
+const RECOMMENDED_MIN_DURATION_SECS: f64 = 3.0;
+const RECOMMENDED_MAX_DURATION_SECS: f64 = 30.0;
+const MIN_SAMPLE_RATE: u32 = 16_000;
+// Below this the clip is effectively silence.
+const SILENCE_RMS_DBFS: f64 = -50.0;
+// A peak this close to full scale means the recording is probably clipped.
+const CLIPPING_PEAK_DBFS: f64 = -0.1;
+const CLIPPED_SAMPLE_RATIO: f64 = 0.001;
+// Reference clips are short; anything bigger is not read into memory.
+const MAX_WAV_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ReferenceAudioReport {
+    pub path: String,
+    pub format: Option<String>,
+    pub duration_secs: Option<f64>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u16>,
+    pub rms_dbfs: Option<f64>,
+    pub peak_dbfs: Option<f64>,
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+struct AudioStats {
+    duration_secs: f64,
+    sample_rate: u32,
+    channels: u16,
+    rms_dbfs: f64,
+    peak_dbfs: f64,
+    clipped_ratio: Option<f64>,
+}
+
+fn to_dbfs(amplitude: f64) -> f64 {
+    if amplitude <= 0.0 {
+        f64::NEG_INFINITY
+    } else {
+        20.0 * amplitude.log10()
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// Decodes one sample to the -1.0..=1.0 range.
+fn decode_sample(bytes: &[u8], format: u16, bits: u16) -> Option<f64> {
+    match (format, bits) {
+        (1, 8) => Some((bytes[0] as f64 - 128.0) / 128.0),
+        (1, 16) => Some(i16::from_le_bytes([bytes[0], bytes[1]]) as f64 / 32_768.0),
+        (1, 24) => {
+            let value = i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8;
+            Some(value as f64 / 8_388_608.0)
+        }
+        (1, 32) => Some(
+            i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64 / 2_147_483_648.0,
+        ),
+        (3, 32) => Some(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64),
+        _ => None,
+    }
+}
+
+/// Parses a RIFF/WAVE file and measures its level directly from the samples.
+fn analyze_wav(bytes: &[u8]) -> Result<AudioStats, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("The file is not a valid WAV (RIFF/WAVE) file.".to_string());
+    }
+
+    let mut fmt: Option<(u16, u16, u32, u16)> = None;
+    let mut data: Option<&[u8]> = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let size = read_u32(bytes, offset + 4).unwrap_or(0) as usize;
+        let body_start = offset + 8;
+        let body_end = body_start.saturating_add(size).min(bytes.len());
+        let body = &bytes[body_start..body_end];
+        match id {
+            b"fmt " => {
+                let mut format = read_u16(body, 0).ok_or("Truncated WAV format chunk.")?;
+                let channels = read_u16(body, 2).ok_or("Truncated WAV format chunk.")?;
+                let sample_rate = read_u32(body, 4).ok_or("Truncated WAV format chunk.")?;
+                let bits = read_u16(body, 14).ok_or("Truncated WAV format chunk.")?;
+                // WAVE_FORMAT_EXTENSIBLE keeps the real format in the sub-format GUID.
+                if format == 0xFFFE {
+                    format = read_u16(body, 24).unwrap_or(format);
+                }
+                fmt = Some((format, channels, sample_rate, bits));
+            }
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even size.
+        offset = body_start.saturating_add(size + (size & 1));
+    }
+
+    let (format, channels, sample_rate, bits) = fmt.ok_or("The WAV file has no format chunk.")?;
+    let data = data.ok_or("The WAV file has no audio data.")?;
+    if channels == 0 || sample_rate == 0 {
+        return Err("The WAV header reports zero channels or sample rate.".to_string());
+    }
+    let sample_bytes = (bits as usize).div_ceil(8);
+    if sample_bytes == 0 || decode_sample(&[0; 4][..sample_bytes.min(4)], format, bits).is_none() {
+        return Err(format!(
+            "Unsupported WAV encoding (format {}, {} bits). Use 16-bit PCM.",
+            format, bits
+        ));
+    }
+
+    let mut sum_squares = 0.0;
+    let mut peak: f64 = 0.0;
+    let mut clipped = 0usize;
+    let mut count = 0usize;
+    for chunk in data.chunks_exact(sample_bytes) {
+        let Some(sample) = decode_sample(chunk, format, bits) else {
+            continue;
+        };
+        let magnitude = sample.abs();
+        sum_squares += sample * sample;
+        peak = peak.max(magnitude);
+        if magnitude >= 0.999 {
+            clipped += 1;
+        }
+        count += 1;
+    }
+
+    let frames = count / channels as usize;
+    let rms = if count > 0 {
+        (sum_squares / count as f64).sqrt()
+    } else {
+        0.0
+    };
+    Ok(AudioStats {
+        duration_secs: frames as f64 / sample_rate as f64,
+        sample_rate,
+        channels,
+        rms_dbfs: to_dbfs(rms),
+        peak_dbfs: to_dbfs(peak),
+        clipped_ratio: (count > 0).then(|| clipped as f64 / count as f64),
+    })
+}
+
+fn parse_duration(text: &str) -> Option<f64> {
+    let mut parts = text.trim().split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+fn db_value(line: &str, key: &str) -> Option<f64> {
+    let rest = &line[line.find(key)? + key.len()..];
+    rest.trim().trim_end_matches("dB").trim().parse().ok()
+}
+
+/// Compressed formats are decoded by ffmpeg; its `volumedetect` filter gives the levels.
+async fn analyze_with_ffmpeg(path: &Path) -> Result<AudioStats, String> {
+    let output = new_command("ffmpeg")
+        .arg("-hide_banner")
+        .arg("-i")
+        .arg(path)
+        .args(["-af", "volumedetect", "-f", "null", "-"])
+        .output()
+        .await
+        .map_err(|_| {
+            "ffmpeg is required to inspect this format. Install ffmpeg or convert the clip to WAV."
+                .to_string()
+        })?;
+    let log = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        let reason = log.lines().last().unwrap_or("unknown error");
+        return Err(format!("ffmpeg could not decode the file: {}", reason));
+    }
+
+    let mut stats = AudioStats {
+        duration_secs: 0.0,
+        sample_rate: 0,
+        channels: 0,
+        rms_dbfs: f64::NEG_INFINITY,
+        peak_dbfs: f64::NEG_INFINITY,
+        clipped_ratio: None,
+    };
+    for line in log.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("Duration:") {
+            if let Some(duration) = rest.split(',').next().and_then(parse_duration) {
+                stats.duration_secs = duration;
+            }
+        } else if line.contains("Audio:") && stats.sample_rate == 0 {
+            // e.g. "Stream #0:0: Audio: mp3, 44100 Hz, stereo, fltp, 128 kb/s"
+            for field in line.split(',').map(str::trim) {
+                if let Some(rate) = field.strip_suffix(" Hz") {
+                    stats.sample_rate = rate.parse().unwrap_or(0);
+                } else if field == "mono" {
+                    stats.channels = 1;
+                } else if field == "stereo" {
+                    stats.channels = 2;
+                }
+            }
+        } else if let Some(value) = db_value(line, "mean_volume:") {
+            stats.rms_dbfs = value;
+        } else if let Some(value) = db_value(line, "max_volume:") {
+            stats.peak_dbfs = value;
+        }
+    }
+    Ok(stats)
+}
+
+pub async fn check_reference_audio(path: &str) -> ReferenceAudioReport {
+    let file_path = Path::new(path);
+    let mut report = ReferenceAudioReport {
+        path: path.to_string(),
+        ..ReferenceAudioReport::default()
+    };
+
+    let metadata = match fs::metadata(file_path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        Ok(_) => {
+            report.errors.push(format!("{} is not a file.", path));
+            return report;
+        }
+        Err(e) => {
+            report.errors.push(format!("Cannot read {}: {}", path, e));
+            return report;
+        }
+    };
+
+    let extension = file_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !SUPPORTED_REFERENCE_FORMATS.contains(&extension.as_str()) {
+        report.errors.push(format!(
+            "Unsupported audio format '{}'. Expected one of: {}.",
+            extension,
+            SUPPORTED_REFERENCE_FORMATS.join(", ")
+        ));
+        return report;
+    }
+    report.format = Some(extension.clone());
+
+    let stats = if extension == "wav" {
+        if metadata.len() > MAX_WAV_BYTES {
+            report.errors.push(format!(
+                "The file is {} MB; a reference clip should be a few seconds long.",
+                metadata.len() / 1024 / 1024
+            ));
+            return report;
+        }
+        fs::read(file_path)
+            .map_err(|e| format!("Cannot read {}: {}", path, e))
+            .and_then(|bytes| analyze_wav(&bytes))
+    } else {
+        analyze_with_ffmpeg(file_path).await
+    };
+    let stats = match stats {
+        Ok(stats) => stats,
+        Err(e) => {
+            report.errors.push(e);
+            return report;
+        }
+    };
+
+    report.duration_secs = Some(stats.duration_secs);
+    report.sample_rate = (stats.sample_rate > 0).then_some(stats.sample_rate);
+    report.channels = (stats.channels > 0).then_some(stats.channels);
+    report.rms_dbfs = stats.rms_dbfs.is_finite().then_some(stats.rms_dbfs);
+    report.peak_dbfs = stats.peak_dbfs.is_finite().then_some(stats.peak_dbfs);
+
+    if stats.duration_secs < MIN_DURATION_SECS {
+        report.errors.push(format!(
+            "The clip is only {:.1}s long; use 3–30 seconds of clear speech.",
+            stats.duration_secs
+        ));
+    } else if stats.duration_secs < RECOMMENDED_MIN_DURATION_SECS {
+        report.warnings.push(format!(
+            "The clip is {:.1}s long; at least 3 seconds gives a more faithful voice.",
+            stats.duration_secs
+        ));
+    } else if stats.duration_secs > RECOMMENDED_MAX_DURATION_SECS {
+        report.warnings.push(format!(
+            "The clip is {:.0}s long; clips over 30 seconds slow down synthesis without improving the voice.",
+            stats.duration_secs
+        ));
+    }
+
+    if stats.sample_rate > 0 && stats.sample_rate < MIN_SAMPLE_RATE {
+        report.warnings.push(format!(
+            "The sample rate is {} Hz; 16 kHz or higher is recommended.",
+            stats.sample_rate
+        ));
+    }
+
+    if stats.rms_dbfs < SILENCE_RMS_DBFS {
+        report
+            .errors
+            .push("The clip is silent or nearly silent.".to_string());
+    } else if stats
+        .clipped_ratio
+        .map_or(stats.peak_dbfs >= CLIPPING_PEAK_DBFS, |ratio| {
+            ratio > CLIPPED_SAMPLE_RATIO
+        })
+    {
+        report.warnings.push(
+            "The clip appears to be clipped (distorted); record at a lower volume.".to_string(),
+        );
+    }
+
+    report.valid = report.errors.is_empty();
+    report
+}
+
+/// Checks a voice reference clip before it is sent to the server: format, duration,
+/// sample rate and level (silence / clipping).
+#[tauri::command]
+pub async fn validate_reference_audio(path: String) -> Result<ReferenceAudioReport, String> {
+    Ok(check_reference_audio(&path).await)
+}
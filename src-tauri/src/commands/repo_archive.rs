@@ -0,0 +1,157 @@
+// src-tauri/src/commands/repo_archive.rs
+
+use super::command_utils::new_command;
+use super::index_tts::{emit_core_deploy_log, run_command_with_streaming};
+use super::lfs::set_lfs_smudge_skipped;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+// Written into the checkout by an archive download; holds the URL it came from.
+const ARCHIVE_MARKER: &str = ".indextts-hub-archive";
+const ARCHIVE_FILE: &str = ".indextts-hub-archive.tar.gz";
+const SNAPSHOT_AUTHOR: [(&str, &str); 2] = [
+    ("user.name", "IndexTTS Hub"),
+    ("user.email", "indextts-hub@localhost"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneMethod {
+    Git,
+    Archive,
+}
+
+impl CloneMethod {
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.map(|value| value.trim().to_lowercase()).as_deref() {
+            None | Some("") | Some("git") => Ok(CloneMethod::Git),
+            Some("archive") => Ok(CloneMethod::Archive),
+            Some(other) => Err(format!(
+                "Unsupported clone method '{}'. Use \"git\" or \"archive\".",
+                other
+            )),
+        }
+    }
+}
+
+/// True when the checkout came from a source archive rather than `git clone`.
+pub fn is_archive_checkout(target_dir: &str) -> bool {
+    Path::new(target_dir).join(ARCHIVE_MARKER).is_file()
+}
+
+/// Error for git update operations, which need history an archive checkout doesn't have.
+pub fn archive_update_unsupported(target_dir: &str) -> String {
+    format!(
+        "{} was installed from a source archive, so it has no upstream history to update from. Redeploy with clone_method \"git\" to get updates.",
+        target_dir
+    )
+}
+
+/// Tarball of the default branch, e.g. `https://github.com/owner/repo/archive/HEAD.tar.gz`.
+/// Mirrors are assumed to use GitHub's archive layout.
+pub fn archive_url(repo_url: &str) -> Result<String, String> {
+    let base = repo_url
+        .trim()
+        .trim_end_matches('/')
+        .trim_end_matches(".git");
+    if !base.starts_with("https://") && !base.starts_with("http://") {
+        return Err(format!(
+            "Archive download needs an http(s) repository URL, got {}.",
+            repo_url
+        ));
+    }
+    Ok(format!("{}/archive/HEAD.tar.gz", base))
+}
+
+async fn run_git(app_handle: &AppHandle, target_dir: &str, args: &[&str]) -> Result<(), String> {
+    let mut command = new_command("git");
+    command.arg("-C").arg(target_dir);
+    for (key, value) in SNAPSHOT_AUTHOR {
+        command.arg("-c").arg(format!("{}={}", key, value));
+    }
+    command.args(args);
+    run_command_with_streaming(app_handle, "clone_repo", command).await
+}
+
+/// Turns the extracted archive into a single-commit repository pointing at `repo_url`.
+async fn init_snapshot_repo(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    repo_url: &str,
+) -> Result<(), String> {
+    run_git(app_handle, target_dir, &["init", "--quiet"]).await?;
+    run_git(
+        app_handle,
+        target_dir,
+        &["remote", "add", "origin", repo_url],
+    )
+    .await?;
+    let exclude = Path::new(target_dir)
+        .join(".git")
+        .join("info")
+        .join("exclude");
+    let mut excluded = fs::read_to_string(&exclude).unwrap_or_default();
+    excluded.push_str(&format!("\n{}\n", ARCHIVE_MARKER));
+    fs::write(&exclude, excluded)
+        .map_err(|e| format!("Failed to write {}: {}", exclude.display(), e))?;
+    run_git(app_handle, target_dir, &["add", "-A"]).await?;
+    run_git(
+        app_handle,
+        target_dir,
+        &["commit", "--quiet", "-m", "Snapshot from source archive"],
+    )
+    .await?;
+    // Archives carry LFS pointers only; the model download provides the weights.
+    set_lfs_smudge_skipped(target_dir, true)
+}
+
+/// Downloads and extracts the repository tarball into `target_dir` instead of cloning.
+pub async fn download_repo_archive(
+    app_handle: &AppHandle,
+    target_dir: &str,
+    repo_url: &str,
+    init_git: bool,
+) -> Result<(), String> {
+    let url = archive_url(repo_url)?;
+    fs::create_dir_all(target_dir)
+        .map_err(|e| format!("Failed to create {}: {}", target_dir, e))?;
+    let archive = Path::new(target_dir).join(ARCHIVE_FILE);
+
+    emit_core_deploy_log(
+        app_handle,
+        "clone_repo",
+        "stdout",
+        &format!("以源码压缩包方式下载仓库: {}", url),
+    );
+    let mut download = new_command("curl");
+    download
+        .args(["-L", "--fail", "-sS", "--retry", "3", "-o"])
+        .arg(&archive)
+        .arg(&url);
+    run_command_with_streaming(app_handle, "clone_repo", download).await?;
+
+    let mut extract = new_command("tar");
+    extract
+        .arg("-xzf")
+        .arg(&archive)
+        .args(["--strip-components=1", "-C"])
+        .arg(target_dir);
+    let extracted = run_command_with_streaming(app_handle, "clone_repo", extract).await;
+    let _ = fs::remove_file(&archive);
+    extracted?;
+
+    let marker = Path::new(target_dir).join(ARCHIVE_MARKER);
+    fs::write(&marker, format!("{}\n", url))
+        .map_err(|e| format!("Failed to write {}: {}", marker.display(), e))?;
+
+    if init_git {
+        init_snapshot_repo(app_handle, target_dir, repo_url).await?;
+    }
+    emit_core_deploy_log(
+        app_handle,
+        "clone_repo",
+        "stdout",
+        "压缩包安装不包含 Git 历史，无法使用检查更新和拉取更新。",
+    );
+    Ok(())
+}
@@ -0,0 +1,123 @@
+// src-tauri/src/commands/repo_remote.rs
+
+use super::command_utils::new_command;
+use super::settings::load_settings;
+use serde::Serialize;
+use tauri::AppHandle;
+
+pub const INDEX_TTS_REPO_URL: &str = "https://github.com/index-tts/index-tts.git";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RemoteCheck {
+    pub expected: String,
+    pub actual: Option<String>,
+    pub matches: bool,
+}
+
+/// The upstream repository deployments are cloned from: the `repo_url` setting when the
+/// user pointed the app at a fork or mirror, otherwise the official index-tts repo.
+pub fn expected_repo_url(app_handle: &AppHandle) -> String {
+    load_settings(app_handle)
+        .repo_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| INDEX_TTS_REPO_URL.to_string())
+}
+
+/// Reduces a remote URL to `host/owner/repo` so https, ssh and `.git`-suffixed forms of
+/// the same repository compare equal.
+pub fn normalize_remote_url(url: &str) -> String {
+    let mut url = url.trim().to_lowercase();
+    if let Some(pos) = url.find("://") {
+        url = url[pos + 3..].to_string();
+    } else if let Some((user_host, path)) = url.split_once(':') {
+        // scp-like syntax: git@github.com:owner/repo.git
+        url = format!("{}/{}", user_host, path);
+    }
+    if let Some(pos) = url.find('@') {
+        if url[..pos].find('/').is_none() {
+            url = url[pos + 1..].to_string();
+        }
+    }
+    url.trim_end_matches('/')
+        .trim_end_matches(".git")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub async fn origin_url(target_dir: &str) -> Option<String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+pub async fn check_repo_remote(app_handle: &AppHandle, target_dir: &str) -> RemoteCheck {
+    let expected = expected_repo_url(app_handle);
+    let actual = origin_url(target_dir).await;
+    let matches = actual
+        .as_deref()
+        .is_some_and(|url| normalize_remote_url(url) == normalize_remote_url(&expected));
+    RemoteCheck {
+        expected,
+        actual,
+        matches,
+    }
+}
+
+pub async fn set_origin_url(target_dir: &str, url: &str) -> Result<(), String> {
+    // `set-url` fails when there is no origin yet, so add it in that case.
+    let action = if origin_url(target_dir).await.is_some() {
+        "set-url"
+    } else {
+        "add"
+    };
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(["remote", action, "origin", url])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute git remote: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to set origin to {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_repo_remote(
+    app_handle: AppHandle,
+    target_dir: String,
+) -> Result<RemoteCheck, String> {
+    Ok(check_repo_remote(&app_handle, &target_dir).await)
+}
+
+/// Points `origin` of an existing deployment at `url`, or at the expected index-tts
+/// remote when no URL is given. Only the remote is changed; run the clone step with
+/// `force` to also check out its code.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_repo_remote(
+    app_handle: AppHandle,
+    target_dir: String,
+    url: Option<String>,
+) -> Result<RemoteCheck, String> {
+    let url = url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| expected_repo_url(&app_handle));
+    set_origin_url(&target_dir, &url).await?;
+    Ok(check_repo_remote(&app_handle, &target_dir).await)
+}
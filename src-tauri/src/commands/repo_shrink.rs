@@ -0,0 +1,166 @@
+// src-tauri/src/commands/repo_shrink.rs
+
+use super::command_utils::{clean_path_arg, new_command};
+use super::dir_lock::acquire_dir_lock;
+use super::index_tts::{emit_core_deploy_log, run_command_with_streaming};
+use super::repo_archive::{archive_update_unsupported, is_archive_checkout};
+use super::storage_media::dir_size_bytes;
+use serde::Serialize;
+use std::path::Path;
+use tauri::AppHandle;
+
+const SHRINK_STEP: &str = "shrink_repo";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ShrinkReport {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_reclaimed: u64,
+    pub shallow: bool,
+    pub lfs_pruned: bool,
+    pub warnings: Vec<String>,
+}
+
+async fn git(app_handle: &AppHandle, target_dir: &str, args: &[&str]) -> Result<(), String> {
+    let mut command = new_command("git");
+    command.arg("-C").arg(target_dir).args(args);
+    run_command_with_streaming(app_handle, SHRINK_STEP, command).await
+}
+
+async fn git_stdout(target_dir: &str, args: &[&str]) -> Option<String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Slims a full clone down: with `shallow` (the default) history is cut to the checked
+/// out commit, then unreachable objects are collected and LFS objects that are neither
+/// checked out nor missing from the remote are pruned. The working tree is not touched.
+/// Tags and other local branches keep their history reachable.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn shrink_repo(
+    app_handle: AppHandle,
+    target_dir: String,
+    shallow: Option<bool>,
+) -> Result<ShrinkReport, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    let git_dir = Path::new(&target_dir).join(".git");
+    if !git_dir.is_dir() {
+        if is_archive_checkout(&target_dir) {
+            return Err(archive_update_unsupported(&target_dir));
+        }
+        return Err(format!("{} is not a git repository.", target_dir));
+    }
+    let _dir_lock = acquire_dir_lock(&target_dir, "shrink_repo")?;
+
+    let bytes_before = dir_size_bytes(&git_dir);
+    let mut warnings = Vec::new();
+    emit_core_deploy_log(
+        &app_handle,
+        SHRINK_STEP,
+        "stdout",
+        &format!(
+            "开始精简仓库，当前 .git 占用 {:.1} MB。",
+            bytes_before as f64 / 1024.0 / 1024.0
+        ),
+    );
+
+    let mut is_shallow = false;
+    if shallow.unwrap_or(true) {
+        let head = git_stdout(&target_dir, &["rev-parse", "HEAD"])
+            .await
+            .ok_or_else(|| format!("Failed to resolve HEAD in {}.", target_dir))?;
+        // Fetching the checked-out commit at depth 1 moves the shallow boundary to it;
+        // the second fetch does the same for the remote-tracking branches.
+        let fetched = match git(
+            &app_handle,
+            &target_dir,
+            &["fetch", "--depth", "1", "origin", &head],
+        )
+        .await
+        {
+            Ok(()) => {
+                git(
+                    &app_handle,
+                    &target_dir,
+                    &["fetch", "--depth", "1", "origin"],
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        match fetched {
+            Ok(()) => is_shallow = true,
+            Err(e) => warnings.push(format!(
+                "History could not be truncated ({}); only garbage collection was run.",
+                e
+            )),
+        }
+    }
+
+    // Expiring every reflog would also drop stash entries, so keep them when present.
+    if git_stdout(
+        &target_dir,
+        &["rev-parse", "--verify", "--quiet", "refs/stash"],
+    )
+    .await
+    .is_some()
+    {
+        warnings.push(
+            "Stash entries were kept, so some old objects could not be reclaimed.".to_string(),
+        );
+    } else {
+        git(
+            &app_handle,
+            &target_dir,
+            &["reflog", "expire", "--expire=now", "--all"],
+        )
+        .await?;
+    }
+    git(&app_handle, &target_dir, &["gc", "--prune=now"]).await?;
+
+    // `git lfs prune` always keeps the objects of the current checkout; --verify-remote
+    // additionally keeps anything the remote doesn't have.
+    let mut lfs_pruned = false;
+    if git_dir.join("lfs").is_dir() {
+        match git(
+            &app_handle,
+            &target_dir,
+            &["lfs", "prune", "--verify-remote"],
+        )
+        .await
+        {
+            Ok(()) => lfs_pruned = true,
+            Err(e) => warnings.push(format!("Git LFS objects were not pruned: {}", e)),
+        }
+    }
+
+    let bytes_after = dir_size_bytes(&git_dir);
+    let bytes_reclaimed = bytes_before.saturating_sub(bytes_after);
+    emit_core_deploy_log(
+        &app_handle,
+        SHRINK_STEP,
+        "stdout",
+        &format!(
+            "仓库精简完成，释放 {:.1} MB。",
+            bytes_reclaimed as f64 / 1024.0 / 1024.0
+        ),
+    );
+
+    Ok(ShrinkReport {
+        bytes_before,
+        bytes_after,
+        bytes_reclaimed,
+        shallow: is_shallow,
+        lfs_pruned,
+        warnings,
+    })
+}
@@ -0,0 +1,122 @@
+// src-tauri/src/commands/resource_monitor.rs
+
+use super::system_info::volume_for_path;
+use super::tasks::spawn_tracked;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::System;
+use tauri::{AppHandle, Emitter};
+
+const RESOURCE_WARNING_EVENT: &str = "resource-warning";
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+// A model download alone is several GB; below this the deploy is likely to fail.
+const LOW_DISK_GB: f64 = 5.0;
+const LOW_MEMORY_GB: f64 = 1.0;
+// Swap growth since the deploy started that indicates the machine is thrashing.
+const SWAP_GROWTH_GB: f64 = 1.0;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResourceSample {
+    pub disk_available_gb: Option<f64>,
+    pub memory_available_gb: f64,
+    pub swap_used_gb: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResourceWarning {
+    pub kind: String,
+    pub target_dir: String,
+    pub message: String,
+    pub sample: ResourceSample,
+}
+
+fn sample(sys: &mut System, target_dir: &str) -> ResourceSample {
+    sys.refresh_memory();
+    ResourceSample {
+        disk_available_gb: volume_for_path(Path::new(target_dir)).map(|volume| volume.available_gb),
+        memory_available_gb: sys.available_memory() as f64 / BYTES_PER_GB,
+        swap_used_gb: sys.used_swap() as f64 / BYTES_PER_GB,
+    }
+}
+
+/// Stops the monitor when dropped.
+pub struct ResourceMonitorGuard(Arc<AtomicBool>);
+
+impl Drop for ResourceMonitorGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Samples free disk on the deployment's volume and memory pressure while the returned
+/// guard is alive, emitting `resource-warning` when either crosses its threshold. Each
+/// kind is reported once until it recovers.
+pub fn start_resource_monitor(app_handle: &AppHandle, target_dir: &str) -> ResourceMonitorGuard {
+    let stop = Arc::new(AtomicBool::new(false));
+    let task_stop = stop.clone();
+    let task_app = app_handle.clone();
+    let target_dir = target_dir.to_string();
+    spawn_tracked(app_handle, "resource_monitor", async move {
+        let mut sys = System::new();
+        let baseline_swap = sample(&mut sys, &target_dir).swap_used_gb;
+        let mut disk_warned = false;
+        let mut memory_warned = false;
+        while !task_stop.load(Ordering::SeqCst) {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+            if task_stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let current = sample(&mut sys, &target_dir);
+
+            let low_disk = current
+                .disk_available_gb
+                .filter(|available| *available < LOW_DISK_GB);
+            if let Some(available) = low_disk.filter(|_| !disk_warned) {
+                let _ = task_app.emit(
+                    RESOURCE_WARNING_EVENT,
+                    ResourceWarning {
+                        kind: "disk".to_string(),
+                        target_dir: target_dir.clone(),
+                        message: format!(
+                            "Only {:.1} GB free on the deployment drive; the deploy may fail when it fills up.",
+                            available
+                        ),
+                        sample: current.clone(),
+                    },
+                );
+            }
+            disk_warned = low_disk.is_some();
+
+            let swap_growth = current.swap_used_gb - baseline_swap;
+            let memory_pressure =
+                current.memory_available_gb < LOW_MEMORY_GB || swap_growth > SWAP_GROWTH_GB;
+            if memory_pressure && !memory_warned {
+                let _ = task_app.emit(
+                    RESOURCE_WARNING_EVENT,
+                    ResourceWarning {
+                        kind: "memory".to_string(),
+                        target_dir: target_dir.clone(),
+                        message: format!(
+                            "Memory is running low ({:.1} GB available, swap grew by {:.1} GB); close other applications.",
+                            current.memory_available_gb,
+                            swap_growth.max(0.0)
+                        ),
+                        sample: current,
+                    },
+                );
+            }
+            memory_warned = memory_pressure;
+        }
+    });
+    ResourceMonitorGuard(stop)
+}
+
+/// Current free disk on the volume holding `target_dir`, available memory and swap use.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_resource_status(target_dir: String) -> Result<ResourceSample, String> {
+    Ok(sample(&mut System::new(), &target_dir))
+}
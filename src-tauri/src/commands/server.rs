@@ -1,16 +1,88 @@
 // src-tauri/src/commands/server.rs
 
-use super::command_utils::new_command;
+use super::command_history::record_command;
+use super::command_utils::{clean_path_arg, new_command};
+use super::config_store::{load_json, save_json};
+use super::deploy_error::DeployError;
+use super::deployments::unix_timestamp;
+use super::dir_lock::{acquire_dir_lock, DirLock};
+use super::driver_check::record_known_good_driver;
+use super::engine::{probe_model_dir, EngineManager};
+use super::git_locks::{clear_stale_git_locks, is_git_lock_error};
+use super::git_settings::ensure_repo_identity;
+use super::gpu_usage::{capture_gpu_snapshot, check_device_available, GpuSnapshot};
+use super::launch_args::notify_if_launch_args_invalid;
+use super::log_files::{append_log_line, APP_LOG, SERVER_LOG};
+use super::memory_limit::{
+    apply_memory_limit, memory_limit_hit, memory_limited_command, MemoryLimitGuard,
+};
+use super::network::{advertised_url, validate_bind_host};
+use super::notifications::{notify_server_event, ServerEvent};
+use super::processes::is_descendant_of;
+use super::repo_archive::{archive_update_unsupported, is_archive_checkout};
+use super::server_config::{
+    load_server_config, logical_core_count, resolve_launch_settings, stop_scan_ports,
+    validate_launch_settings, LaunchSettings, DEFAULT_SERVER_PORT,
+};
+use super::settings::load_settings;
+use super::tasks::spawn_tracked;
 use serde::{Deserialize, Serialize};
-use std::net::{Ipv4Addr, SocketAddrV4, TcpStream};
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
 use std::path::Path;
+use std::process::ExitStatus;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, MutexGuard};
-use std::time::Duration;
-use tauri::{AppHandle, Emitter, State};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, System};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Child;
 use tokio::time::sleep;
 
+// Gradio prints this once the web UI is listening.
+const GRADIO_READY_MARKER: &str = "Running on local URL";
+// Printed once the share tunnel is up, or when it could not be created.
+const GRADIO_SHARE_MARKER: &str = "Running on public URL:";
+const GRADIO_SHARE_FAILED_MARKER: &str = "Could not create share link";
+const SHARE_URL_EVENT: &str = "server-share-url";
+const BIND_MISMATCH_EVENT: &str = "server-bind-mismatch";
+const SERVER_STATUS_EVENT: &str = "server-status-changed";
+// How long a start may take before the server is reported as failed to bind.
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(120);
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const BIND_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+// How long a stopping server gets to exit on its own before its tree is killed.
+const GRACEFUL_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+// How long a server whose output closed is given to be reaped, so its exit status
+// (code or signal) is known when the crash is classified.
+const EXIT_STATUS_WAIT: Duration = Duration::from_secs(3);
+const EXIT_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const SHARE_WARNING: &str = "Share mode publishes this server on a public gradio.live URL: anyone with the link can use it and your GPU. Share it only with people you trust, prefer Gradio auth, and stop the server when the demo is over.";
+pub const STARTUP_HISTORY_FILE: &str = "startup_history.json";
+const STARTUP_HISTORY_LIMIT: usize = 20;
+// Structured copy of the stdout/stderr lines, tagged with a log level for filtering.
+const SERVER_LOG_EVENT: &str = "server-log";
+const LOG_TAIL_LIMIT: usize = 50;
+// Sequenced lines kept for `get_server_logs_since`, so a returning UI can fill its gap.
+const SERVER_LOG_BUFFER_LIMIT: usize = 2000;
+// After Gradio reports its URL, requests are retried this long before the server is
+// declared unhealthy.
+const READINESS_WINDOW: Duration = Duration::from_secs(20);
+const READINESS_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+const HTTP_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+// How long a port with no owning process is given to leave TIME_WAIT after a stop.
+#[cfg(windows)]
+const LINGERING_SOCKET_GRACE: Duration = Duration::from_secs(1);
+#[cfg(windows)]
+const TASKKILL_NOT_FOUND: i32 = 128;
+const DEFAULT_SERVER_HOST: &str = "127.0.0.1";
+// Programs the server runs as (`python3.11`, `pythonw`, uv but not uvicorn); a listener
+// started by anything else is never killed.
+const PYTHON_PROGRAM_PREFIX: &str = "python";
+const UV_PROGRAM: &str = "uv";
+
 #[cfg(target_os = "windows")]
 const WINDOWS_WEBUI_WRAPPER: &str = r#"
 import asyncio
@@ -38,39 +110,1001 @@ runpy.run_path("webui.py", run_name="__main__")
 "#;
 
 // Define a struct to hold the child process, to be managed by Tauri State
-pub struct ServerChildProcess(Mutex<Option<Child>>);
+pub struct ServerChildProcess {
+    child: Mutex<Option<Child>>,
+    dir_lock: Mutex<Option<DirLock>>,
+    ready: AtomicBool,
+    started_at: Mutex<Option<Instant>>,
+    unhealthy: Mutex<Option<String>>,
+    // Set when the server did not come up within its startup timeout.
+    startup_error: Mutex<Option<String>>,
+    log_buffer: Mutex<ServerLogBuffer>,
+    url: Mutex<Option<String>>,
+    share_url: Mutex<Option<String>>,
+    memory_limit: Mutex<Option<MemoryLimitGuard>>,
+    launch: Mutex<Option<ServerLaunchRecord>>,
+    crash: Mutex<Option<ServerCrash>>,
+}
+
+/// Parameters of the most recent server start, kept for support snapshots.
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerLaunchRecord {
+    pub target_dir: String,
+    pub host: String,
+    pub port: u16,
+    pub device: String,
+    pub precision: Option<String>,
+    pub log_level: String,
+    pub cpu_threads: Option<usize>,
+    pub gpu_index: Option<u32>,
+    pub share: bool,
+    pub memory_limit_mb: Option<u64>,
+    pub webui_args: Vec<String>,
+    pub started_at: u64,
+    // Result of checking where the server really listens; `host`/`port` above are
+    // corrected when it bound somewhere other than requested.
+    pub bind: Option<BindVerification>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BindVerification {
+    pub requested_host: String,
+    pub requested_port: u16,
+    // The address from Gradio's "Running on local URL" line.
+    pub reported_url: Option<String>,
+    // Where a connection actually succeeded; `None` when nothing answered.
+    pub actual_host: Option<String>,
+    pub actual_port: Option<u16>,
+    pub mismatch: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerCrash {
+    pub exit_code: Option<i32>,
+    pub crashed_at: u64,
+    // The server ran under `memory_limit_mb` and most likely died by exceeding it.
+    pub memory_limit_hit: bool,
+    // Driver/VRAM state right after a CUDA server crashed; filled in asynchronously.
+    pub gpu: Option<GpuSnapshot>,
+}
 
 impl ServerChildProcess {
     pub fn new() -> Self {
-        ServerChildProcess(Mutex::new(None))
+        ServerChildProcess {
+            child: Mutex::new(None),
+            dir_lock: Mutex::new(None),
+            ready: AtomicBool::new(false),
+            started_at: Mutex::new(None),
+            unhealthy: Mutex::new(None),
+            startup_error: Mutex::new(None),
+            log_buffer: Mutex::new(ServerLogBuffer::default()),
+            url: Mutex::new(None),
+            share_url: Mutex::new(None),
+            memory_limit: Mutex::new(None),
+            launch: Mutex::new(None),
+            crash: Mutex::new(None),
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::SeqCst);
     }
 
     pub fn lock(&self) -> MutexGuard<'_, Option<Child>> {
-        self.0.lock().expect("Server child process mutex poisoned")
+        self.child
+            .lock()
+            .expect("Server child process mutex poisoned")
+    }
+
+    fn set_started_at(&self, started_at: Option<Instant>) {
+        *self
+            .started_at
+            .lock()
+            .expect("Server start time mutex poisoned") = started_at;
+    }
+
+    fn take_started_at(&self) -> Option<Instant> {
+        self.started_at
+            .lock()
+            .expect("Server start time mutex poisoned")
+            .take()
+    }
+
+    fn set_dir_lock(&self, lock: Option<DirLock>) {
+        *self
+            .dir_lock
+            .lock()
+            .expect("Server dir lock mutex poisoned") = lock;
+    }
+
+    fn set_url(&self, url: Option<String>) {
+        *self.url.lock().expect("Server url mutex poisoned") = url;
+    }
+
+    fn url(&self) -> Option<String> {
+        self.url.lock().expect("Server url mutex poisoned").clone()
+    }
+
+    fn set_share_url(&self, url: Option<String>) {
+        *self
+            .share_url
+            .lock()
+            .expect("Server share url mutex poisoned") = url;
+    }
+
+    fn share_url(&self) -> Option<String> {
+        self.share_url
+            .lock()
+            .expect("Server share url mutex poisoned")
+            .clone()
+    }
+
+    fn set_memory_limit(&self, guard: Option<MemoryLimitGuard>) {
+        *self
+            .memory_limit
+            .lock()
+            .expect("Server memory limit mutex poisoned") = guard;
+    }
+
+    fn scope_oom_killed(&self) -> bool {
+        self.memory_limit
+            .lock()
+            .expect("Server memory limit mutex poisoned")
+            .as_ref()
+            .is_some_and(MemoryLimitGuard::oom_killed)
+    }
+
+    fn unhealthy_reason(&self) -> Option<String> {
+        self.unhealthy
+            .lock()
+            .expect("Server health mutex poisoned")
+            .clone()
+    }
+
+    fn set_unhealthy(&self, reason: Option<String>) {
+        *self.unhealthy.lock().expect("Server health mutex poisoned") = reason;
+    }
+
+    fn startup_error(&self) -> Option<String> {
+        self.startup_error
+            .lock()
+            .expect("Server startup error mutex poisoned")
+            .clone()
+    }
+
+    fn set_startup_error(&self, reason: Option<String>) {
+        *self
+            .startup_error
+            .lock()
+            .expect("Server startup error mutex poisoned") = reason;
+    }
+
+    fn child_pid(&self) -> Option<u32> {
+        self.lock().as_ref().and_then(Child::id)
+    }
+
+    /// Stores a line and returns it tagged with its sequence number.
+    fn push_log_line(&self, mut event: ServerLogEvent) -> ServerLogEvent {
+        let mut buffer = self
+            .log_buffer
+            .lock()
+            .expect("Server log buffer mutex poisoned");
+        buffer.next_seq += 1;
+        event.seq = buffer.next_seq;
+        if buffer.lines.len() >= SERVER_LOG_BUFFER_LIMIT {
+            buffer.lines.pop_front();
+        }
+        buffer.lines.push_back(event.clone());
+        event
+    }
+
+    /// Lines with a sequence number above `seq`, oldest first.
+    pub fn log_lines_since(&self, seq: u64) -> ServerLogsSince {
+        let buffer = self
+            .log_buffer
+            .lock()
+            .expect("Server log buffer mutex poisoned");
+        let oldest = buffer.lines.front().map_or(buffer.next_seq + 1, |e| e.seq);
+        ServerLogsSince {
+            lines: buffer
+                .lines
+                .iter()
+                .filter(|event| event.seq > seq)
+                .cloned()
+                .collect(),
+            latest_seq: buffer.next_seq,
+            // Lines between `seq` and the oldest kept one were dropped or cleared.
+            truncated: seq + 1 < oldest && seq < buffer.next_seq,
+        }
+    }
+
+    pub fn launch_record(&self) -> Option<ServerLaunchRecord> {
+        self.launch
+            .lock()
+            .expect("Server launch mutex poisoned")
+            .clone()
+    }
+
+    fn set_launch_record(&self, record: Option<ServerLaunchRecord>) {
+        *self.launch.lock().expect("Server launch mutex poisoned") = record;
+    }
+
+    /// Stores the bind check and moves the recorded address to where the server listens.
+    fn record_bind(&self, bind: BindVerification) {
+        let mut launch = self.launch.lock().expect("Server launch mutex poisoned");
+        let Some(launch) = launch.as_mut() else {
+            return;
+        };
+        if let (true, Some(host), Some(port)) =
+            (bind.mismatch, bind.actual_host.clone(), bind.actual_port)
+        {
+            launch.host = host;
+            launch.port = port;
+            self.set_url(Some(advertised_url(&launch.host, launch.port)));
+        }
+        launch.bind = Some(bind);
+    }
+
+    /// Set when the server exited without being asked to; cleared by the next start.
+    pub fn last_crash(&self) -> Option<ServerCrash> {
+        self.crash
+            .lock()
+            .expect("Server crash mutex poisoned")
+            .clone()
+    }
+
+    fn set_crash(&self, crash: Option<ServerCrash>) {
+        *self.crash.lock().expect("Server crash mutex poisoned") = crash;
+    }
+
+    fn attach_crash_gpu_snapshot(&self, crashed_at: u64, snapshot: GpuSnapshot) {
+        if let Some(crash) = self
+            .crash
+            .lock()
+            .expect("Server crash mutex poisoned")
+            .as_mut()
+            .filter(|crash| crash.crashed_at == crashed_at)
+        {
+            crash.gpu = Some(snapshot);
+        }
+    }
+
+    /// The last lines of output, for crash reports and snapshots.
+    pub fn log_tail(&self) -> Vec<String> {
+        let buffer = self
+            .log_buffer
+            .lock()
+            .expect("Server log buffer mutex poisoned");
+        buffer
+            .lines
+            .iter()
+            .skip(buffer.lines.len().saturating_sub(LOG_TAIL_LIMIT))
+            .map(|event| event.line.clone())
+            .collect()
+    }
+
+    /// Drops the previous run's lines; sequence numbers keep counting so old cursors
+    /// stay valid.
+    fn clear_log_tail(&self) {
+        self.log_buffer
+            .lock()
+            .expect("Server log buffer mutex poisoned")
+            .lines
+            .clear();
     }
 }
 
-#[derive(Debug, Serialize, Clone, Copy)] // Add Clone, Copy for convenience
+#[derive(Debug, Serialize, Clone)] // Add Clone for convenience
 pub enum ServerStatus {
     Running,
     Stopped,
     Starting,
+    // The port is open but every request during the readiness window failed.
+    Unhealthy { reason: String },
+    // The last run exited on its own; cleared by the next start.
+    Crashed(ServerCrash),
+    // Still running, but not listening within the startup timeout.
+    Error { reason: String },
+}
+
+/// Sends `server-status-changed` so the UI doesn't have to poll `get_server_status`.
+fn emit_server_status(app_handle: &AppHandle, status: ServerStatus) {
+    let _ = app_handle.emit(SERVER_STATUS_EVENT, status);
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StartupSample {
+    pub duration_secs: f64,
+    pub recorded_at: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StartupTiming {
+    pub last_secs: Option<f64>,
+    pub typical_secs: Option<f64>,
+    pub abnormally_slow: bool,
+    pub history: Vec<StartupSample>,
+}
+
+fn median_secs(samples: &[StartupSample]) -> Option<f64> {
+    let mut values: Vec<f64> = samples.iter().map(|s| s.duration_secs).collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    Some(values[values.len() / 2])
+}
+
+/// Plain HTTP/1.0 GET returning the status code and the start of the body.
+fn http_get(host: &str, port: u16) -> Result<(u16, String), String> {
+    let address = format!("{}:{}", host, port);
+    let socket = address
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", address, e))?
+        .next()
+        .ok_or_else(|| format!("Failed to resolve {}", address))?;
+    let mut stream = TcpStream::connect_timeout(&socket, HTTP_PROBE_TIMEOUT)
+        .map_err(|e| format!("Failed to connect to {}: {}", address, e))?;
+    let _ = stream.set_read_timeout(Some(HTTP_PROBE_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(HTTP_PROBE_TIMEOUT));
+
+    let request = format!(
+        "GET / HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        address
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("Failed to send request to {}: {}", address, e))?;
+    let mut response = Vec::new();
+    let _ = stream.take(64 * 1024).read_to_end(&mut response);
+    let response = String::from_utf8_lossy(&response);
+
+    let status = response
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| format!("Invalid HTTP response from {}", address))?;
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.chars().take(2000).collect())
+        .unwrap_or_default();
+    Ok((status, body))
+}
+
+/// Host and port from a "Running on local URL:  http://127.0.0.1:7860" line.
+fn parse_running_url(line: &str) -> Option<(String, u16)> {
+    let url = line.split_whitespace().find(|word| word.contains("://"))?;
+    let authority = url.split("://").nth(1)?.split('/').next()?;
+    let (host, port) = authority.rsplit_once(':')?;
+    Some((
+        host.trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string(),
+        port.parse().ok()?,
+    ))
+}
+
+/// The address to connect to for a server bound to `host`.
+fn dial_host(host: &str) -> &str {
+    match host {
+        "0.0.0.0" => "127.0.0.1",
+        "::" => "::1",
+        host => host,
+    }
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    host == "localhost"
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|address| address.is_loopback())
+}
+
+/// Gradio prints 127.0.0.1 or localhost for a server bound to every interface, so an
+/// unspecified or loopback request matches any loopback report.
+fn same_bind_host(requested: &str, actual: &str) -> bool {
+    requested == actual
+        || matches!(requested, "0.0.0.0" | "::")
+        || (is_loopback_host(requested) && is_loopback_host(actual))
+}
+
+fn accepts_connections(host: &str, port: u16) -> bool {
+    let Ok(addresses) = (dial_host(host), port).to_socket_addrs() else {
+        return false;
+    };
+    addresses
+        .into_iter()
+        .any(|address| TcpStream::connect_timeout(&address, BIND_PROBE_TIMEOUT).is_ok())
+}
+
+/// Checks that the server listens where it was asked to, cross-checking the address
+/// Gradio reported against actual connection attempts.
+async fn check_bind(
+    requested_host: String,
+    requested_port: u16,
+    reported: Option<(String, u16)>,
+) -> BindVerification {
+    let reported_url = reported
+        .as_ref()
+        .map(|(host, port)| format!("http://{}:{}", host, port));
+    let probe_host = requested_host.clone();
+    let probe_reported = reported.clone();
+    let (requested_open, reported_open) = tauri::async_runtime::spawn_blocking(move || {
+        (
+            accepts_connections(&probe_host, requested_port),
+            probe_reported.is_some_and(|(host, port)| accepts_connections(&host, port)),
+        )
+    })
+    .await
+    .unwrap_or((false, false));
+
+    let actual = if requested_open {
+        Some((requested_host.clone(), requested_port))
+    } else if reported_open {
+        reported
+    } else {
+        None
+    };
+    let mismatch = actual.as_ref().is_some_and(|(host, port)| {
+        *port != requested_port || !same_bind_host(&requested_host, host)
+    });
+    let message = match &actual {
+        None => format!(
+            "Nothing accepts connections on {}:{}{}.",
+            requested_host,
+            requested_port,
+            reported_url
+                .as_ref()
+                .map(|url| format!(" or at the reported {}", url))
+                .unwrap_or_default()
+        ),
+        Some((host, port)) if mismatch => format!(
+            "webui.py was asked to listen on {}:{} but is listening on {}:{}. It may ignore --host/--port, or GRADIO_SERVER_NAME/GRADIO_SERVER_PORT override them.",
+            requested_host, requested_port, host, port
+        ),
+        Some((host, port)) => format!("Server is listening on {}:{}.", host, port),
+    };
+    BindVerification {
+        requested_host,
+        requested_port,
+        reported_url,
+        actual_host: actual.as_ref().map(|(host, _)| host.clone()),
+        actual_port: actual.map(|(_, port)| port),
+        mismatch,
+        message,
+    }
+}
+
+/// Verifies the bind once Gradio reports its URL and, if the server ended up elsewhere,
+/// records the real address and warns. Returns where to send the health probes.
+async fn verify_bind_on_ready(
+    app_handle: &AppHandle,
+    line: &str,
+    host: &str,
+    port: u16,
+) -> (String, u16) {
+    let bind = check_bind(host.to_string(), port, parse_running_url(line)).await;
+    let target = match (&bind.actual_host, bind.actual_port) {
+        (Some(actual_host), Some(actual_port)) => (actual_host.clone(), actual_port),
+        _ => (host.to_string(), port),
+    };
+    if bind.mismatch {
+        emit_server_log(app_handle, "stderr", &format!("WARNING: {}", bind.message));
+        let _ = app_handle.emit(BIND_MISMATCH_EVENT, bind.clone());
+    }
+    app_handle.state::<ServerChildProcess>().record_bind(bind);
+    (dial_host(&target.0).to_string(), target.1)
+}
+
+/// Re-checks where the running server listens and corrects the stored address if it
+/// moved. Uses the originally requested host/port, not an earlier correction.
+#[tauri::command]
+pub async fn verify_server_bind(
+    app_handle: AppHandle,
+    state: State<'_, ServerChildProcess>,
+) -> Result<BindVerification, String> {
+    if state.lock().is_none() {
+        return Err("Server is not running.".to_string());
+    }
+    let launch = state
+        .launch_record()
+        .ok_or_else(|| "No server launch has been recorded.".to_string())?;
+    let (host, port, reported) = match &launch.bind {
+        Some(bind) => (
+            bind.requested_host.clone(),
+            bind.requested_port,
+            bind.reported_url.as_deref().and_then(parse_running_url),
+        ),
+        None => (launch.host.clone(), launch.port, None),
+    };
+    let bind = check_bind(host, port, reported).await;
+    if bind.mismatch {
+        let _ = app_handle.emit(BIND_MISMATCH_EVENT, bind.clone());
+    }
+    state.record_bind(bind.clone());
+    Ok(bind)
+}
+
+/// Runs after Gradio prints its URL: the server only counts as running once a request
+/// succeeds. Server errors across the whole window mark it unhealthy instead.
+async fn verify_http_health(app_handle: AppHandle, host: String, port: u16) {
+    let state = app_handle.state::<ServerChildProcess>();
+    let deadline = Instant::now() + READINESS_WINDOW;
+    let last_failure = loop {
+        if state.lock().is_none() {
+            return;
+        }
+        let probe_host = host.clone();
+        let result = tauri::async_runtime::spawn_blocking(move || http_get(&probe_host, port))
+            .await
+            .map_err(|e| format!("Health check task failed: {}", e))
+            .and_then(|result| result);
+        let failure = match result {
+            Ok((status, _)) if status < 500 => {
+                on_server_ready(&app_handle);
+                return;
+            }
+            Ok((status, body)) => format!("HTTP {}: {}", status, body.trim()),
+            Err(e) => e,
+        };
+        if Instant::now() >= deadline {
+            break failure;
+        }
+        sleep(READINESS_PROBE_INTERVAL).await;
+    };
+
+    let reason = format!(
+        "Server responded with errors for {}s. Last response: {}\n--- recent server log ---\n{}",
+        READINESS_WINDOW.as_secs(),
+        last_failure,
+        state.log_tail().join("\n")
+    );
+    state.set_unhealthy(Some(reason.clone()));
+    emit_server_status(&app_handle, ServerStatus::Unhealthy { reason });
+}
+
+/// Polls the server until it answers HTTP, covering webui.py versions whose output
+/// never shows Gradio's URL line. Reports an error once `timeout` passes without it
+/// listening; the process is left running in case the model load is just slow.
+async fn watch_startup(app_handle: AppHandle, pid: u32, timeout: Duration) {
+    let state = app_handle.state::<ServerChildProcess>();
+    let deadline = Instant::now() + timeout;
+    loop {
+        sleep(STARTUP_POLL_INTERVAL).await;
+        // Stopped, exited (reported as a crash) or replaced by another start.
+        if state.child_pid() != Some(pid) || state.is_ready() {
+            return;
+        }
+        let Some(launch) = state.launch_record() else {
+            return;
+        };
+        let probe_host = dial_host(&launch.host).to_string();
+        let port = launch.port;
+        let answered = tauri::async_runtime::spawn_blocking(move || http_get(&probe_host, port))
+            .await
+            .is_ok_and(|result| result.is_ok_and(|(status, _)| status < 500));
+        if answered {
+            on_server_ready(&app_handle);
+            return;
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+    }
+
+    let Some(launch) = state.launch_record() else {
+        return;
+    };
+    let reason = format!(
+        "The server did not start listening on {}:{} within {}s. Check the server log for errors; if the model is still loading, it will switch to running once it is up.\n--- recent server log ---\n{}",
+        launch.host,
+        launch.port,
+        timeout.as_secs(),
+        state.log_tail().join("\n")
+    );
+    state.set_startup_error(Some(reason.clone()));
+    emit_server_status(&app_handle, ServerStatus::Error { reason });
+}
+
+/// Waits for the registered server to exit and returns its status, or `None` once it is
+/// no longer registered because it was stopped on purpose. The child stays registered
+/// while waiting, so the lock is only held for each poll.
+async fn registered_exit_status(state: &ServerChildProcess) -> Option<ExitStatus> {
+    loop {
+        {
+            let mut guard = state.lock();
+            let child = guard.as_mut()?;
+            if let Ok(Some(status)) = child.try_wait() {
+                return Some(status);
+            }
+        }
+        sleep(EXIT_STATUS_POLL_INTERVAL).await;
+    }
+}
+
+/// Called when the server's stdout closes. If the child is still registered, nobody
+/// asked it to stop, so the exit is reported as a crash.
+async fn on_server_output_closed(app_handle: &AppHandle, spawned_at: Instant) {
+    let state = app_handle.state::<ServerChildProcess>();
+    // stdout usually closes just before the child is reaped.
+    let waited = tokio::time::timeout(EXIT_STATUS_WAIT, registered_exit_status(&state)).await;
+    let exit_status = match waited {
+        Ok(None) => return,
+        Ok(Some(status)) => Some(status),
+        Err(_) => {
+            if state.lock().is_none() {
+                return;
+            }
+            None
+        }
+    };
+    let exit_code = exit_status.and_then(|status| status.code());
+    let launch = state.launch_record();
+    let memory_limit_mb = launch.as_ref().and_then(|launch| launch.memory_limit_mb);
+    let limit_hit = if memory_limit_mb.is_some() {
+        // Queries systemd, which blocks.
+        let oom_app = app_handle.clone();
+        let oom_killed = tauri::async_runtime::spawn_blocking(move || {
+            oom_app.state::<ServerChildProcess>().scope_oom_killed()
+        })
+        .await
+        .unwrap_or(false);
+        memory_limit_hit(exit_status, oom_killed, &state.log_tail())
+    } else {
+        false
+    };
+    let crashed_at = unix_timestamp();
+    let crash = ServerCrash {
+        exit_code,
+        crashed_at,
+        memory_limit_hit: limit_hit,
+        gpu: None,
+    };
+    state.set_crash(Some(crash.clone()));
+    emit_server_status(app_handle, ServerStatus::Crashed(crash));
+    if launch.is_some_and(|launch| launch.device == "cuda") {
+        // Taken now, before other processes grab or release the VRAM.
+        let snapshot_app = app_handle.clone();
+        spawn_tracked(app_handle, "crash_gpu_snapshot", async move {
+            let snapshot = capture_gpu_snapshot().await;
+            snapshot_app
+                .state::<ServerChildProcess>()
+                .attach_crash_gpu_snapshot(crashed_at, snapshot);
+        });
+    }
+
+    notify_server_event(
+        app_handle,
+        ServerEvent {
+            kind: if limit_hit { "oom" } else { "crashed" }.to_string(),
+            success: false,
+            duration_secs: spawned_at.elapsed().as_secs_f64(),
+            exit_code,
+            message: match (limit_hit, exit_code) {
+                (true, _) => format!(
+                    "IndexTTS server ran out of memory under its {} MB limit. Raise memory_limit_mb or use fp16 precision.",
+                    memory_limit_mb.unwrap_or_default()
+                ),
+                (false, Some(code)) => {
+                    format!("IndexTTS server exited unexpectedly with code {}.", code)
+                }
+                (false, None) => "IndexTTS server exited unexpectedly.".to_string(),
+            },
+        },
+    );
+}
+
+/// Called once webui.py reports its URL: flips the status to Running and records how
+/// long the model took to load.
+fn on_server_ready(app_handle: &AppHandle) {
+    let state = app_handle.state::<ServerChildProcess>();
+    if state.is_ready() {
+        return;
+    }
+    state.set_ready(true);
+    state.set_startup_error(None);
+    emit_server_status(app_handle, ServerStatus::Running);
+
+    if let Some(started_at) = state.take_started_at() {
+        let mut history: Vec<StartupSample> = load_json(app_handle, STARTUP_HISTORY_FILE)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        history.push(StartupSample {
+            duration_secs: started_at.elapsed().as_secs_f64(),
+            recorded_at: unix_timestamp(),
+        });
+        if history.len() > STARTUP_HISTORY_LIMIT {
+            history.drain(..history.len() - STARTUP_HISTORY_LIMIT);
+        }
+        let _ = save_json(app_handle, STARTUP_HISTORY_FILE, &history);
+    }
+
+    if let Some(launch) = state
+        .launch_record()
+        .filter(|launch| launch.device == "cuda")
+    {
+        let driver_app = app_handle.clone();
+        spawn_tracked(app_handle, "record_known_good_driver", async move {
+            record_known_good_driver(&driver_app, &launch.target_dir).await;
+        });
+    }
+}
+
+#[tauri::command]
+pub fn get_last_startup_duration(app_handle: AppHandle) -> Result<StartupTiming, String> {
+    let history: Vec<StartupSample> =
+        load_json(&app_handle, STARTUP_HISTORY_FILE)?.unwrap_or_default();
+    let last_secs = history.last().map(|sample| sample.duration_secs);
+
+    // Compare the latest start against the ones before it.
+    let previous = &history[..history.len().saturating_sub(1)];
+    let typical_secs = median_secs(previous).or(last_secs);
+    let abnormally_slow = match (last_secs, median_secs(previous)) {
+        (Some(last), Some(typical)) if previous.len() >= 3 => last > typical * 2.0,
+        _ => false,
+    };
+
+    Ok(StartupTiming {
+        last_secs,
+        typical_secs,
+        abnormally_slow,
+        history,
+    })
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerLogEvent {
+    // Increases by one per line for the lifetime of the app, across server restarts.
+    pub seq: u64,
+    pub stream: String,
+    pub level: String,
+    pub line: String,
+}
+
+#[derive(Default)]
+struct ServerLogBuffer {
+    next_seq: u64,
+    lines: VecDeque<ServerLogEvent>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerLogsSince {
+    pub lines: Vec<ServerLogEvent>,
+    pub latest_seq: u64,
+    // Some lines after the cursor are no longer buffered.
+    pub truncated: bool,
+}
+
+/// Best-effort level of a server log line, from Python logging / Gradio output.
+fn classify_log_level(line: &str) -> &'static str {
+    let upper = line.to_uppercase();
+    if upper.contains("TRACEBACK") || upper.contains("ERROR") || upper.contains("CRITICAL") {
+        "error"
+    } else if upper.contains("WARNING") || upper.contains("WARN:") {
+        "warning"
+    } else if upper.contains("DEBUG") {
+        "debug"
+    } else {
+        "info"
+    }
+}
+
+fn emit_server_log(app_handle: &AppHandle, stream: &str, line: &str) {
+    let event = app_handle
+        .state::<ServerChildProcess>()
+        .push_log_line(ServerLogEvent {
+            seq: 0,
+            stream: stream.to_string(),
+            level: classify_log_level(line).to_string(),
+            line: line.to_string(),
+        });
+    append_log_line(app_handle, SERVER_LOG, stream, line);
+    let _ = app_handle.emit(SERVER_LOG_EVENT, event);
+}
+
+/// Every buffered line of the current (or last) server run, up to the last 2000, with
+/// its stream. The buffer is cleared when a new server starts.
+#[tauri::command]
+pub fn get_server_logs(
+    state: State<'_, ServerChildProcess>,
+) -> Result<Vec<ServerLogEvent>, String> {
+    Ok(state.log_lines_since(0).lines)
+}
+
+/// Returns the buffered server log lines after sequence number `seq` (0 for all), so a
+/// UI that reconnects can fetch exactly the `server-log` events it missed.
+#[tauri::command]
+pub fn get_server_logs_since(
+    seq: u64,
+    state: State<'_, ServerChildProcess>,
+) -> Result<ServerLogsSince, String> {
+    Ok(state.log_lines_since(seq))
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ShareUrlEvent {
+    pub url: Option<String>,
+    pub error: Option<String>,
+    pub warning: String,
+}
+
+/// Picks the public link (or the tunnel failure) out of Gradio's output in share mode.
+fn handle_share_line(app_handle: &AppHandle, line: &str) {
+    let event = if let Some((_, rest)) = line.split_once(GRADIO_SHARE_MARKER) {
+        let url = rest.trim().to_string();
+        app_handle
+            .state::<ServerChildProcess>()
+            .set_share_url(Some(url.clone()));
+        ShareUrlEvent {
+            url: Some(url),
+            error: None,
+            warning: SHARE_WARNING.to_string(),
+        }
+    } else if line.contains(GRADIO_SHARE_FAILED_MARKER) {
+        ShareUrlEvent {
+            url: None,
+            error: Some(format!(
+                "The public share link could not be created; the server is still reachable locally. Gradio said: {}",
+                line.trim()
+            )),
+            warning: SHARE_WARNING.to_string(),
+        }
+    } else {
+        return;
+    };
+    let _ = app_handle.emit(SHARE_URL_EVENT, event);
+}
+
+/// Maps the requested verbosity onto the env vars Gradio and transformers read.
+fn apply_log_level(command: &mut tokio::process::Command, log_level: &str) {
+    command.env("TRANSFORMERS_VERBOSITY", log_level);
+    if log_level == "debug" {
+        command
+            .env("GRADIO_DEBUG", "1")
+            .env("PYTHONWARNINGS", "default");
+    }
+}
+
+/// Caps the thread pools of torch (OpenMP) and the BLAS backends it may use.
+fn apply_cpu_threads(command: &mut tokio::process::Command, threads: usize) {
+    let threads = threads.to_string();
+    for var in [
+        "OMP_NUM_THREADS",
+        "MKL_NUM_THREADS",
+        "OPENBLAS_NUM_THREADS",
+        "NUMEXPR_NUM_THREADS",
+    ] {
+        command.env(var, &threads);
+    }
+}
+
+/// Launch precondition: the model webui.py will load must be present. A missing model
+/// carries `recoverable_action: "download_model"` so the UI can offer `ensure_model`.
+async fn check_model_present(target_dir: &str, model_dir: &str) -> Result<(), DeployError> {
+    let effective_dir = if model_dir.is_empty() {
+        Path::new(target_dir)
+            .join("checkpoints")
+            .to_string_lossy()
+            .to_string()
+    } else {
+        model_dir.to_string()
+    };
+    let missing = match probe_model_dir(&effective_dir).await {
+        Ok(probe) => !probe.has_core_files,
+        Err(_) if !Path::new(&effective_dir).exists() => true,
+        Err(e) => return Err(DeployError::new("start_server", e)),
+    };
+    if !missing {
+        return Ok(());
+    }
+
+    let mut error = DeployError::new(
+        "start_server",
+        format!(
+            "Model files are missing from {}. Please download the model first.",
+            effective_dir
+        ),
+    );
+    error.kind = "model_missing".to_string();
+    error.remediation = Some(
+        "Run ensure_model to download only the model, then start the server again.".to_string(),
+    );
+    error.recoverable_action = Some("download_model".to_string());
+    Err(error)
 }
 
 #[tauri::command(rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
 pub async fn start_index_tts_server(
     app_handle: AppHandle,
     target_dir: String,
-    host: String,
-    port: u16,
-    device: String,
+    host: Option<String>,
+    port: Option<u16>,
+    device: Option<String>,
     precision: Option<String>,
+    preset: Option<String>,
+    log_level: Option<String>,
+    cpu_threads: Option<usize>,
+    gpu_index: Option<u32>,
+    share: Option<bool>,
+    memory_limit_mb: Option<u64>,
+    startup_timeout_secs: Option<u64>,
+    state: State<'_, ServerChildProcess>,
+) -> Result<ServerStatus, DeployError> {
+    let target_dir = clean_path_arg(&target_dir);
+    let model_dir = app_handle.state::<EngineManager>().lock().model_dir.clone();
+    check_model_present(&target_dir, &model_dir).await?;
+    launch_server(
+        app_handle,
+        target_dir,
+        model_dir,
+        LaunchSettings {
+            host,
+            port,
+            device,
+            precision,
+            log_level,
+            cpu_threads,
+            gpu_index,
+        },
+        preset,
+        share.unwrap_or(false),
+        memory_limit_mb.filter(|limit| *limit > 0),
+        startup_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map_or(DEFAULT_STARTUP_TIMEOUT, Duration::from_secs),
+        state,
+    )
+    .await
+    .map_err(|e| DeployError::new("start_server", e))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn launch_server(
+    app_handle: AppHandle,
+    target_dir: String,
+    model_dir: String,
+    requested: LaunchSettings,
+    preset: Option<String>,
+    share: bool,
+    memory_limit_mb: Option<u64>,
+    startup_timeout: Duration,
     state: State<'_, ServerChildProcess>,
 ) -> Result<ServerStatus, String> {
+    if state.lock().is_some() {
+        return Err("Server is already running.".to_string());
+    }
+    let settings = resolve_launch_settings(&app_handle, preset.as_deref(), requested)?;
+    validate_launch_settings(&settings)?;
+    // Fail now rather than partway into the model load.
+    check_device_available(
+        &target_dir,
+        settings.device.as_deref().unwrap_or("cpu"),
+        settings.gpu_index,
+    )
+    .await?;
+
     let mut guard = state.lock();
     if guard.is_some() {
         return Err("Server is already running.".to_string());
     }
+    let host = settings
+        .host
+        .unwrap_or_else(|| DEFAULT_SERVER_HOST.to_string());
+    let port = settings.port.unwrap_or(DEFAULT_SERVER_PORT);
+    let device = settings.device.unwrap_or_else(|| "cpu".to_string());
+    let precision = settings.precision;
+    let log_level = settings.log_level.unwrap_or_else(|| "info".to_string());
+    let cpu_threads = settings
+        .cpu_threads
+        .map(|threads| threads.min(logical_core_count()));
 
     let repo_path = Path::new(&target_dir);
     if !repo_path.exists() {
@@ -84,21 +1118,50 @@ pub async fn start_index_tts_server(
         ));
     }
 
-    let mut command = new_command("uv");
+    validate_bind_host(&host)?;
+    let port_check = check_port(&host, port);
+    if !port_check.is_usable() {
+        return Err(port_check.message);
+    }
+
+    // Held for as long as the server runs so a second app instance can't start it again.
+    let dir_lock = acquire_dir_lock(&target_dir, "start_index_tts_server")?;
+
+    let (mut command, limit_warning) = memory_limited_command("uv", memory_limit_mb);
     command
         .arg("run")
         .current_dir(&target_dir)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped());
 
+    apply_log_level(&mut command, &log_level);
+    if let Some(threads) = cpu_threads {
+        apply_cpu_threads(&mut command, threads);
+    }
+    // webui.py has no --share flag; Gradio's launch() reads this instead.
+    if share {
+        command.env("GRADIO_SHARE", "True");
+    }
+
+    // Own process group so stopping the server also reaches python/worker children.
+    #[cfg(unix)]
+    command.process_group(0);
+    // On Windows the group is also what CTRL-BREAK is delivered to on a graceful stop.
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        command.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
+    }
+
     let mut webui_args: Vec<String> = vec![
         "--host".to_string(),
-        host,
+        host.clone(),
         "--port".to_string(),
         port.to_string(),
     ];
 
-    if let Some(p) = precision {
+    if let Some(p) = &precision {
         if p == "fp16" {
             webui_args.push("--fp16".to_string());
         }
@@ -106,8 +1169,33 @@ pub async fn start_index_tts_server(
 
     if device == "cuda" {
         webui_args.push("--cuda_kernel".to_string());
+        // Only the chosen card is visible, so webui.py's default cuda:0 lands on it.
+        if let Some(index) = settings.gpu_index {
+            command.env("CUDA_VISIBLE_DEVICES", index.to_string());
+        }
     }
 
+    if !model_dir.is_empty() {
+        webui_args.push("--model_dir".to_string());
+        webui_args.push(model_dir);
+    }
+
+    let launch_record = ServerLaunchRecord {
+        target_dir: target_dir.clone(),
+        host: host.clone(),
+        port,
+        device: device.clone(),
+        precision,
+        log_level: log_level.clone(),
+        cpu_threads,
+        gpu_index: settings.gpu_index,
+        share,
+        memory_limit_mb,
+        webui_args: webui_args.clone(),
+        started_at: unix_timestamp(),
+        bind: None,
+    };
+
     #[cfg(target_os = "windows")]
     {
         let mut argv: Vec<String> = Vec::with_capacity(webui_args.len() + 1);
@@ -132,9 +1220,21 @@ pub async fn start_index_tts_server(
     // For now, let's assume it's handled by setup_index_tts_env or download_index_tts_model if required.
     // If a direct HF_ENDPOINT is needed here, it should be passed from the frontend.
 
+    record_command(&app_handle, "start_server", &command);
+    let spawned_at = Instant::now();
     let mut child = command
         .spawn()
         .map_err(|e| format!("Failed to start server: {}", e))?;
+    let (memory_limit, limit_warning) = match memory_limit_mb {
+        Some(limit_mb) => match apply_memory_limit(&child, limit_mb) {
+            Ok(guard) => (Some(guard), limit_warning),
+            Err(e) => (
+                None,
+                Some(format!("{}; the server runs without a memory cap.", e)),
+            ),
+        },
+        None => (None, limit_warning),
+    };
 
     let stdout = child
         .stdout
@@ -145,52 +1245,290 @@ pub async fn start_index_tts_server(
         .take()
         .ok_or("Failed to capture stderr".to_string())?;
 
-    tokio::spawn({
+    spawn_tracked(&app_handle, "server_stdout", {
         let app_handle = app_handle.clone();
+        let host = host.clone();
         async move {
             let mut reader = BufReader::new(stdout).lines();
             while let Ok(Some(line)) = reader.next_line().await {
+                if line.contains(GRADIO_READY_MARKER) {
+                    let (probe_host, probe_port) =
+                        verify_bind_on_ready(&app_handle, &line, &host, port).await;
+                    spawn_tracked(
+                        &app_handle,
+                        "server_health_probe",
+                        verify_http_health(app_handle.clone(), probe_host, probe_port),
+                    );
+                }
+                if share {
+                    handle_share_line(&app_handle, &line);
+                }
+                emit_server_log(&app_handle, "stdout", &line);
                 app_handle.emit("server-log-stdout", line).unwrap();
             }
+            on_server_output_closed(&app_handle, spawned_at).await;
         }
     });
 
     let app_handle_err = app_handle.clone();
-    tokio::spawn(async move {
+    spawn_tracked(&app_handle, "server_stderr", async move {
         let mut reader = BufReader::new(stderr).lines();
         while let Ok(Some(line)) = reader.next_line().await {
+            if share {
+                handle_share_line(&app_handle_err, &line);
+            }
+            emit_server_log(&app_handle_err, "stderr", &line);
             app_handle_err.emit("server-log-stderr", line).unwrap();
         }
     });
 
+    let pid = child.id();
     *guard = Some(child);
+    state.set_dir_lock(Some(dir_lock));
+    state.set_ready(false);
+    state.set_unhealthy(None);
+    state.set_startup_error(None);
+    state.clear_log_tail();
+    state.set_launch_record(Some(launch_record));
+    state.set_crash(None);
+    state.set_started_at(Some(spawned_at));
+    state.set_url(Some(advertised_url(&host, port)));
+    state.set_share_url(None);
+    state.set_memory_limit(memory_limit);
+    if let Some(warning) = limit_warning {
+        emit_server_log(&app_handle, "stderr", &format!("WARNING: {}", warning));
+    }
+    if share {
+        emit_server_log(
+            &app_handle,
+            "stderr",
+            &format!("WARNING: {}", SHARE_WARNING),
+        );
+    }
+    if let Some(pid) = pid {
+        spawn_tracked(
+            &app_handle,
+            "server_startup_watch",
+            watch_startup(app_handle.clone(), pid, startup_timeout),
+        );
+    }
+    emit_server_status(&app_handle, ServerStatus::Starting);
 
     Ok(ServerStatus::Starting)
 }
 
 #[tauri::command]
 pub async fn stop_index_tts_server(
+    app_handle: AppHandle,
     state: State<'_, ServerChildProcess>,
 ) -> Result<ServerStatus, String> {
     let child = {
         let mut guard = state.lock();
         guard.take()
     };
+    let server_pid = child.as_ref().and_then(Child::id);
 
     if let Some(mut child_process) = child {
-        if let Err(e) = child_process.kill().await {
+        if let Err(e) = stop_process_tree(&mut child_process).await {
             let mut guard = state.lock();
             *guard = Some(child_process);
             return Err(format!("Failed to stop server: {}", e));
         }
-        let _ = child_process.wait().await;
     }
+    state.set_dir_lock(None);
+    state.set_ready(false);
+    state.set_unhealthy(None);
+    state.set_started_at(None);
+    state.set_url(None);
+    state.set_share_url(None);
+    state.set_memory_limit(None);
+
+    state.set_startup_error(None);
 
-    ensure_port_closed(7860).await?;
+    // The address the server was started on (or found listening on); without a launch
+    // record the app did not start it and the configured scan list is checked instead,
+    // on the host of the preset using each port.
+    let addresses = match state.launch_record() {
+        Some(launch) => vec![(launch.host, launch.port)],
+        None => {
+            let config = load_server_config(&app_handle);
+            stop_scan_ports(&config)
+                .into_iter()
+                .map(|port| {
+                    let host = config
+                        .presets
+                        .values()
+                        .find(|preset| preset.port == Some(port))
+                        .and_then(|preset| preset.host.clone())
+                        .unwrap_or_else(|| DEFAULT_SERVER_HOST.to_string());
+                    (host, port)
+                })
+                .collect()
+        }
+    };
+    for (host, port) in addresses {
+        ensure_port_closed(&host, port, server_pid).await?;
+    }
+    emit_server_status(&app_handle, ServerStatus::Stopped);
 
     Ok(ServerStatus::Stopped)
 }
 
+/// Abort a server that is still loading (webui.py has not printed its URL yet),
+/// killing the whole process tree so no half-initialized CUDA context survives.
+#[tauri::command]
+pub async fn cancel_server_start(
+    app_handle: AppHandle,
+    state: State<'_, ServerChildProcess>,
+) -> Result<ServerStatus, String> {
+    let child = {
+        let mut guard = state.lock();
+        if guard.is_some() && state.is_ready() {
+            return Err(
+                "Server has finished loading. Use stop_index_tts_server to stop it.".to_string(),
+            );
+        }
+        guard.take()
+    };
+
+    let Some(mut child_process) = child else {
+        return Err("No server start is in progress.".to_string());
+    };
+
+    if let Err(e) = kill_process_tree(&mut child_process).await {
+        let mut guard = state.lock();
+        *guard = Some(child_process);
+        return Err(format!("Failed to cancel server start: {}", e));
+    }
+    state.set_dir_lock(None);
+    state.set_unhealthy(None);
+    state.set_started_at(None);
+    state.set_url(None);
+    state.set_share_url(None);
+    state.set_memory_limit(None);
+    state.set_startup_error(None);
+    emit_server_status(&app_handle, ServerStatus::Stopped);
+
+    Ok(ServerStatus::Stopped)
+}
+
+/// Called on app exit. Stops the server unless the user chose to keep it running, in
+/// which case the handle is dropped and the process is left detached.
+pub fn shutdown_server(app_handle: &AppHandle) {
+    let Some(state) = app_handle.try_state::<ServerChildProcess>() else {
+        return;
+    };
+    let child = state.lock().take();
+    let Some(mut child) = child else {
+        return;
+    };
+    if load_settings(app_handle).keep_server_on_exit {
+        drop(child);
+    } else if let Err(e) = tauri::async_runtime::block_on(stop_process_tree(&mut child)) {
+        append_log_line(
+            app_handle,
+            APP_LOG,
+            "shutdown",
+            &format!("Failed to stop server on exit: {}", e),
+        );
+    }
+    state.set_dir_lock(None);
+}
+
+/// Asks the server's process group to exit: SIGTERM on Unix.
+#[cfg(unix)]
+fn request_graceful_stop(pid: u32) -> bool {
+    // The server runs in its own process group, so a negative PID reaches all of it.
+    unsafe { libc::kill(-(pid as libc::pid_t), libc::SIGTERM) == 0 }
+}
+
+/// Asks the server's process group to exit with CTRL-BREAK, which Python turns into
+/// KeyboardInterrupt.
+#[cfg(windows)]
+fn request_graceful_stop(pid: u32) -> bool {
+    use windows_sys::Win32::System::Console::{
+        AttachConsole, FreeConsole, GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT,
+    };
+
+    unsafe {
+        // Release builds have no console of their own; the event can only be sent to a
+        // group on the caller's console, so borrow the server's for the call.
+        let attached = AttachConsole(pid) != 0;
+        let sent = GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid) != 0;
+        if attached {
+            FreeConsole();
+        }
+        sent
+    }
+}
+
+/// Polls `try_wait` until the process exits or `timeout` passes; true when it exited.
+async fn wait_for_exit(child: &mut Child, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        sleep(Duration::from_millis(200)).await;
+    }
+}
+
+/// Stop the server the polite way first so it can release the CUDA context and finish
+/// writing files, and kill the tree only if it is still running after
+/// `GRACEFUL_STOP_TIMEOUT`.
+async fn stop_process_tree(child: &mut Child) -> Result<(), String> {
+    if let Some(pid) = child.id() {
+        if request_graceful_stop(pid) && wait_for_exit(child, GRACEFUL_STOP_TIMEOUT).await {
+            return Ok(());
+        }
+    }
+    kill_process_tree(child).await
+}
+
+/// Kill the server and every process it spawned (uv -> python -> workers).
+async fn kill_process_tree(child: &mut Child) -> Result<(), String> {
+    if let Some(pid) = child.id() {
+        // The server runs in its own process group, so a negative PID reaches all of it.
+        #[cfg(unix)]
+        let _ = new_command("kill")
+            .args(["-9", "--", &format!("-{}", pid)])
+            .output()
+            .await;
+
+        #[cfg(windows)]
+        let _ = new_command("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .output()
+            .await;
+    }
+
+    if let Err(e) = child.kill().await {
+        // The tree kill above may already have reaped it.
+        if child.try_wait().map_or(true, |status| status.is_none()) {
+            return Err(e.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// URL collaborators should use to reach the running server; `None` when it is stopped.
+#[tauri::command]
+pub fn get_server_url(state: State<'_, ServerChildProcess>) -> Result<Option<String>, String> {
+    Ok(state.lock().as_ref().and(state.url()))
+}
+
+/// Public gradio.live link when the server was started with `share`; `None` until the
+/// tunnel is up.
+#[tauri::command]
+pub fn get_server_share_url(
+    state: State<'_, ServerChildProcess>,
+) -> Result<Option<String>, String> {
+    Ok(state.lock().as_ref().and(state.share_url()))
+}
+
 #[tauri::command]
 pub async fn get_server_status(
     state: State<'_, ServerChildProcess>,
@@ -203,12 +1541,22 @@ pub async fn get_server_status(
         {
             Some(_status) => {
                 *guard = None;
-                Ok(ServerStatus::Stopped)
+                state.set_dir_lock(None);
+                Ok(state
+                    .last_crash()
+                    .map_or(ServerStatus::Stopped, ServerStatus::Crashed))
             }
-            None => Ok(ServerStatus::Running),
+            None if state.is_ready() => Ok(ServerStatus::Running),
+            None => match (state.unhealthy_reason(), state.startup_error()) {
+                (Some(reason), _) => Ok(ServerStatus::Unhealthy { reason }),
+                (None, Some(reason)) => Ok(ServerStatus::Error { reason }),
+                (None, None) => Ok(ServerStatus::Starting),
+            },
         }
     } else {
-        Ok(ServerStatus::Stopped)
+        Ok(state
+            .last_crash()
+            .map_or(ServerStatus::Stopped, ServerStatus::Crashed))
     }
 }
 
@@ -217,21 +1565,96 @@ pub struct RepoUpdateInfo {
     pub has_update: bool,
     pub local_hash: String,
     pub remote_hash: String,
+    // The ref `remote_hash` was read from, e.g. `origin/main` or a pinned tag.
+    pub compared_ref: String,
     pub message: String,
 }
 
+/// What updates are compared against (`compare`, e.g. `origin/main` or `v2.0.0`) and
+/// the remote and ref `git pull` fetches it with.
+struct UpdateRef {
+    compare: String,
+    remote: String,
+    refspec: String,
+}
+
+async fn ref_exists(target_dir: &str, full_ref: &str) -> bool {
+    git_stdout(target_dir, &["rev-parse", "--verify", "--quiet", full_ref])
+        .await
+        .is_ok()
+}
+
+/// Resolves `branch` (a branch on origin, `remote/branch`, a tag or a commit) or, when
+/// it is `None`, the branch HEAD tracks, falling back to the remote's default branch.
+async fn resolve_update_ref(target_dir: &str, branch: Option<&str>) -> Result<UpdateRef, String> {
+    if let Some(branch) = branch.map(str::trim).filter(|branch| !branch.is_empty()) {
+        if let Some((remote, name)) = branch.split_once('/') {
+            if ref_exists(target_dir, &format!("refs/remotes/{}", branch)).await {
+                return Ok(UpdateRef {
+                    compare: branch.to_string(),
+                    remote: remote.to_string(),
+                    refspec: name.to_string(),
+                });
+            }
+        }
+        // Tags and commits are compared as they are; branches through their remote copy.
+        let remote_branch = format!("origin/{}", branch);
+        let compare = if ref_exists(target_dir, &format!("refs/remotes/{}", remote_branch)).await {
+            remote_branch
+        } else {
+            branch.to_string()
+        };
+        return Ok(UpdateRef {
+            compare,
+            remote: "origin".to_string(),
+            refspec: branch.to_string(),
+        });
+    }
+
+    let upstream = match git_stdout(
+        target_dir,
+        &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+    )
+    .await
+    {
+        Ok(upstream) => upstream,
+        Err(_) => git_stdout(target_dir, &["rev-parse", "--abbrev-ref", "origin/HEAD"])
+            .await
+            .map_err(|_| {
+                "HEAD does not track a remote branch (it may be pinned to a tag or commit). Pass the branch or tag to compare against.".to_string()
+            })?,
+    };
+    let (remote, name) = upstream
+        .split_once('/')
+        .ok_or_else(|| format!("Unexpected upstream ref '{}'.", upstream))?;
+    Ok(UpdateRef {
+        compare: upstream.clone(),
+        remote: remote.to_string(),
+        refspec: name.to_string(),
+    })
+}
+
+/// Fetches and compares HEAD with `branch`, or with the tracked upstream branch when
+/// `branch` is `None`.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn check_repo_update(target_dir: String) -> Result<RepoUpdateInfo, String> {
+pub async fn check_repo_update(
+    target_dir: String,
+    branch: Option<String>,
+) -> Result<RepoUpdateInfo, String> {
+    let target_dir = clean_path_arg(&target_dir);
     let repo_path = Path::new(&target_dir);
     if !repo_path.exists() || !repo_path.is_dir() {
         return Err("Repository directory does not exist.".to_string());
     }
+    if is_archive_checkout(&target_dir) {
+        return Err(archive_update_unsupported(&target_dir));
+    }
 
     // Fetch latest changes from remote
     let fetch_output = new_command("git")
         .arg("-C")
         .arg(&target_dir)
-        .arg("fetch")
+        .args(["fetch", "--tags"])
         .output()
         .await
         .map_err(|e| format!("Failed to execute git fetch: {}", e))?;
@@ -262,11 +1685,12 @@ pub async fn check_repo_update(target_dir: String) -> Result<RepoUpdateInfo, Str
         .trim()
         .to_string();
 
-    // Get remote HEAD commit hash for the current branch
+    let update_ref = resolve_update_ref(&target_dir, branch.as_deref()).await?;
     let remote_hash_output = new_command("git")
         .arg("-C")
         .arg(&target_dir)
-        .args(["log", "-n", "1", "--pretty=format:%H", "origin/main"]) // Assuming 'main' branch
+        .args(["log", "-n", "1", "--pretty=format:%H"])
+        .arg(&update_ref.compare)
         .output()
         .await
         .map_err(|e| format!("Failed to get remote hash: {}", e))?;
@@ -292,24 +1716,60 @@ pub async fn check_repo_update(target_dir: String) -> Result<RepoUpdateInfo, Str
         has_update,
         local_hash,
         remote_hash,
+        compared_ref: update_ref.compare,
         message,
     })
 }
 
+async fn run_git_pull(
+    target_dir: &str,
+    update_ref: &UpdateRef,
+) -> Result<std::process::Output, String> {
+    new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .arg("pull")
+        .arg(&update_ref.remote)
+        .arg(&update_ref.refspec)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute git pull: {}", e))
+}
+
+/// Pulls `branch`, or the tracked upstream branch when `branch` is `None`.
 #[tauri::command(rename_all = "snake_case")]
-pub async fn pull_repo(target_dir: String) -> Result<String, String> {
+pub async fn pull_repo(
+    app_handle: AppHandle,
+    target_dir: String,
+    branch: Option<String>,
+) -> Result<String, String> {
+    let target_dir = clean_path_arg(&target_dir);
     let repo_path = Path::new(&target_dir);
     if !repo_path.exists() || !repo_path.is_dir() {
         return Err("Repository directory does not exist.".to_string());
     }
+    if is_archive_checkout(&target_dir) {
+        return Err(archive_update_unsupported(&target_dir));
+    }
+    // A pull that has to merge creates a commit, which needs an identity. Fast-forwards
+    // don't, so failing to set one is not fatal here.
+    let _ = ensure_repo_identity(&target_dir, true).await;
+    let update_ref = resolve_update_ref(&target_dir, branch.as_deref()).await?;
 
-    let pull_output = new_command("git")
-        .arg("-C")
-        .arg(&target_dir)
-        .arg("pull")
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute git pull: {}", e))?;
+    let mut pull_output = run_git_pull(&target_dir, &update_ref).await?;
+    if !pull_output.status.success()
+        && is_git_lock_error(&String::from_utf8_lossy(&pull_output.stderr))
+    {
+        // Retry once after removing locks left by an interrupted git run.
+        let locks = clear_stale_git_locks(&target_dir)?;
+        if locks.cleared.is_empty() {
+            return Err(format!(
+                "Git pull failed because another git process holds a lock in {}. Wait for it to finish, or run clear_git_locks once it has exited.",
+                target_dir
+            ));
+        }
+        pull_output = run_git_pull(&target_dir, &update_ref).await?;
+    }
 
     if !pull_output.status.success() {
         return Err(format!(
@@ -317,27 +1777,196 @@ pub async fn pull_repo(target_dir: String) -> Result<String, String> {
             String::from_utf8_lossy(&pull_output.stderr)
         ));
     }
+    // The update may have changed the options webui.py accepts.
+    notify_if_launch_args_invalid(&app_handle, &target_dir).await;
 
     Ok("SUCCESS".to_string())
 }
 
-fn port_is_reachable(port: u16) -> bool {
-    let addr = SocketAddrV4::new(Ipv4Addr::LOCALHOST, port);
-    TcpStream::connect_timeout(&addr.into(), Duration::from_millis(200)).is_ok()
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReleaseTag {
+    pub name: String,
+    pub notes: String,
 }
 
-async fn ensure_port_closed(port: u16) -> Result<(), String> {
-    const MAX_ATTEMPTS: u8 = 5;
-    for _ in 0..MAX_ATTEMPTS {
-        if !port_is_reachable(port) {
-            return Ok(());
-        }
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateChangelog {
+    pub local_hash: String,
+    pub remote_hash: String,
+    pub compared_ref: String,
+    pub commits: Vec<String>,
+    pub tags: Vec<ReleaseTag>,
+}
 
-        force_kill_port(port).await?;
-        sleep(Duration::from_millis(300)).await;
+/// Runs a git subcommand inside `target_dir` and returns its trimmed stdout.
+async fn git_stdout(target_dir: &str, args: &[&str]) -> Result<String, String> {
+    let output = new_command("git")
+        .arg("-C")
+        .arg(target_dir)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute git {}: {}", args.join(" "), e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        ));
     }
 
-    if port_is_reachable(port) {
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_update_changelog(
+    target_dir: String,
+    branch: Option<String>,
+) -> Result<UpdateChangelog, String> {
+    let target_dir = clean_path_arg(&target_dir);
+    let repo_path = Path::new(&target_dir);
+    if !repo_path.exists() || !repo_path.is_dir() {
+        return Err("Repository directory does not exist.".to_string());
+    }
+    if is_archive_checkout(&target_dir) {
+        return Err(archive_update_unsupported(&target_dir));
+    }
+
+    // Shallow clones don't have the history needed to diff against the remote.
+    let is_shallow = git_stdout(&target_dir, &["rev-parse", "--is-shallow-repository"]).await?;
+    if is_shallow == "true" {
+        git_stdout(&target_dir, &["fetch", "--deepen=200", "--tags"]).await?;
+    } else {
+        git_stdout(&target_dir, &["fetch", "--tags"]).await?;
+    }
+
+    let local_hash = git_stdout(&target_dir, &["rev-parse", "HEAD"]).await?;
+    let update_ref = resolve_update_ref(&target_dir, branch.as_deref()).await?;
+    // Peel annotated tags to the commit they point at.
+    let remote_commit = format!("{}^{{commit}}", update_ref.compare);
+    let remote_hash = git_stdout(&target_dir, &["rev-parse", &remote_commit]).await?;
+
+    let range = format!("{}..{}", local_hash, remote_hash);
+    let commits = git_stdout(&target_dir, &["log", &range, "--oneline"])
+        .await?
+        .lines()
+        .map(|line| line.to_string())
+        .collect();
+
+    let tag_names = git_stdout(
+        &target_dir,
+        &["tag", "--merged", &remote_hash, "--no-merged", &local_hash],
+    )
+    .await?;
+
+    let mut tags = Vec::new();
+    for name in tag_names
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+    {
+        let notes = git_stdout(&target_dir, &["tag", "-l", "--format=%(contents)", name])
+            .await
+            .unwrap_or_default();
+        tags.push(ReleaseTag {
+            name: name.to_string(),
+            notes,
+        });
+    }
+
+    Ok(UpdateChangelog {
+        local_hash,
+        remote_hash,
+        compared_ref: update_ref.compare,
+        commits,
+        tags,
+    })
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PortValidation {
+    pub host: String,
+    pub port: u16,
+    pub in_range: bool,
+    pub privileged: bool,
+    pub available: bool,
+    pub suggested_port: Option<u16>,
+    pub message: String,
+}
+
+impl PortValidation {
+    pub fn is_usable(&self) -> bool {
+        self.in_range && self.available
+    }
+}
+
+/// Try binding `host:port`; `Err` carries the kind of failure so callers can tell
+/// "permission denied" apart from "already in use".
+fn try_bind(host: &str, port: u16) -> Result<(), ErrorKind> {
+    TcpListener::bind((host, port))
+        .map(|_| ())
+        .map_err(|e| e.kind())
+}
+
+fn find_free_port(host: &str, start: u16) -> Option<u16> {
+    (start..=start.saturating_add(100))
+        .skip(1)
+        .find(|candidate| try_bind(host, *candidate).is_ok())
+}
+
+pub fn check_port(host: &str, port: u16) -> PortValidation {
+    let mut result = PortValidation {
+        host: host.to_string(),
+        port,
+        in_range: port != 0,
+        privileged: port != 0 && port < 1024,
+        available: false,
+        suggested_port: None,
+        message: String::new(),
+    };
+
+    if !result.in_range {
+        result.message =
+            "Port 0 lets the OS pick a random port; please choose a fixed port such as 7860."
+                .to_string();
+        return result;
+    }
+
+    match try_bind(host, port) {
+        Ok(()) => {
+            result.available = true;
+            result.message = format!("Port {} is available on {}.", port, host);
+        }
+        Err(ErrorKind::PermissionDenied) => {
+            result.suggested_port = find_free_port(host, 7859);
+            result.message = format!(
+                "Port {} is privileged and this app is not allowed to bind it. Use a port above 1023.",
+                port
+            );
+        }
+        Err(ErrorKind::AddrNotAvailable) => {
+            result.message = format!("Host '{}' is not an address of this machine.", host);
+        }
+        Err(_) => {
+            result.suggested_port = find_free_port(host, port);
+            result.message = match result.suggested_port {
+                Some(next) => format!("Port {} is already in use. Port {} is free.", port, next),
+                None => format!("Port {} is already in use.", port),
+            };
+        }
+    }
+
+    result
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn validate_port(host: String, port: u16) -> Result<PortValidation, String> {
+    Ok(check_port(&host, port))
+}
+
+fn check_port_closed(host: &str, port: u16) -> Result<(), String> {
+    if accepts_connections(host, port) {
         Err(format!(
             "Port {} is still serving requests. Please close IndexTTS2 manually.",
             port
@@ -347,11 +1976,71 @@ async fn ensure_port_closed(port: u16) -> Result<(), String> {
     }
 }
 
+/// Makes sure nothing serves `host:port` after a stop, killing leftover server
+/// processes that still listen on it. `server_pid` is the server the app started.
+async fn ensure_port_closed(host: &str, port: u16, server_pid: Option<u32>) -> Result<(), String> {
+    const MAX_ATTEMPTS: u8 = 5;
+    for _ in 0..MAX_ATTEMPTS {
+        if !accepts_connections(host, port) {
+            return Ok(());
+        }
+
+        if force_kill_port(port, server_pid).await? == 0 {
+            // No process we can find owns the port, so killing again won't help.
+            #[cfg(windows)]
+            {
+                // Most likely a socket the OS has not torn down yet (TIME_WAIT), which
+                // frees itself without our help.
+                sleep(LINGERING_SOCKET_GRACE).await;
+            }
+            return check_port_closed(host, port);
+        }
+        sleep(Duration::from_millis(300)).await;
+    }
+
+    check_port_closed(host, port)
+}
+
+/// Whether `pid` may be killed to free the server port: the server the app started or
+/// one of its children, or an orphaned python/uv process.
+fn is_server_process(sys: &System, pid: u32, server_pid: Option<u32>) -> bool {
+    let pid = Pid::from_u32(pid);
+    if server_pid.is_some_and(|server| is_descendant_of(sys, pid, Pid::from_u32(server))) {
+        return true;
+    }
+    let Some(process) = sys.process(pid) else {
+        return false;
+    };
+    std::iter::once(process.name())
+        .chain(process.cmd().first().map(String::as_str))
+        .any(|path| {
+            let program = Path::new(path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            program.starts_with(PYTHON_PROGRAM_PREFIX) || program == UV_PROGRAM
+        })
+}
+
+/// Keeps the PIDs that belong to the server, so a foreign listener is never killed.
+fn server_pids(pids: Vec<&str>, server_pid: Option<u32>) -> Vec<&str> {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    pids.into_iter()
+        .filter(|pid| {
+            pid.parse()
+                .is_ok_and(|pid| is_server_process(&sys, pid, server_pid))
+        })
+        .collect()
+}
+
+/// Kills the server processes listening on `port` and returns how many there were.
 #[cfg(unix)]
-async fn force_kill_port(port: u16) -> Result<(), String> {
-    let port_spec = format!(":{}", port);
+async fn force_kill_port(port: u16, server_pid: Option<u32>) -> Result<usize, String> {
+    let port_spec = format!("-iTCP:{}", port);
+    // Listeners only: clients connected to the port (a browser tab) are not the server.
     let output = new_command("lsof")
-        .args(["-ti", &port_spec])
+        .args(["-t", "-nP", &port_spec, "-sTCP:LISTEN"])
         .output()
         .await
         .map_err(|e| {
@@ -362,15 +2051,19 @@ async fn force_kill_port(port: u16) -> Result<(), String> {
         })?;
 
     if output.stdout.is_empty() {
-        return Ok(());
+        return Ok(0);
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    for pid in stdout
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-    {
+    let pids = server_pids(
+        stdout
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect(),
+        server_pid,
+    );
+    for pid in &pids {
         let kill_output = new_command("kill")
             .args(["-9", pid])
             .output()
@@ -388,13 +2081,16 @@ async fn force_kill_port(port: u16) -> Result<(), String> {
         }
     }
 
-    Ok(())
+    Ok(pids.len())
 }
 
+/// Kills the live server processes listening on `port` and returns how many there were.
+/// Sockets left by a process that already exited report PID 0 or a PID that no longer
+/// exists; they are not an error and are skipped.
 #[cfg(windows)]
-async fn force_kill_port(port: u16) -> Result<(), String> {
+async fn force_kill_port(port: u16, server_pid: Option<u32>) -> Result<usize, String> {
     let script = format!(
-        "Get-NetTCPConnection -LocalPort {} -ErrorAction SilentlyContinue | Select-Object -ExpandProperty OwningProcess",
+        "Get-NetTCPConnection -LocalPort {} -State Listen -ErrorAction SilentlyContinue | Where-Object {{ $_.OwningProcess -gt 4 -and (Get-Process -Id $_.OwningProcess -ErrorAction SilentlyContinue) }} | Select-Object -ExpandProperty OwningProcess -Unique",
         port
     );
     let output = new_command("powershell")
@@ -409,20 +2105,23 @@ async fn force_kill_port(port: u16) -> Result<(), String> {
         })?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut killed_any = false;
-    for pid in stdout
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-    {
-        killed_any = true;
+    let pids = server_pids(
+        stdout
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect(),
+        server_pid,
+    );
+    for pid in &pids {
         let kill_output = new_command("taskkill")
             .args(["/PID", pid, "/F"])
             .output()
             .await
             .map_err(|e| format!("Failed to kill PID {} on port {}: {}", pid, port, e))?;
 
-        if !kill_output.status.success() {
+        // 128: the process exited between the query and the kill, so the port is free.
+        if !kill_output.status.success() && kill_output.status.code() != Some(TASKKILL_NOT_FOUND) {
             return Err(format!(
                 "taskkill failed for PID {} on port {}: {}",
                 pid,
@@ -432,14 +2131,10 @@ async fn force_kill_port(port: u16) -> Result<(), String> {
         }
     }
 
-    if !killed_any && stdout.trim().is_empty() {
-        return Ok(());
-    }
-
-    Ok(())
+    Ok(pids.len())
 }
 
 #[cfg(not(any(unix, windows)))]
-async fn force_kill_port(_port: u16) -> Result<(), String> {
+async fn force_kill_port(_port: u16, _server_pid: Option<u32>) -> Result<usize, String> {
     Err("Force killing ports is not supported on this platform.".to_string())
 }
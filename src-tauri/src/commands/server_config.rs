@@ -0,0 +1,235 @@
+// src-tauri/src/commands/server_config.rs
+
+use super::config_store::{load_json, save_json};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use sysinfo::System;
+use tauri::AppHandle;
+
+pub const SERVER_CONFIG_FILE: &str = "server_config.json";
+
+pub const SUPPORTED_DEVICES: [&str; 3] = ["cpu", "cuda", "mps"];
+pub const SUPPORTED_PRECISIONS: [&str; 2] = ["fp16", "fp32"];
+pub const SUPPORTED_LOG_LEVELS: [&str; 4] = ["debug", "info", "warning", "error"];
+pub const DEFAULT_SERVER_PORT: u16 = 7860;
+
+/// webui.py launch parameters; every field is optional so a preset can pin only
+/// the values it cares about.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LaunchSettings {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub device: Option<String>,
+    pub precision: Option<String>,
+    pub log_level: Option<String>,
+    pub cpu_threads: Option<usize>,
+    // NVIDIA GPU index (as listed by `get_gpu_usage`) to run on when device is "cuda".
+    pub gpu_index: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub presets: BTreeMap<String, LaunchSettings>,
+    // Used when neither the launch call nor the preset sets a thread count.
+    pub cpu_threads: Option<usize>,
+    // Ports freed on stop when the app did not start the running server itself (e.g.
+    // it was restarted in between); empty means the default port and every preset's.
+    pub stop_scan_ports: Vec<u16>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CpuThreadsInfo {
+    pub logical_cores: usize,
+    pub physical_cores: Option<usize>,
+    pub configured: Option<usize>,
+}
+
+pub fn load_server_config(app_handle: &AppHandle) -> ServerConfig {
+    load_json(app_handle, SERVER_CONFIG_FILE)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+pub fn save_server_config(app_handle: &AppHandle, config: &ServerConfig) -> Result<(), String> {
+    save_json(app_handle, SERVER_CONFIG_FILE, config)
+}
+
+pub fn validate_launch_settings(settings: &LaunchSettings) -> Result<(), String> {
+    if let Some(device) = &settings.device {
+        if !SUPPORTED_DEVICES.contains(&device.as_str()) {
+            return Err(format!(
+                "Unsupported device '{}'. Expected one of: {}.",
+                device,
+                SUPPORTED_DEVICES.join(", ")
+            ));
+        }
+    }
+    if let Some(precision) = &settings.precision {
+        if !SUPPORTED_PRECISIONS.contains(&precision.as_str()) {
+            return Err(format!(
+                "Unsupported precision '{}'. Expected one of: {}.",
+                precision,
+                SUPPORTED_PRECISIONS.join(", ")
+            ));
+        }
+    }
+    if let Some(level) = &settings.log_level {
+        if !SUPPORTED_LOG_LEVELS.contains(&level.as_str()) {
+            return Err(format!(
+                "Unsupported log level '{}'. Expected one of: {}.",
+                level,
+                SUPPORTED_LOG_LEVELS.join(", ")
+            ));
+        }
+    }
+    if settings.port == Some(0) {
+        return Err("Port 0 is not a valid launch port.".to_string());
+    }
+    if settings.cpu_threads == Some(0) {
+        return Err("CPU thread count must be at least 1.".to_string());
+    }
+    Ok(())
+}
+
+impl LaunchSettings {
+    /// Fills every unset field from `fallback`.
+    pub fn or(self, fallback: LaunchSettings) -> LaunchSettings {
+        LaunchSettings {
+            host: self.host.or(fallback.host),
+            port: self.port.or(fallback.port),
+            device: self.device.or(fallback.device),
+            precision: self.precision.or(fallback.precision),
+            log_level: self.log_level.or(fallback.log_level),
+            cpu_threads: self.cpu_threads.or(fallback.cpu_threads),
+            gpu_index: self.gpu_index.or(fallback.gpu_index),
+        }
+    }
+}
+
+/// Merges explicit launch arguments over the named preset, if any, and the saved
+/// CPU thread count.
+pub fn resolve_launch_settings(
+    app_handle: &AppHandle,
+    preset: Option<&str>,
+    explicit: LaunchSettings,
+) -> Result<LaunchSettings, String> {
+    let mut config = load_server_config(app_handle);
+    let saved = LaunchSettings {
+        cpu_threads: config.cpu_threads,
+        ..LaunchSettings::default()
+    };
+    let Some(name) = preset.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(explicit.or(saved));
+    };
+    let stored = config
+        .presets
+        .remove(name)
+        .ok_or_else(|| format!("Launch preset '{}' does not exist.", name))?;
+    Ok(explicit.or(stored).or(saved))
+}
+
+/// Number of logical CPUs, the upper bound for `cpu_threads`.
+pub fn logical_core_count() -> usize {
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    sys.cpus().len().max(1)
+}
+
+#[tauri::command]
+pub fn get_cpu_threads(app_handle: AppHandle) -> Result<CpuThreadsInfo, String> {
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    Ok(CpuThreadsInfo {
+        logical_cores: sys.cpus().len().max(1),
+        physical_cores: sys.physical_core_count(),
+        configured: load_server_config(&app_handle).cpu_threads,
+    })
+}
+
+/// Saves the default CPU inference thread count; `None` goes back to using every core.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_cpu_threads(
+    app_handle: AppHandle,
+    cpu_threads: Option<usize>,
+) -> Result<CpuThreadsInfo, String> {
+    if cpu_threads == Some(0) {
+        return Err("CPU thread count must be at least 1.".to_string());
+    }
+    let mut config = load_server_config(&app_handle);
+    config.cpu_threads = cpu_threads.map(|threads| threads.min(logical_core_count()));
+    save_server_config(&app_handle, &config)?;
+    get_cpu_threads(app_handle)
+}
+
+/// Ports to free after a stop when the launch port of the server is not known.
+pub fn stop_scan_ports(config: &ServerConfig) -> Vec<u16> {
+    let mut ports = if config.stop_scan_ports.is_empty() {
+        std::iter::once(DEFAULT_SERVER_PORT)
+            .chain(config.presets.values().filter_map(|preset| preset.port))
+            .collect()
+    } else {
+        config.stop_scan_ports.clone()
+    };
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+#[tauri::command]
+pub fn get_stop_scan_ports(app_handle: AppHandle) -> Result<Vec<u16>, String> {
+    Ok(stop_scan_ports(&load_server_config(&app_handle)))
+}
+
+/// Saves the ports checked on stop when the server's port is unknown; an empty list
+/// goes back to the default port plus the preset ports.
+#[tauri::command]
+pub fn set_stop_scan_ports(app_handle: AppHandle, ports: Vec<u16>) -> Result<Vec<u16>, String> {
+    if ports.contains(&0) {
+        return Err("Port 0 cannot be scanned.".to_string());
+    }
+    let mut config = load_server_config(&app_handle);
+    config.stop_scan_ports = ports;
+    save_server_config(&app_handle, &config)?;
+    get_stop_scan_ports(app_handle)
+}
+
+#[tauri::command]
+pub fn list_launch_presets(
+    app_handle: AppHandle,
+) -> Result<BTreeMap<String, LaunchSettings>, String> {
+    Ok(load_server_config(&app_handle).presets)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn save_launch_preset(
+    app_handle: AppHandle,
+    name: String,
+    settings: LaunchSettings,
+) -> Result<BTreeMap<String, LaunchSettings>, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Preset name cannot be empty.".to_string());
+    }
+    validate_launch_settings(&settings)?;
+
+    let mut config = load_server_config(&app_handle);
+    config.presets.insert(name, settings);
+    save_server_config(&app_handle, &config)?;
+    Ok(config.presets)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn delete_launch_preset(
+    app_handle: AppHandle,
+    name: String,
+) -> Result<BTreeMap<String, LaunchSettings>, String> {
+    let mut config = load_server_config(&app_handle);
+    if config.presets.remove(name.trim()).is_none() {
+        return Err(format!("Launch preset '{}' does not exist.", name));
+    }
+    save_server_config(&app_handle, &config)?;
+    Ok(config.presets)
+}
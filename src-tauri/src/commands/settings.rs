@@ -0,0 +1,73 @@
+// src-tauri/src/commands/settings.rs
+
+use super::config_store::{load_json, save_json};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Persisted app-wide preferences that aren't tied to a single deployment.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AppSettings {
+    pub torch_build: Option<String>,
+    // Opt-in: emit deploy-finished / server-event for system notifications.
+    pub notifications_enabled: bool,
+    // Leave the web UI server running when the app exits instead of stopping it.
+    pub keep_server_on_exit: bool,
+    // Fork or mirror to deploy from instead of the official index-tts repository.
+    pub repo_url: Option<String>,
+    // Set HF_HUB_ENABLE_HF_TRANSFER for HuggingFace model downloads.
+    pub hf_transfer_enabled: bool,
+    // Opt-in background update check; `None` disables it.
+    pub update_check_interval_hours: Option<u64>,
+    pub last_update_check: Option<u64>,
+    // UV_CACHE_DIR for uv child processes; `None` keeps uv's default location.
+    pub uv_cache_dir: Option<String>,
+    // Parallel file downloads for the hf/modelscope CLI; `None` keeps the tool's default.
+    pub download_concurrency: Option<usize>,
+    // Overrides the recommended `lfs.concurrenttransfers` for new clones and optimizations.
+    pub lfs_concurrent_transfers: Option<usize>,
+    // Free space required before cloning or downloading the model; `None` uses the default.
+    pub min_free_disk_gb: Option<f64>,
+}
+
+/// Loads the settings file, falling back to defaults when it is missing or unreadable.
+pub fn load_settings(app_handle: &AppHandle) -> AppSettings {
+    load_json(app_handle, SETTINGS_FILE)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+pub fn save_settings(app_handle: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    save_json(app_handle, SETTINGS_FILE, settings)
+}
+
+#[tauri::command]
+pub fn get_settings(app_handle: AppHandle) -> Result<AppSettings, String> {
+    Ok(load_settings(&app_handle))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_keep_server_on_exit(app_handle: AppHandle, enabled: bool) -> Result<bool, String> {
+    let mut settings = load_settings(&app_handle);
+    settings.keep_server_on_exit = enabled;
+    save_settings(&app_handle, &settings)?;
+    Ok(enabled)
+}
+
+/// Sets the free space the clone and model download require; `None` restores the default.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_min_free_disk_gb(
+    app_handle: AppHandle,
+    min_free_disk_gb: Option<f64>,
+) -> Result<Option<f64>, String> {
+    if min_free_disk_gb.is_some_and(|gb| !gb.is_finite() || gb < 0.0) {
+        return Err("The free space threshold must be a non-negative number of GB.".to_string());
+    }
+    let mut settings = load_settings(&app_handle);
+    settings.min_free_disk_gb = min_free_disk_gb;
+    save_settings(&app_handle, &settings)?;
+    Ok(min_free_disk_gb)
+}
@@ -0,0 +1,196 @@
+// src-tauri/src/commands/step_cancel.rs
+
+use super::command_utils::new_command;
+use super::jobs::current_job_id;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use sysinfo::{Pid, System};
+use tauri::{AppHandle, Manager};
+
+// Helper steps that belong to a user-facing deploy step and are cancelled with it.
+const STEP_GROUPS: [(&str, &[&str]); 1] = [("download_model", &["install_model_tool"])];
+
+struct RunningStep {
+    step: String,
+    job_id: Option<String>,
+    // Start time as seen by the OS, to tell the process apart from a later one that
+    // reuses its PID.
+    started_at: Option<u64>,
+    cancelled: bool,
+}
+
+/// Processes started by `run_command_with_streaming`, keyed by PID, so a deploy step
+/// can be cancelled from another command.
+pub struct RunningSteps {
+    processes: Mutex<HashMap<u32, RunningStep>>,
+}
+
+impl RunningSteps {
+    pub fn new() -> Self {
+        RunningSteps {
+            processes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<u32, RunningStep>> {
+        self.processes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn register(&self, step: &str, pid: u32) {
+        self.lock().insert(
+            pid,
+            RunningStep {
+                step: step.to_string(),
+                job_id: current_job_id(),
+                started_at: process_start_time(pid),
+                cancelled: false,
+            },
+        );
+    }
+
+    /// Forgets the process and reports whether it was cancelled.
+    fn unregister(&self, pid: u32) -> bool {
+        self.lock()
+            .remove(&pid)
+            .is_some_and(|running| running.cancelled)
+    }
+
+    /// Marks the matching processes as cancelled and returns their PIDs with the start
+    /// time recorded for each.
+    fn mark_cancelled_where(
+        &self,
+        matches: impl Fn(&RunningStep) -> bool,
+    ) -> Vec<(u32, Option<u64>)> {
+        self.lock()
+            .iter_mut()
+            .filter(|(_, running)| matches(running))
+            .map(|(pid, running)| {
+                running.cancelled = true;
+                (*pid, running.started_at)
+            })
+            .collect()
+    }
+
+    /// Marks every process of `step` (and its helper steps) as cancelled.
+    fn mark_cancelled(&self, step: &str) -> Vec<(u32, Option<u64>)> {
+        let helpers = STEP_GROUPS
+            .iter()
+            .find(|(group, _)| *group == step)
+            .map(|(_, helpers)| *helpers)
+            .unwrap_or_default();
+        self.mark_cancelled_where(|running| {
+            running.step == step || helpers.contains(&running.step.as_str())
+        })
+    }
+}
+
+/// Keeps a spawned step process registered for `cancel_deploy_step` until it is
+/// finished or dropped, so a step whose future is aborted (a cancelled job, app exit)
+/// does not leave its PID behind for a later cancel to kill.
+pub struct StepProcessGuard {
+    app_handle: AppHandle,
+    pid: Option<u32>,
+}
+
+impl StepProcessGuard {
+    /// Stops tracking the process and returns whether it ended because it was cancelled.
+    pub fn finish(mut self) -> bool {
+        self.unregister()
+    }
+
+    fn unregister(&mut self) -> bool {
+        match (self.app_handle.try_state::<RunningSteps>(), self.pid.take()) {
+            (Some(steps), Some(pid)) => steps.unregister(pid),
+            _ => false,
+        }
+    }
+}
+
+impl Drop for StepProcessGuard {
+    fn drop(&mut self) {
+        self.unregister();
+    }
+}
+
+/// Records a spawned step process until the returned guard is finished or dropped.
+pub fn track_step_process(
+    app_handle: &AppHandle,
+    step: &str,
+    pid: Option<u32>,
+) -> StepProcessGuard {
+    let steps = app_handle.try_state::<RunningSteps>();
+    if let (Some(steps), Some(pid)) = (steps, pid) {
+        steps.register(step, pid);
+    }
+    StepProcessGuard {
+        app_handle: app_handle.clone(),
+        pid,
+    }
+}
+
+fn process_start_time(pid: u32) -> Option<u64> {
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_process(pid);
+    sys.process(pid).map(|process| process.start_time())
+}
+
+/// Whether `pid` still belongs to the process that was registered, and not to an
+/// unrelated one that got the PID after it exited.
+fn is_same_process(pid: u32, started_at: Option<u64>) -> bool {
+    match (process_start_time(pid), started_at) {
+        (Some(now), Some(then)) => now == then,
+        // Registered without a start time: only require that something is still there.
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Kills `pid` with everything it started: git-lfs under git, python and pip under uv.
+async fn kill_process_tree(pid: u32) -> bool {
+    // Step processes run in their own process group, so a negative PID reaches all of it.
+    #[cfg(unix)]
+    let output = new_command("kill")
+        .args(["-9", "--", &format!("-{}", pid)])
+        .output()
+        .await;
+
+    #[cfg(windows)]
+    let output = new_command("taskkill")
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .output()
+        .await;
+
+    output.is_ok_and(|output| output.status.success())
+}
+
+async fn kill_marked(processes: Vec<(u32, Option<u64>)>) -> bool {
+    let mut killed = false;
+    for (pid, started_at) in processes {
+        if is_same_process(pid, started_at) {
+            killed |= kill_process_tree(pid).await;
+        }
+    }
+    killed
+}
+
+/// Kills the process trees of every step running under `job_id`; the job's future is
+/// then aborted by the caller. Returns whether any process was killed.
+pub async fn cancel_job_processes(app_handle: &AppHandle, job_id: &str) -> bool {
+    let Some(steps) = app_handle.try_state::<RunningSteps>() else {
+        return false;
+    };
+    let processes = steps.mark_cancelled_where(|running| running.job_id.as_deref() == Some(job_id));
+    kill_marked(processes).await
+}
+
+/// Aborts a running deploy step (`clone_repo`, `init_lfs`, `setup_env`,
+/// `download_model`, ...) by killing its process tree. The step then fails with a
+/// "was cancelled" error. Returns whether any process was killed.
+#[tauri::command]
+pub async fn cancel_deploy_step(app_handle: AppHandle, step: String) -> Result<bool, String> {
+    let processes = app_handle
+        .state::<RunningSteps>()
+        .mark_cancelled(step.trim());
+    Ok(kill_marked(processes).await)
+}
@@ -0,0 +1,181 @@
+// src-tauri/src/commands/storage_media.rs
+
+use super::disk_bench::{benchmark_dir, DiskBenchmark};
+use super::system_info::volume_for_path;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+// Typical sustained read speeds in MB/s, used when no benchmark was run.
+const TYPICAL_READ_MB_S: [(MediaType, f64); 5] = [
+    (MediaType::Ssd, 500.0),
+    (MediaType::Hdd, 120.0),
+    (MediaType::Removable, 40.0),
+    (MediaType::Network, 60.0),
+    (MediaType::Unknown, 200.0),
+];
+// Loads slower than this are worth telling the user about.
+const SLOW_LOAD_SECS: f64 = 60.0;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaType {
+    Ssd,
+    Hdd,
+    Removable,
+    Network,
+    Unknown,
+}
+
+impl MediaType {
+    fn is_slow(self) -> bool {
+        matches!(
+            self,
+            MediaType::Hdd | MediaType::Removable | MediaType::Network
+        )
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            MediaType::Ssd => "an SSD",
+            MediaType::Hdd => "a hard disk",
+            MediaType::Removable => "a removable drive",
+            MediaType::Network => "a network share",
+            MediaType::Unknown => "an unknown drive",
+        }
+    }
+
+    fn typical_read_mb_s(self) -> f64 {
+        TYPICAL_READ_MB_S
+            .iter()
+            .find(|(media, _)| *media == self)
+            .map_or(200.0, |(_, speed)| *speed)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PathMedia {
+    pub path: String,
+    pub media: MediaType,
+    pub mount_point: Option<String>,
+    pub slow: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StorageMediaReport {
+    pub install: Option<PathMedia>,
+    pub model: Option<PathMedia>,
+    pub model_size_mb: Option<f64>,
+    pub estimated_load_secs: Option<f64>,
+    pub benchmark: Option<DiskBenchmark>,
+    pub warning: Option<String>,
+}
+
+pub fn media_for_path(path: &str) -> PathMedia {
+    let volume = volume_for_path(Path::new(path));
+    // UNC paths never match a local mount point.
+    let unc = path.starts_with("\\\\") || path.starts_with("//");
+    let media = match &volume {
+        _ if unc => MediaType::Network,
+        Some(volume) if volume.is_network => MediaType::Network,
+        Some(volume) if volume.is_removable => MediaType::Removable,
+        Some(volume) if volume.kind == "ssd" => MediaType::Ssd,
+        Some(volume) if volume.kind == "hdd" => MediaType::Hdd,
+        _ => MediaType::Unknown,
+    };
+    PathMedia {
+        path: path.to_string(),
+        media,
+        mount_point: volume.map(|volume| volume.mount_point),
+        slow: media.is_slow(),
+    }
+}
+
+pub fn dir_size_bytes(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size_bytes(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+pub struct ModelLoadEstimate {
+    pub media: PathMedia,
+    pub size_mb: f64,
+    pub load_secs: f64,
+    pub warning: Option<String>,
+}
+
+/// Rough time to read the model from its media, with a warning when it sits on slow
+/// media. `read_mb_s` overrides the typical speed for the media type.
+pub fn estimate_model_load(model_dir: &str, read_mb_s: Option<f64>) -> ModelLoadEstimate {
+    let media = media_for_path(model_dir);
+    let size_mb = dir_size_bytes(Path::new(model_dir)) as f64 / 1024.0 / 1024.0;
+    let speed = read_mb_s
+        .filter(|speed| *speed > 0.0)
+        .unwrap_or_else(|| media.media.typical_read_mb_s());
+    let load_secs = size_mb / speed;
+    let warning = (media.slow || load_secs > SLOW_LOAD_SECS).then(|| {
+        format!(
+            "The model ({:.1} GB) is on {}; expect about {:.0}s just to read it at launch. Move it to a local SSD for faster starts.",
+            size_mb / 1024.0,
+            media.media.label(),
+            load_secs
+        )
+    });
+    ModelLoadEstimate {
+        media,
+        size_mb,
+        load_secs,
+        warning,
+    }
+}
+
+/// Identifies the media (SSD, HDD, removable, network) under the install and model
+/// folders and warns when the model sits on slow media. With `benchmark`, the model
+/// volume is measured instead of assuming a typical speed.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_storage_media(
+    install_dir: Option<String>,
+    model_dir: Option<String>,
+    benchmark: Option<bool>,
+) -> Result<StorageMediaReport, String> {
+    let install = install_dir
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| media_for_path(&dir));
+    let Some(model_dir) = model_dir.filter(|dir| !dir.is_empty()) else {
+        return Ok(StorageMediaReport {
+            install,
+            model: None,
+            model_size_mb: None,
+            estimated_load_secs: None,
+            benchmark: None,
+            warning: None,
+        });
+    };
+
+    let disk = if benchmark.unwrap_or(false) {
+        Some(benchmark_dir(&model_dir, None).await?)
+    } else {
+        None
+    };
+    // The read pass hits a file that was just written and may be cached; sequential
+    // write speed is the more honest proxy for a cold model load.
+    let measured = disk.as_ref().map(|disk| disk.write_mb_s);
+    let estimate = estimate_model_load(&model_dir, measured);
+
+    Ok(StorageMediaReport {
+        install,
+        model: Some(estimate.media),
+        model_size_mb: Some(estimate.size_mb),
+        estimated_load_secs: Some(estimate.load_secs),
+        benchmark: disk,
+        warning: estimate.warning,
+    })
+}
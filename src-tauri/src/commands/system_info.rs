@@ -1,15 +1,71 @@
 // src-tauri/src/commands/system_info.rs
 
 use super::command_utils::new_command;
+use super::settings::load_settings;
 use serde::{Deserialize, Serialize};
-use sysinfo::{Disks, System};
+use std::path::{Path, PathBuf};
+use sysinfo::{Disk, DiskKind, Disks, System};
+use tauri::AppHandle;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+// The IndexTTS-2 checkpoints plus the Python environment that runs them.
+pub const DEFAULT_MIN_FREE_DISK_GB: f64 = 15.0;
+
+// Remote filesystems report whatever the server feels like, so they are left out of the
+// volume list and flagged when the target lives on one.
+const NETWORK_FILE_SYSTEMS: [&str; 13] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smbfs",
+    "smb2",
+    "smb3",
+    "sshfs",
+    "fuse.sshfs",
+    "afpfs",
+    "webdav",
+    "davfs",
+    "9p",
+    "ncpfs",
+];
+
+// Windows display adapters, the same list DXGI enumerates. `qwMemorySize` is the 64-bit
+// dedicated memory; Win32_VideoController.AdapterRAM wraps at 4 GB.
+const WINDOWS_ADAPTER_QUERY: &str = "Get-ItemProperty 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Class\\{4d36e968-e325-11ce-bfc1-08002be10318}\\0*' -ErrorAction SilentlyContinue | Select-Object DriverDesc,'HardwareInformation.qwMemorySize' | ConvertTo-Json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+    Apple,
+}
+
+impl GpuVendor {
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.contains("nvidia") || name.contains("geforce") || name.contains("quadro") {
+            Some(GpuVendor::Nvidia)
+        } else if name.contains("amd") || name.contains("radeon") {
+            Some(GpuVendor::Amd)
+        } else if name.contains("intel") {
+            Some(GpuVendor::Intel)
+        } else {
+            None
+        }
+    }
+}
 
 // Re-using GpuInfo from index_tts.rs to avoid duplication
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GpuInfo {
+    pub vendor: GpuVendor,
     pub has_cuda: bool,
     pub name: Option<String>,
+    // Total memory of the card
     pub vram_gb: Option<f64>,
+    // What other processes leave free; this is what decides whether the model fits
+    pub free_vram_gb: Option<f64>,
     pub recommended_fp16: bool,
 }
 
@@ -23,10 +79,374 @@ pub struct SystemInfo {
     pub total_disk_gb: f64,
     pub available_disk_gb: f64,
     pub gpu_info: Option<GpuInfo>, // Add GPU info here
+    pub volumes: Vec<VolumeInfo>,
+    pub target_volume: Option<String>,
+    pub disk_info_suspect: bool,
+    pub disk_warning: Option<String>,
+    pub linux_compat: Option<LinuxCompat>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinuxCompat {
+    pub glibc_version: Option<String>,
+    pub distro: Option<String>,
+    pub kernel_version: Option<String>,
+    pub required_glibc: String,
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VolumeInfo {
+    pub mount_point: String,
+    pub name: String,
+    pub file_system: String,
+    pub total_gb: f64,
+    pub available_gb: f64,
+    pub is_removable: bool,
+    pub is_network: bool,
+    // "ssd", "hdd" or "unknown", as reported by the OS.
+    pub kind: String,
+}
+
+impl VolumeInfo {
+    fn from_disk(disk: &Disk) -> Self {
+        let file_system = disk.file_system().to_string_lossy().to_string();
+        VolumeInfo {
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            name: disk.name().to_string_lossy().to_string(),
+            is_network: NETWORK_FILE_SYSTEMS.contains(&file_system.to_lowercase().as_str()),
+            file_system,
+            total_gb: disk.total_space() as f64 / BYTES_PER_GB,
+            available_gb: disk.available_space() as f64 / BYTES_PER_GB,
+            is_removable: disk.is_removable(),
+            kind: match disk.kind() {
+                DiskKind::SSD => "ssd",
+                DiskKind::HDD => "hdd",
+                DiskKind::Unknown(_) => "unknown",
+            }
+            .to_string(),
+        }
+    }
+
+    fn is_accessible(&self) -> bool {
+        !self.is_network && self.total_gb > 0.0 && self.available_gb <= self.total_gb
+    }
+}
+
+fn path_key(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    let text = text.strip_prefix(r"\\?\").unwrap_or(&text);
+    if cfg!(target_os = "windows") {
+        text.to_lowercase().replace('/', "\\")
+    } else {
+        text.to_string()
+    }
 }
 
-#[tauri::command]
-pub async fn get_system_info() -> Result<SystemInfo, String> {
+/// Resolves `path` (or its closest existing ancestor, for directories that are not
+/// created yet) through symlinks so it can be matched against mount points.
+fn resolve_existing(path: &Path) -> PathBuf {
+    let mut current = path.to_path_buf();
+    loop {
+        if let Ok(resolved) = current.canonicalize() {
+            return resolved;
+        }
+        if !current.pop() {
+            return path.to_path_buf();
+        }
+    }
+}
+
+/// Returns the volume holding `path`, i.e. the one with the longest matching mount point.
+pub fn volume_for_path(path: &Path) -> Option<VolumeInfo> {
+    let target = path_key(&resolve_existing(path));
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| {
+            let mount = path_key(disk.mount_point());
+            Path::new(&target).starts_with(Path::new(&mount))
+        })
+        .max_by_key(|disk| path_key(disk.mount_point()).len())
+        .map(VolumeInfo::from_disk)
+}
+
+/// Free space a clone or model download needs: the user's setting or the default.
+pub fn required_free_disk_gb(app_handle: &AppHandle) -> f64 {
+    load_settings(app_handle)
+        .min_free_disk_gb
+        .unwrap_or(DEFAULT_MIN_FREE_DISK_GB)
+}
+
+/// Fails when the volume holding `path` has less than `required_gb` free, so a download
+/// does not run out of space partway through. Volumes that cannot be identified, network
+/// shares and volumes reporting implausible sizes are let through.
+pub fn ensure_free_space(path: &Path, required_gb: f64, purpose: &str) -> Result<(), String> {
+    let Some(volume) = volume_for_path(path).filter(VolumeInfo::is_accessible) else {
+        return Ok(());
+    };
+    if volume.available_gb < required_gb {
+        return Err(format!(
+            "Not enough disk space for {}: {:.1} GB required, {:.1} GB available on {}. Free up space or choose another location.",
+            purpose, required_gb, volume.available_gb, volume.mount_point
+        ));
+    }
+    Ok(())
+}
+
+// Minimum glibc of the manylinux tag used by each torch wheel flavour. Older CUDA
+// flavours are only published for torch releases that still targeted manylinux2014.
+const TORCH_GLIBC_MINIMUMS: [(&str, &str); 2] = [("cu118", "2.17"), ("cu121", "2.17")];
+const DEFAULT_TORCH_GLIBC_MINIMUM: &str = "2.28";
+
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|part| part.parse::<u32>().unwrap_or(0))
+        .collect()
+}
+
+async fn detect_glibc_version() -> Option<String> {
+    let output = new_command("ldd").arg("--version").output().await.ok()?;
+    // musl's ldd prints its banner to stderr and exits non-zero; that is not glibc.
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().last())
+        .filter(|version| version.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .map(|version| version.to_string())
+}
+
+fn detect_distro() -> Option<String> {
+    let os_release = std::fs::read_to_string("/etc/os-release").ok()?;
+    os_release
+        .lines()
+        .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+        .map(|name| name.trim_matches('"').to_string())
+}
+
+/// Linux-only check that the system glibc is new enough for the torch wheels that
+/// `setup_index_tts_env` will install. Returns `None` on other platforms.
+pub async fn check_linux_compat(torch_build: Option<&str>) -> Option<LinuxCompat> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let required_glibc = torch_build
+        .and_then(|build| {
+            TORCH_GLIBC_MINIMUMS
+                .iter()
+                .find(|(name, _)| *name == build)
+                .map(|(_, minimum)| *minimum)
+        })
+        .unwrap_or(DEFAULT_TORCH_GLIBC_MINIMUM)
+        .to_string();
+    let glibc_version = detect_glibc_version().await;
+
+    let warning = match &glibc_version {
+        None => Some(
+            "Could not detect glibc (musl-based distributions are not supported by the torch wheels)."
+                .to_string(),
+        ),
+        Some(version) if parse_version(version) < parse_version(&required_glibc) => Some(format!(
+            "glibc {} is older than {} required by the {} torch build; upgrade the distribution.",
+            version,
+            required_glibc,
+            torch_build.unwrap_or("default")
+        )),
+        Some(_) => None,
+    };
+
+    Some(LinuxCompat {
+        glibc_version,
+        distro: detect_distro(),
+        kernel_version: System::kernel_version(),
+        required_glibc,
+        warning,
+    })
+}
+
+fn default_disk_target() -> PathBuf {
+    let home_var = if cfg!(target_os = "windows") {
+        "USERPROFILE"
+    } else {
+        "HOME"
+    };
+    std::env::var_os(home_var)
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("/"))
+}
+
+/// Half precision pays off once more than 8 GB is available to the model. Free memory
+/// is used when known, so a card shared with other processes is judged by what is left.
+pub fn recommend_fp16(vram_gb: Option<f64>, free_vram_gb: Option<f64>) -> bool {
+    free_vram_gb.or(vram_gb).is_some_and(|vram| vram > 8.0)
+}
+
+fn gpu_info(
+    vendor: GpuVendor,
+    name: Option<String>,
+    vram_gb: Option<f64>,
+    free_vram_gb: Option<f64>,
+) -> GpuInfo {
+    let has_cuda = vendor == GpuVendor::Nvidia;
+    GpuInfo {
+        vendor,
+        has_cuda,
+        name,
+        vram_gb,
+        free_vram_gb,
+        // The half-precision path of the engine is only exercised on CUDA.
+        recommended_fp16: has_cuda && recommend_fp16(vram_gb, free_vram_gb),
+    }
+}
+
+fn parse_mib(value: &str) -> Option<f64> {
+    value
+        .replace(" MiB", "")
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|mib| mib / 1024.0)
+}
+
+async fn detect_nvidia_gpu() -> Option<GpuInfo> {
+    let output = new_command("nvidia-smi")
+        .arg("--query-gpu=name,memory.total,memory.free,driver_version")
+        .arg("--format=csv,noheader")
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first = stdout.lines().next()?;
+    let parts: Vec<&str> = first.split(',').map(|s| s.trim()).collect();
+    if parts.len() < 2 {
+        return None;
+    }
+    // VRAM is reported in MiB
+    Some(gpu_info(
+        GpuVendor::Nvidia,
+        Some(parts[0].to_string()),
+        parse_mib(parts[1]),
+        parts.get(2).and_then(|free| parse_mib(free)),
+    ))
+}
+
+/// Reads the first card from `rocm-smi`, whose lines look like
+/// `GPU[0]  : Card series:  Radeon RX 7900 XTX` and `GPU[0]  : VRAM Total Memory (B): 25753026560`.
+fn parse_rocm_smi(stdout: &str) -> Option<GpuInfo> {
+    let mut name = None;
+    let mut vram_gb = None;
+    let mut used_gb = None;
+    for line in stdout.lines().filter(|line| line.starts_with("GPU[0]")) {
+        let Some((_, field)) = line.split_once(':') else {
+            continue;
+        };
+        let Some((key, value)) = field.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_lowercase(), value.trim());
+        if value.is_empty() {
+            continue;
+        }
+        if key == "card series" || (key == "card model" && name.is_none()) {
+            name = Some(value.to_string());
+        } else if key == "vram total memory (b)" {
+            vram_gb = value.parse::<f64>().ok().map(|bytes| bytes / BYTES_PER_GB);
+        } else if key == "vram total used memory (b)" {
+            used_gb = value.parse::<f64>().ok().map(|bytes| bytes / BYTES_PER_GB);
+        }
+    }
+    if name.is_none() && vram_gb.is_none() {
+        return None;
+    }
+    let free_vram_gb = vram_gb
+        .zip(used_gb)
+        .map(|(total, used)| (total - used).max(0.0));
+    Some(gpu_info(GpuVendor::Amd, name, vram_gb, free_vram_gb))
+}
+
+async fn detect_rocm_gpu() -> Option<GpuInfo> {
+    let output = new_command("rocm-smi")
+        .args(["--showproductname", "--showmeminfo", "vram"])
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+    parse_rocm_smi(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Picks the AMD or Intel adapter with the most dedicated memory; NVIDIA cards are left
+/// to `nvidia-smi` and software adapters have no known vendor.
+fn parse_windows_adapters(stdout: &str) -> Option<GpuInfo> {
+    let adapters: Vec<serde_json::Value> = serde_json::from_str(stdout)
+        .or_else(|_| serde_json::from_str(stdout).map(|adapter| vec![adapter]))
+        .unwrap_or_default();
+    adapters
+        .iter()
+        .filter_map(|adapter| {
+            let name = adapter.get("DriverDesc")?.as_str()?.trim().to_string();
+            let vendor = GpuVendor::from_name(&name).filter(|v| *v != GpuVendor::Nvidia)?;
+            let vram_gb = adapter
+                .get("HardwareInformation.qwMemorySize")
+                .and_then(|size| size.as_f64())
+                .map(|bytes| bytes / BYTES_PER_GB);
+            // The adapter class has no usage figures.
+            Some(gpu_info(vendor, Some(name), vram_gb, None))
+        })
+        .max_by(|a, b| {
+            a.vram_gb
+                .unwrap_or(0.0)
+                .total_cmp(&b.vram_gb.unwrap_or(0.0))
+        })
+}
+
+async fn detect_windows_gpu() -> Option<GpuInfo> {
+    let output = new_command("powershell")
+        .args(["-NoProfile", "-Command", WINDOWS_ADAPTER_QUERY])
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+    parse_windows_adapters(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// NVIDIA first since it is the only vendor with CUDA, then the platform's own tools.
+async fn detect_gpu(
+    cpu_brand: &str,
+    total_memory_gb: f64,
+    available_memory_gb: f64,
+) -> Option<GpuInfo> {
+    if let Some(info) = detect_nvidia_gpu().await {
+        return Some(info);
+    }
+    match std::env::consts::OS {
+        "linux" => detect_rocm_gpu().await,
+        "windows" => detect_windows_gpu().await,
+        // Apple silicon GPUs share the unified memory with the CPU.
+        "macos" if std::env::consts::ARCH == "aarch64" => Some(gpu_info(
+            GpuVendor::Apple,
+            Some(cpu_brand.to_string()),
+            Some(total_memory_gb),
+            Some(available_memory_gb),
+        )),
+        _ => None,
+    }
+}
+
+/// `target_dir` is the install/model location whose volume should be reported; the
+/// user's home directory is used when it is omitted.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_system_info(
+    app_handle: AppHandle,
+    target_dir: Option<String>,
+) -> Result<SystemInfo, String> {
     let mut sys = System::new_all();
     sys.refresh_all();
 
@@ -44,53 +464,42 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
     let total_memory_gb = sys.total_memory() as f64 / 1024.0 / 1024.0 / 1024.0;
     let available_memory_gb = sys.available_memory() as f64 / 1024.0 / 1024.0 / 1024.0;
 
-    // Disk information (sum up all disks, convert to GB)
+    // Disk information for the volume that holds the target, plus every local volume
     let disks = Disks::new_with_refreshed_list();
-    let mut total_disk_bytes: u64 = 0;
-    let mut available_disk_bytes: u64 = 0;
-    for disk in disks.list() {
-        total_disk_bytes += disk.total_space();
-        available_disk_bytes += disk.available_space();
-    }
-    let total_disk_gb = total_disk_bytes as f64 / 1024.0 / 1024.0 / 1024.0;
-    let available_disk_gb = available_disk_bytes as f64 / 1024.0 / 1024.0 / 1024.0;
+    let volumes: Vec<VolumeInfo> = disks
+        .list()
+        .iter()
+        .map(VolumeInfo::from_disk)
+        .filter(VolumeInfo::is_accessible)
+        .collect();
 
-    // GPU information (attempt to get using a dummy script or NVRM/DXGI directly)
-    // For simplicity, we'll try to detect NVIDIA cards and assume CUDA availability
-    let mut gpu_info: Option<GpuInfo> = None;
+    let disk_target = target_dir
+        .as_deref()
+        .map(PathBuf::from)
+        .unwrap_or_else(default_disk_target);
+    let target = volume_for_path(&disk_target);
+    let disk_warning = match &target {
+        None => Some(format!(
+            "Could not determine the volume for {}.",
+            disk_target.display()
+        )),
+        Some(volume) if volume.is_network => Some(format!(
+            "{} is on a network volume ({}); reported space may be inaccurate.",
+            disk_target.display(),
+            volume.file_system
+        )),
+        Some(volume) if !volume.is_accessible() => Some(format!(
+            "Volume {} reported implausible sizes (total {:.1} GB, available {:.1} GB).",
+            volume.mount_point, volume.total_gb, volume.available_gb
+        )),
+        Some(_) => None,
+    };
+    let total_disk_gb = target.as_ref().map_or(0.0, |volume| volume.total_gb);
+    let available_disk_gb = target.as_ref().map_or(0.0, |volume| volume.available_gb);
+    let target_volume = target.map(|volume| volume.mount_point);
 
-    // Attempt to detect NVIDIA GPU using nvidia-smi
-    let nvidia_smi_output = new_command("nvidia-smi")
-        .arg("--query-gpu=name,memory.total,driver_version")
-        .arg("--format=csv,noheader")
-        .output()
-        .await;
-
-    if let Ok(output) = nvidia_smi_output {
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let line = stdout.lines().next();
-            if let Some(l) = line {
-                let parts: Vec<&str> = l.split(',').map(|s| s.trim()).collect();
-                if parts.len() >= 2 {
-                    let name = Some(parts[0].to_string());
-                    // Extract VRAM in MiB, convert to GB
-                    let vram_mib_str = parts[1].replace(" MiB", "");
-                    let vram_gb = vram_mib_str.parse::<f64>().ok().map(|mib| mib / 1024.0);
-
-                    let has_cuda = true; // If nvidia-smi works, assume CUDA is available
-                    let recommended_fp16 = vram_gb.map_or(false, |vram| vram > 8.0);
-
-                    gpu_info = Some(GpuInfo {
-                        has_cuda,
-                        name,
-                        vram_gb,
-                        recommended_fp16,
-                    });
-                }
-            }
-        }
-    }
+    // GPU information: NVIDIA via nvidia-smi, then ROCm, Windows adapters or Apple silicon
+    let gpu_info = detect_gpu(&cpu_brand, total_memory_gb, available_memory_gb).await;
 
     Ok(SystemInfo {
         os,
@@ -101,5 +510,10 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
         total_disk_gb,
         available_disk_gb,
         gpu_info,
+        volumes,
+        target_volume,
+        disk_info_suspect: disk_warning.is_some(),
+        disk_warning,
+        linux_compat: check_linux_compat(load_settings(&app_handle).torch_build.as_deref()).await,
     })
 }
@@ -0,0 +1,165 @@
+// src-tauri/src/commands/tasks.rs
+
+use super::jobs::JobRegistry;
+use super::log_files::{append_log_line, APP_LOG};
+use super::server::shutdown_server;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+
+struct TrackedTask {
+    name: String,
+    handle: JoinHandle<()>,
+}
+
+/// Background tasks spawned by commands (log followers, server output readers, health
+/// probes). They are aborted together when the app exits so none of them outlives it
+/// or emits to a closed window.
+#[derive(Default)]
+pub struct BackgroundTasks {
+    shutting_down: AtomicBool,
+    next_id: AtomicU64,
+    tasks: Mutex<HashMap<u64, TrackedTask>>,
+}
+
+impl BackgroundTasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, TrackedTask>> {
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Registers the task `spawn` starts; it gets the id to unregister itself with.
+    fn track(&self, name: &str, spawn: impl FnOnce(u64) -> JoinHandle<()>) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        // Held across the spawn so a task that finishes immediately can't try to
+        // unregister itself before it has been registered.
+        let mut registry = self.lock();
+        let handle = spawn(id);
+        registry.insert(
+            id,
+            TrackedTask {
+                name: name.to_string(),
+                handle,
+            },
+        );
+    }
+
+    /// Aborts every tracked task and refuses new ones. Returns the names of the aborted tasks.
+    pub fn shutdown(&self) -> Vec<String> {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.lock()
+            .drain()
+            .map(|(_, task)| {
+                task.handle.abort();
+                task.name
+            })
+            .collect()
+    }
+}
+
+/// Spawns `task` on the async runtime and registers it for cancellation on shutdown.
+/// Finished tasks drop out of the registry on their own. Nothing is spawned once the
+/// app is shutting down.
+pub fn spawn_tracked<F>(app_handle: &AppHandle, name: &str, task: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let Some(tasks) = app_handle.try_state::<BackgroundTasks>() else {
+        tauri::async_runtime::spawn(task);
+        return;
+    };
+    if tasks.is_shutting_down() {
+        return;
+    }
+
+    let task_app = app_handle.clone();
+    tasks.track(name, |id| {
+        tauri::async_runtime::spawn(async move {
+            task.await;
+            if let Some(tasks) = task_app.try_state::<BackgroundTasks>() {
+                tasks.lock().remove(&id);
+            }
+        })
+    });
+}
+
+/// Called from the exit handler: cancels background tasks and running jobs, then stops
+/// or detaches the managed server according to the user's settings.
+pub fn shutdown_background_tasks(app_handle: &AppHandle) {
+    if let Some(tasks) = app_handle.try_state::<BackgroundTasks>() {
+        let aborted = tasks.shutdown();
+        if !aborted.is_empty() {
+            append_log_line(
+                app_handle,
+                APP_LOG,
+                "shutdown",
+                &format!("Stopped background tasks: {}", aborted.join(", ")),
+            );
+        }
+    }
+    if let Some(jobs) = app_handle.try_state::<JobRegistry>() {
+        jobs.abort_all();
+    }
+    shutdown_server(app_handle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    // Reports on the channel when the task owning it is dropped, i.e. has terminated.
+    struct DropSignal(mpsc::Sender<String>, String);
+
+    impl Drop for DropSignal {
+        fn drop(&mut self) {
+            let _ = self.0.send(self.1.clone());
+        }
+    }
+
+    #[test]
+    fn shutdown_terminates_every_task() {
+        let tasks = BackgroundTasks::new();
+        let (sender, receiver) = mpsc::channel();
+        let names = ["log_follower", "server_output", "health_probe"];
+        for name in names {
+            let signal = DropSignal(sender.clone(), name.to_string());
+            tasks.track(name, |_| {
+                tauri::async_runtime::spawn(async move {
+                    let _signal = signal;
+                    std::future::pending::<()>().await;
+                })
+            });
+        }
+
+        let mut aborted = tasks.shutdown();
+        aborted.sort();
+        assert_eq!(aborted, ["health_probe", "log_follower", "server_output"]);
+        assert!(tasks.is_shutting_down());
+        assert!(tasks.lock().is_empty());
+
+        let mut terminated: Vec<String> = (0..names.len())
+            .map(|_| {
+                receiver
+                    .recv_timeout(Duration::from_secs(5))
+                    .expect("task still running after shutdown")
+            })
+            .collect();
+        terminated.sort();
+        assert_eq!(
+            terminated,
+            ["health_probe", "log_follower", "server_output"]
+        );
+    }
+}
@@ -10,6 +10,26 @@ pub struct ToolStatus {
     pub python_installed: bool,
     pub uv_installed: bool,
     pub cuda_toolkit_installed: bool,
+    pub ffmpeg_installed: bool,
+}
+
+// Export formats and the ffmpeg audio encoders that can produce them, in order of preference.
+const EXPORT_FORMAT_ENCODERS: [(&str, &[&str]); 5] = [
+    ("mp3", &["libmp3lame", "mp3"]),
+    ("flac", &["flac"]),
+    ("ogg", &["libvorbis", "vorbis", "libopus"]),
+    ("opus", &["libopus", "opus"]),
+    ("m4a", &["aac", "aac_at", "libfdk_aac"]),
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FfmpegStatus {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub audio_encoders: Vec<String>,
+    /// Output formats `synthesize` can offer; wav needs no ffmpeg and is always listed.
+    pub supported_formats: Vec<String>,
+    pub install_hint: Option<String>,
 }
 
 /// Checks if a command exists and runs successfully with a --version flag.
@@ -41,5 +61,74 @@ pub async fn check_tools() -> Result<ToolStatus, String> {
         python_installed,
         uv_installed,
         cuda_toolkit_installed,
+        ffmpeg_installed: check_command("ffmpeg", "-version").await,
+    })
+}
+
+fn ffmpeg_install_hint() -> String {
+    match std::env::consts::OS {
+        "windows" => "ffmpeg was not found. Install it with `winget install --id Gyan.FFmpeg -e` or download it from https://ffmpeg.org/download.html and add it to PATH.".to_string(),
+        "macos" => "ffmpeg was not found. Install it with `brew install ffmpeg`.".to_string(),
+        _ => "ffmpeg was not found. Install it with your package manager, e.g. `sudo apt install ffmpeg` or `sudo dnf install ffmpeg`.".to_string(),
+    }
+}
+
+/// Parses the audio rows of `ffmpeg -encoders`, e.g. ` A....D libmp3lame  libmp3lame MP3`.
+fn parse_audio_encoders(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let flags = parts.next()?;
+            let name = parts.next()?;
+            (flags.len() == 6 && flags.starts_with('A')).then(|| name.to_string())
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_ffmpeg_status() -> Result<FfmpegStatus, String> {
+    let version_output = new_command("ffmpeg").arg("-version").output().await;
+    let version = match version_output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("ffmpeg version "))
+            .and_then(|rest| rest.split_whitespace().next())
+            .map(str::to_string),
+        _ => {
+            return Ok(FfmpegStatus {
+                installed: false,
+                version: None,
+                audio_encoders: Vec::new(),
+                supported_formats: vec!["wav".to_string()],
+                install_hint: Some(ffmpeg_install_hint()),
+            });
+        }
+    };
+
+    let audio_encoders = new_command("ffmpeg")
+        .args(["-hide_banner", "-encoders"])
+        .output()
+        .await
+        .map(|output| parse_audio_encoders(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default();
+
+    let mut supported_formats = vec!["wav".to_string()];
+    for (format, encoders) in EXPORT_FORMAT_ENCODERS {
+        if encoders
+            .iter()
+            .any(|encoder| audio_encoders.iter().any(|name| name == encoder))
+        {
+            supported_formats.push(format.to_string());
+        }
+    }
+
+    Ok(FfmpegStatus {
+        installed: true,
+        version,
+        audio_encoders,
+        supported_formats,
+        install_hint: None,
     })
 }
@@ -0,0 +1,149 @@
+// src-tauri/src/commands/torch_build.rs
+
+use super::command_utils::new_command;
+use super::settings::{load_settings, save_settings};
+use serde::Serialize;
+use tauri::AppHandle;
+
+// index-tts declares its torch wheels under this uv index name; passing the same name on
+// the command line replaces the URL from pyproject.toml.
+pub const TORCH_INDEX_NAME: &str = "pytorch-cuda";
+const TORCH_INDEX_BASE_URL: &str = "https://download.pytorch.org/whl";
+
+// Minimum NVIDIA driver major version for each CUDA wheel flavour, newest first.
+const CUDA_DRIVER_MINIMUMS: [(&str, u32); 5] = [
+    ("cu128", 570),
+    ("cu126", 560),
+    ("cu124", 550),
+    ("cu121", 525),
+    ("cu118", 520),
+];
+
+#[derive(Debug, Serialize)]
+pub struct TorchBuildSuggestion {
+    pub driver_version: Option<String>,
+    pub suggested: String,
+    pub current: Option<String>,
+    pub reason: String,
+}
+
+/// Normalizes user input such as "12.1", "cu121" or "CPU" into a wheel tag.
+pub fn normalize_torch_build(value: &str) -> Result<String, String> {
+    let value = value.trim().to_lowercase();
+    if value == "cpu" {
+        return Ok(value);
+    }
+
+    let digits: String = value
+        .trim_start_matches("cu")
+        .chars()
+        .filter(|c| *c != '.')
+        .collect();
+    if digits.len() == 3 && digits.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(format!("cu{}", digits));
+    }
+
+    Err(format!(
+        "Unsupported torch build '{}'. Use \"cpu\" or a CUDA version such as \"12.1\".",
+        value
+    ))
+}
+
+pub fn torch_index_url(build: &str) -> String {
+    format!("{}/{}", TORCH_INDEX_BASE_URL, build)
+}
+
+pub async fn query_nvidia_driver_version() -> Option<String> {
+    let output = new_command("nvidia-smi")
+        .args(["--query-gpu=driver_version", "--format=csv,noheader"])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+fn driver_major(version: &str) -> u32 {
+    version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok())
+        .unwrap_or(0)
+}
+
+/// Whether `driver_version` meets the minimum for a CUDA wheel flavour. CPU builds and
+/// unknown flavours always fit.
+pub fn driver_supports_build(driver_version: &str, build: &str) -> bool {
+    CUDA_DRIVER_MINIMUMS
+        .iter()
+        .find(|(flavour, _)| *flavour == build)
+        .is_none_or(|(_, minimum)| driver_major(driver_version) >= *minimum)
+}
+
+pub fn suggest_for_driver(driver_version: Option<&str>) -> (String, String) {
+    let Some(version) = driver_version else {
+        return (
+            "cpu".to_string(),
+            "No NVIDIA driver detected; the CPU build is the safe choice.".to_string(),
+        );
+    };
+
+    let major = driver_major(version);
+
+    match CUDA_DRIVER_MINIMUMS
+        .iter()
+        .find(|(_, minimum)| major >= *minimum)
+    {
+        Some((build, minimum)) => (
+            build.to_string(),
+            format!(
+                "Driver {} supports {} (requires >= {}).",
+                version, build, minimum
+            ),
+        ),
+        None => (
+            "cpu".to_string(),
+            format!(
+                "Driver {} is too old for the CUDA wheels; update the driver or use the CPU build.",
+                version
+            ),
+        ),
+    }
+}
+
+#[tauri::command]
+pub async fn suggest_torch_build(app_handle: AppHandle) -> Result<TorchBuildSuggestion, String> {
+    let driver_version = query_nvidia_driver_version().await;
+    let (suggested, reason) = suggest_for_driver(driver_version.as_deref());
+
+    Ok(TorchBuildSuggestion {
+        driver_version,
+        suggested,
+        current: load_settings(&app_handle).torch_build,
+        reason,
+    })
+}
+
+/// Pins the torch wheel flavour used by `setup_index_tts_env`; `None` restores the
+/// repository's default index.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_torch_build(
+    app_handle: AppHandle,
+    torch_build: Option<String>,
+) -> Result<Option<String>, String> {
+    let normalized = torch_build
+        .as_deref()
+        .map(normalize_torch_build)
+        .transpose()?;
+
+    let mut settings = load_settings(&app_handle);
+    settings.torch_build = normalized.clone();
+    save_settings(&app_handle, &settings)?;
+    Ok(normalized)
+}
@@ -0,0 +1,236 @@
+// src-tauri/src/commands/tuning.rs
+
+use super::disk_bench::{benchmark_dir, DiskBenchmark};
+use super::download_prewarm::prewarm_download;
+use super::fd_limits::check_fd_limit;
+use super::git_settings::saved_lfs_concurrency;
+use super::server_config::{load_server_config, logical_core_count, save_server_config};
+use super::settings::{load_settings, save_settings};
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+use tauri::AppHandle;
+
+// Small enough to finish in a few seconds, large enough to get past the write cache.
+const TUNING_BENCH_SIZE_MB: u64 = 64;
+// Below this much per-second throughput more connections only add contention.
+const SLOW_LINK_MB_S: f64 = 2.0;
+// Above this a handful of connections can't keep the link busy.
+const FAST_LINK_MB_S: f64 = 20.0;
+// Sequential writes below this are typical of spinning disks, where parallel writers seek.
+const SLOW_DISK_WRITE_MB_S: f64 = 80.0;
+const MAX_CONCURRENCY: usize = 16;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SettingRecommendation {
+    pub value: usize,
+    // What is configured now; `None` means the built-in default.
+    pub current: Option<usize>,
+    pub rationale: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SettingsRecommendation {
+    pub download_concurrency: SettingRecommendation,
+    pub lfs_concurrent_transfers: SettingRecommendation,
+    pub cpu_threads: SettingRecommendation,
+    pub logical_cores: usize,
+    pub physical_cores: Option<usize>,
+    pub throughput_mb_s: Option<f64>,
+    pub disk: Option<DiskBenchmark>,
+    // Measurements that failed; the matching recommendations fall back to safe defaults.
+    pub warnings: Vec<String>,
+}
+
+/// The values the user accepted, as passed back to `apply_recommended_settings`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TuningValues {
+    pub download_concurrency: Option<usize>,
+    pub lfs_concurrent_transfers: Option<usize>,
+    pub cpu_threads: Option<usize>,
+}
+
+/// Parallel transfers for the measured link and disk, before per-setting limits.
+fn transfer_concurrency(
+    throughput_mb_s: Option<f64>,
+    disk_write_mb_s: Option<f64>,
+) -> (usize, String) {
+    if let Some(write) = disk_write_mb_s.filter(|write| *write < SLOW_DISK_WRITE_MB_S) {
+        return (
+            4,
+            format!(
+                "The disk writes at {:.0} MB/s, which looks like a hard disk or network share; more parallel writers would mostly add seeking.",
+                write
+            ),
+        );
+    }
+    match throughput_mb_s {
+        None => (
+            8,
+            "The network could not be measured, so the standard value is kept.".to_string(),
+        ),
+        Some(speed) if speed < SLOW_LINK_MB_S => (
+            4,
+            format!(
+                "The link measured {:.1} MB/s; a few connections already fill it and fewer stall on timeouts.",
+                speed
+            ),
+        ),
+        Some(speed) if speed >= FAST_LINK_MB_S => (
+            MAX_CONCURRENCY,
+            format!(
+                "The link measured {:.1} MB/s and the disk keeps up, so more connections help saturate it.",
+                speed
+            ),
+        ),
+        Some(speed) => (
+            8,
+            format!(
+                "The link measured {:.1} MB/s; a moderate number of connections fills it.",
+                speed
+            ),
+        ),
+    }
+}
+
+fn recommend_cpu_threads(logical: usize, physical: Option<usize>) -> (usize, String) {
+    let physical = physical.unwrap_or_else(|| (logical / 2).max(1));
+    // Keep one core free for the app and the desktop on machines that can spare it.
+    let threads = if physical > 4 { physical - 1 } else { physical };
+    (
+        threads.max(1),
+        format!(
+            "{} physical of {} logical cores: inference does not gain from hyper-threads{}.",
+            physical,
+            logical,
+            if threads < physical {
+                ", and one core is left free so the UI stays responsive"
+            } else {
+                ""
+            }
+        ),
+    )
+}
+
+/// Recommends download concurrency, `lfs.concurrenttransfers` and CPU inference threads
+/// for this machine from the core count, a short disk benchmark of `target_dir` and a
+/// download probe against the model source. Nothing is changed until the values are
+/// passed to `apply_recommended_settings`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn recommend_settings(
+    app_handle: AppHandle,
+    target_dir: String,
+    model_source: Option<String>,
+    network_environment: Option<String>,
+) -> Result<SettingsRecommendation, String> {
+    let mut warnings = Vec::new();
+
+    let disk = match benchmark_dir(&target_dir, Some(TUNING_BENCH_SIZE_MB)).await {
+        Ok(disk) => Some(disk),
+        Err(e) => {
+            warnings.push(format!("Disk benchmark failed: {}", e));
+            None
+        }
+    };
+    let model_source = model_source.unwrap_or_else(|| "huggingface".to_string());
+    let throughput_mb_s = match prewarm_download(model_source, None, network_environment).await {
+        Ok(report) if report.throughput_mb_s.is_some() => report.throughput_mb_s,
+        Ok(report) => {
+            warnings.push(format!(
+                "Download probe failed: {}",
+                report.error.unwrap_or_default()
+            ));
+            None
+        }
+        Err(e) => {
+            warnings.push(format!("Download probe failed: {}", e));
+            None
+        }
+    };
+
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    let logical_cores = sys.cpus().len().max(1);
+    let physical_cores = sys.physical_core_count();
+
+    let (transfers, transfer_reason) =
+        transfer_concurrency(throughput_mb_s, disk.as_ref().map(|disk| disk.write_mb_s));
+
+    // Each hf worker is a thread; beyond two per core they just wait on each other.
+    let download_concurrency = transfers.min(logical_cores * 2).max(1);
+    let mut lfs_transfers = transfers;
+    let mut lfs_reason = transfer_reason.clone();
+    // Every LFS transfer holds several descriptors; stay within the open-file limit.
+    while lfs_transfers > 1 && !check_fd_limit(Some(lfs_transfers as u64), false).sufficient {
+        lfs_transfers /= 2;
+    }
+    if lfs_transfers < transfers {
+        lfs_reason = format!(
+            "{} Lowered to {} to stay within the open-file limit.",
+            transfer_reason, lfs_transfers
+        );
+    }
+    let (cpu_threads, cpu_reason) = recommend_cpu_threads(logical_cores, physical_cores);
+
+    let settings = load_settings(&app_handle);
+    Ok(SettingsRecommendation {
+        download_concurrency: SettingRecommendation {
+            value: download_concurrency,
+            current: settings.download_concurrency,
+            rationale: transfer_reason,
+        },
+        lfs_concurrent_transfers: SettingRecommendation {
+            value: lfs_transfers,
+            current: saved_lfs_concurrency(&app_handle),
+            rationale: lfs_reason,
+        },
+        cpu_threads: SettingRecommendation {
+            value: cpu_threads,
+            current: load_server_config(&app_handle).cpu_threads,
+            rationale: cpu_reason,
+        },
+        logical_cores,
+        physical_cores,
+        throughput_mb_s,
+        disk,
+        warnings,
+    })
+}
+
+/// Saves the accepted recommendations as defaults. Unset fields are left as they are.
+#[tauri::command]
+pub fn apply_recommended_settings(
+    app_handle: AppHandle,
+    values: TuningValues,
+) -> Result<TuningValues, String> {
+    if [
+        values.download_concurrency,
+        values.lfs_concurrent_transfers,
+        values.cpu_threads,
+    ]
+    .contains(&Some(0))
+    {
+        return Err("Recommended values must be at least 1.".to_string());
+    }
+
+    let mut settings = load_settings(&app_handle);
+    if let Some(concurrency) = values.download_concurrency {
+        settings.download_concurrency = Some(concurrency.min(MAX_CONCURRENCY));
+    }
+    if let Some(transfers) = values.lfs_concurrent_transfers {
+        settings.lfs_concurrent_transfers = Some(transfers.min(MAX_CONCURRENCY));
+    }
+    save_settings(&app_handle, &settings)?;
+
+    let mut config = load_server_config(&app_handle);
+    if let Some(threads) = values.cpu_threads {
+        config.cpu_threads = Some(threads.min(logical_core_count()));
+        save_server_config(&app_handle, &config)?;
+    }
+
+    Ok(TuningValues {
+        download_concurrency: settings.download_concurrency,
+        lfs_concurrent_transfers: settings.lfs_concurrent_transfers,
+        cpu_threads: config.cpu_threads,
+    })
+}
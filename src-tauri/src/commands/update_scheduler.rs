@@ -0,0 +1,178 @@
+// src-tauri/src/commands/update_scheduler.rs
+
+use super::deployments::unix_timestamp;
+use super::dir_lock::{acquire_dir_lock, DirLock};
+use super::engine::EngineManager;
+use super::server::{check_repo_update, ServerChildProcess};
+use super::settings::{load_settings, save_settings};
+use super::tasks::spawn_tracked;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const UPDATE_AVAILABLE_EVENT: &str = "update-available";
+const MIN_INTERVAL_HOURS: u64 = 1;
+// How often the loop wakes to notice a changed schedule or a due check.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateAvailableEvent {
+    pub target_dir: String,
+    pub local_hash: String,
+    pub remote_hash: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateSchedule {
+    pub enabled: bool,
+    pub interval_hours: Option<u64>,
+    pub last_checked_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+/// Runs the opt-in periodic `check_repo_update` for the active deployment. Changing the
+/// schedule bumps the generation, which ends the old loop.
+#[derive(Default)]
+pub struct UpdateScheduler {
+    generation: AtomicU64,
+    last_error: Mutex<Option<String>>,
+    // Remote commit already announced, so the event fires once per upstream advance.
+    notified_hash: Mutex<Option<String>>,
+}
+
+impl UpdateScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn set_last_error(&self, error: Option<String>) {
+        *self.last_error.lock().unwrap_or_else(|e| e.into_inner()) = error;
+    }
+}
+
+fn schedule(app_handle: &AppHandle) -> UpdateSchedule {
+    let settings = load_settings(app_handle);
+    UpdateSchedule {
+        enabled: settings.update_check_interval_hours.is_some(),
+        interval_hours: settings.update_check_interval_hours,
+        last_checked_at: settings.last_update_check,
+        last_error: app_handle
+            .state::<UpdateScheduler>()
+            .last_error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone(),
+    }
+}
+
+/// Locks the active deployment for the check. Returns `None` (skip this round) while a
+/// deploy step or a server start/stop owns it. A running server keeps its directory lock
+/// for its whole lifetime, so that case checks without taking the lock.
+fn claim_deployment(app_handle: &AppHandle, target_dir: &str) -> Option<Option<DirLock>> {
+    let server = app_handle.state::<ServerChildProcess>();
+    let server_running = server.lock().is_some();
+    if server_running {
+        return server.is_ready().then_some(None);
+    }
+    acquire_dir_lock(target_dir, "update_check").ok().map(Some)
+}
+
+async fn run_scheduled_check(app_handle: &AppHandle) {
+    let target_dir = app_handle
+        .state::<EngineManager>()
+        .lock()
+        .install_dir
+        .clone();
+    if target_dir.is_empty() {
+        return;
+    }
+    let Some(_dir_lock) = claim_deployment(app_handle, &target_dir) else {
+        return;
+    };
+    let scheduler = app_handle.state::<UpdateScheduler>();
+    let result = check_repo_update(target_dir.clone(), None).await;
+
+    let mut settings = load_settings(app_handle);
+    settings.last_update_check = Some(unix_timestamp());
+    let _ = save_settings(app_handle, &settings);
+
+    match result {
+        Ok(info) => {
+            scheduler.set_last_error(None);
+            if !info.has_update {
+                return;
+            }
+            let mut notified = scheduler
+                .notified_hash
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if notified.as_deref() == Some(info.remote_hash.as_str()) {
+                return;
+            }
+            *notified = Some(info.remote_hash.clone());
+            let _ = app_handle.emit(
+                UPDATE_AVAILABLE_EVENT,
+                UpdateAvailableEvent {
+                    target_dir,
+                    local_hash: info.local_hash,
+                    remote_hash: info.remote_hash,
+                },
+            );
+        }
+        Err(e) => scheduler.set_last_error(Some(e)),
+    }
+}
+
+/// Starts the scheduler loop for the saved interval. Called at startup and whenever the
+/// schedule changes; a loop from an older generation exits on its next wake-up.
+pub fn start_update_scheduler(app_handle: &AppHandle) {
+    let Some(interval_hours) = load_settings(app_handle).update_check_interval_hours else {
+        return;
+    };
+    let generation = app_handle
+        .state::<UpdateScheduler>()
+        .generation
+        .load(Ordering::SeqCst);
+    let interval = interval_hours.max(MIN_INTERVAL_HOURS) * 3600;
+    let task_app = app_handle.clone();
+    spawn_tracked(app_handle, "update_scheduler", async move {
+        loop {
+            let scheduler = task_app.state::<UpdateScheduler>();
+            if scheduler.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let last_checked = load_settings(&task_app).last_update_check.unwrap_or(0);
+            if unix_timestamp().saturating_sub(last_checked) >= interval {
+                run_scheduled_check(&task_app).await;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_update_schedule(app_handle: AppHandle) -> Result<UpdateSchedule, String> {
+    Ok(schedule(&app_handle))
+}
+
+/// Enables periodic update checks every `interval_hours` (at least one hour), or turns
+/// them off with `None`. Checks only notify; nothing is pulled automatically.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_update_schedule(
+    app_handle: AppHandle,
+    interval_hours: Option<u64>,
+) -> Result<UpdateSchedule, String> {
+    let mut settings = load_settings(&app_handle);
+    settings.update_check_interval_hours =
+        interval_hours.map(|hours| hours.max(MIN_INTERVAL_HOURS));
+    save_settings(&app_handle, &settings)?;
+
+    app_handle
+        .state::<UpdateScheduler>()
+        .generation
+        .fetch_add(1, Ordering::SeqCst);
+    start_update_scheduler(&app_handle);
+    Ok(schedule(&app_handle))
+}
@@ -0,0 +1,138 @@
+// src-tauri/src/commands/uv_cache.rs
+
+use super::command_utils::{clean_path_arg, new_command};
+use super::settings::{load_settings, save_settings};
+use super::storage_media::dir_size_bytes;
+use super::system_info::volume_for_path;
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tokio::process::Command;
+
+const UV_CACHE_ENV: &str = "UV_CACHE_DIR";
+// A full IndexTTS sync (torch, CUDA wheels) fills several GB of cache.
+const MIN_UV_CACHE_FREE_GB: f64 = 10.0;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UvCacheLocation {
+    pub path: String,
+    // Set through `set_uv_cache_dir` rather than uv's default or the inherited env.
+    pub custom: bool,
+    pub exists: bool,
+    pub size_bytes: u64,
+    pub available_gb: Option<f64>,
+}
+
+/// Points a uv child process at the configured cache directory, if any.
+pub fn apply_uv_cache_dir(app_handle: &AppHandle, command: &mut Command) {
+    if let Some(dir) = load_settings(app_handle).uv_cache_dir {
+        command.env(UV_CACHE_ENV, dir);
+    }
+}
+
+/// uv's own default when neither the setting nor `UV_CACHE_DIR` is set.
+fn default_uv_cache_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        env::var_os("LOCALAPPDATA").map(|dir| PathBuf::from(dir).join("uv").join("cache"))
+    } else if let Some(xdg) = env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(PathBuf::from(xdg).join("uv"))
+    } else {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("uv"))
+    }
+}
+
+/// Asks uv for the cache directory it would use, falling back to its documented default.
+async fn resolve_uv_cache_dir(app_handle: &AppHandle) -> Option<PathBuf> {
+    let mut command = new_command("uv");
+    command.args(["cache", "dir"]);
+    apply_uv_cache_dir(app_handle, &mut command);
+    if let Ok(output) = command.output().await {
+        let dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !dir.is_empty() {
+            return Some(PathBuf::from(dir));
+        }
+    }
+    load_settings(app_handle)
+        .uv_cache_dir
+        .map(PathBuf::from)
+        .or_else(|| env::var_os(UV_CACHE_ENV).map(PathBuf::from))
+        .or_else(default_uv_cache_dir)
+}
+
+fn cache_location(path: &Path, custom: bool) -> UvCacheLocation {
+    UvCacheLocation {
+        path: path.display().to_string(),
+        custom,
+        exists: path.is_dir(),
+        size_bytes: dir_size_bytes(path),
+        available_gb: volume_for_path(path).map(|volume| volume.available_gb),
+    }
+}
+
+/// Creates `dir` and checks that it is writable and its volume has room for the cache.
+fn validate_cache_dir(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| {
+        format!(
+            "Failed to create uv cache directory {}: {}",
+            dir.display(),
+            e
+        )
+    })?;
+    let probe = dir.join(".indextts-write-test");
+    fs::write(&probe, b"ok").map_err(|e| {
+        format!(
+            "uv cache directory {} is not writable: {}",
+            dir.display(),
+            e
+        )
+    })?;
+    let _ = fs::remove_file(&probe);
+
+    if let Some(volume) = volume_for_path(dir) {
+        if volume.available_gb < MIN_UV_CACHE_FREE_GB {
+            return Err(format!(
+                "Only {:.1} GB free on {}; the uv cache needs at least {:.0} GB.",
+                volume.available_gb, volume.mount_point, MIN_UV_CACHE_FREE_GB
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reports the cache directory uv uses for environment setup and how much it holds.
+#[tauri::command]
+pub async fn get_uv_cache_location(app_handle: AppHandle) -> Result<UvCacheLocation, String> {
+    let custom = load_settings(&app_handle).uv_cache_dir.is_some();
+    let dir = resolve_uv_cache_dir(&app_handle)
+        .await
+        .ok_or_else(|| "Could not determine the uv cache directory.".to_string())?;
+    Ok(cache_location(&dir, custom))
+}
+
+/// Sets the `UV_CACHE_DIR` used by environment setup and model tool installs. `None`
+/// (or an empty path) restores uv's default.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_uv_cache_dir(
+    app_handle: AppHandle,
+    path: Option<String>,
+) -> Result<UvCacheLocation, String> {
+    let path = path
+        .map(|path| clean_path_arg(&path))
+        .filter(|path| !path.is_empty());
+    if let Some(dir) = &path {
+        if !Path::new(dir).is_absolute() {
+            return Err(format!(
+                "uv cache directory must be an absolute path: {}",
+                dir
+            ));
+        }
+        validate_cache_dir(Path::new(dir))?;
+    }
+
+    let mut settings = load_settings(&app_handle);
+    settings.uv_cache_dir = path;
+    save_settings(&app_handle, &settings)?;
+    get_uv_cache_location(app_handle).await
+}
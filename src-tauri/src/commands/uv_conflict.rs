@@ -0,0 +1,184 @@
+// src-tauri/src/commands/uv_conflict.rs
+
+use serde::Serialize;
+
+// uv prints this when the resolver gives up; the explanation follows on `╰─▶` lines.
+const NO_SOLUTION_MARKER: &str = "No solution found when resolving dependencies";
+const DEPENDENCY_VERBS: [&str; 2] = [" depends on ", " requires "];
+const ROOT_DEPENDENT: &str = "your project";
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct DependencyEdge {
+    pub dependent: String,
+    pub requirement: String,
+    pub package: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResolutionConflict {
+    pub package: String,
+    pub constraints: Vec<DependencyEdge>,
+    // Constraint that is most likely safe to loosen.
+    pub relax: Option<DependencyEdge>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UvConflictReport {
+    pub summary: String,
+    pub explanation: String,
+    pub edges: Vec<DependencyEdge>,
+    pub conflicts: Vec<ResolutionConflict>,
+}
+
+/// Normalized distribution name of a requirement such as `numpy>=2.0` or `Foo_Bar[extra]<1`.
+fn package_name(requirement: &str) -> String {
+    requirement
+        .split(|c: char| "<>=!~[{;(".contains(c) || c.is_whitespace())
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+        .replace('_', "-")
+}
+
+/// The resolver's explanation with the box-drawing prefixes removed, as one line.
+fn explanation_text(output: &str) -> Option<String> {
+    let start = output.find(NO_SOLUTION_MARKER)?;
+    let explanation: Vec<&str> = output[start..]
+        .lines()
+        .skip(1)
+        .map(|line| {
+            line.trim()
+                .trim_start_matches(|c: char| "╰─▶│×".contains(c))
+                .trim()
+        })
+        .take_while(|line| !line.starts_with("hint:") && !line.starts_with("help:"))
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!explanation.is_empty()).then(|| explanation.join(" "))
+}
+
+fn parse_edges(text: &str) -> Vec<DependencyEdge> {
+    let mut edges = Vec::new();
+    for verb in DEPENDENCY_VERBS {
+        let mut rest = text;
+        while let Some(pos) = rest.find(verb) {
+            let before = &rest[..pos];
+            let after = &rest[pos + verb.len()..];
+            rest = after;
+
+            let mut words = before.split_whitespace().rev();
+            let Some(last) = words.next() else {
+                continue;
+            };
+            let dependent = if last == "project" && words.next() == Some("your") {
+                ROOT_DEPENDENT.to_string()
+            } else {
+                last.trim_matches(',').to_string()
+            };
+            let requirement = after
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches(['.', ','])
+                .to_string();
+            let package = package_name(&requirement);
+            if package.is_empty() || dependent.is_empty() {
+                continue;
+            }
+            let edge = DependencyEdge {
+                dependent,
+                requirement,
+                package,
+            };
+            if !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        }
+    }
+    edges
+}
+
+fn find_conflicts(edges: &[DependencyEdge]) -> Vec<ResolutionConflict> {
+    let mut packages: Vec<&str> = edges.iter().map(|edge| edge.package.as_str()).collect();
+    packages.dedup();
+    let mut conflicts = Vec::new();
+    for package in packages {
+        if conflicts
+            .iter()
+            .any(|conflict: &ResolutionConflict| conflict.package == package)
+        {
+            continue;
+        }
+        let constraints: Vec<DependencyEdge> = edges
+            .iter()
+            .filter(|edge| edge.package == package)
+            .cloned()
+            .collect();
+        let distinct = constraints
+            .iter()
+            .any(|edge| edge.requirement != constraints[0].requirement);
+        if !distinct {
+            continue;
+        }
+        // The project's own pin is the one the user controls; otherwise loosen the
+        // tightest (exact) pin.
+        let relax = constraints
+            .iter()
+            .find(|edge| edge.dependent == ROOT_DEPENDENT)
+            .or_else(|| {
+                constraints
+                    .iter()
+                    .find(|edge| edge.requirement.contains("=="))
+            })
+            .cloned();
+        conflicts.push(ResolutionConflict {
+            package: package.to_string(),
+            constraints,
+            relax,
+        });
+    }
+    conflicts
+}
+
+/// Turns uv's "No solution found" output into the dependency edges it mentions and the
+/// packages whose constraints can't all hold. `None` when the output is not a
+/// resolution failure.
+pub fn parse_uv_conflict_output(output: &str) -> Option<UvConflictReport> {
+    let explanation = explanation_text(output)?;
+    let edges = parse_edges(&explanation);
+    let conflicts = find_conflicts(&edges);
+    let summary = if conflicts.is_empty() {
+        explanation.clone()
+    } else {
+        conflicts
+            .iter()
+            .map(|conflict| {
+                let constraints: Vec<String> = conflict
+                    .constraints
+                    .iter()
+                    .map(|edge| format!("{} requires {}", edge.dependent, edge.requirement))
+                    .collect();
+                let mut line = format!("{}.", constraints.join(", but "));
+                if let Some(relax) = &conflict.relax {
+                    line.push_str(&format!(
+                        " Relaxing {} (from {}) is the likeliest fix.",
+                        relax.requirement, relax.dependent
+                    ));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    Some(UvConflictReport {
+        summary,
+        explanation,
+        edges,
+        conflicts,
+    })
+}
+
+#[tauri::command]
+pub fn parse_uv_conflict(output: String) -> Result<Option<UvConflictReport>, String> {
+    Ok(parse_uv_conflict_output(&output))
+}
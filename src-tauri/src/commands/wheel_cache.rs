@@ -0,0 +1,123 @@
+// src-tauri/src/commands/wheel_cache.rs
+
+use super::command_utils::new_command;
+use super::index_tts::{run_command_with_streaming, sync_extra_args};
+use super::settings::load_settings;
+use super::torch_build::torch_index_url;
+use super::uv_cache::apply_uv_cache_dir;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+const REQUIREMENTS_FILE: &str = "requirements.txt";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WheelCacheReport {
+    pub dest: String,
+    pub requirements_file: String,
+    pub wheel_count: usize,
+    pub size_bytes: u64,
+    // Whether the requirements resolve from `dest` alone with the network disabled.
+    pub offline_resolve_ok: bool,
+    pub offline_resolve_error: Option<String>,
+}
+
+fn wheel_dir_stats(dir: &Path) -> (usize, u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (0, 0);
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok().map(|meta| (entry.path(), meta)))
+        .filter(|(_, meta)| meta.is_file())
+        .fold((0, 0), |(count, size), (path, meta)| {
+            let is_package = path
+                .extension()
+                .is_some_and(|ext| ext == "whl" || ext == "gz" || ext == "zip");
+            (count + usize::from(is_package), size + meta.len())
+        })
+}
+
+async fn export_requirements(target_dir: &str, requirements: &Path) -> Result<(), String> {
+    let output = new_command("uv")
+        .args(["export", "--format", "requirements-txt", "--no-hashes"])
+        .arg("--no-emit-project")
+        .args(sync_extra_args())
+        .arg("-o")
+        .arg(requirements)
+        .current_dir(target_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute uv export: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "uv export failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Resolves the requirements against the wheel directory with the index and network off.
+async fn check_offline_resolve(requirements: &Path, dest: &str) -> Result<(), String> {
+    let output = new_command("uv")
+        .args(["pip", "compile", "--offline", "--no-index", "--quiet"])
+        .arg("--find-links")
+        .arg(dest)
+        .arg(requirements)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute uv pip compile: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+/// Downloads every wheel the deployment's dependencies need into `dest`, for an offline
+/// `setup_index_tts_env` (`offline_wheel_dir`) on a machine without network access.
+/// Wheels are for this machine's platform and Python version.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn prefetch_wheels(
+    app_handle: AppHandle,
+    target_dir: String,
+    dest: String,
+) -> Result<WheelCacheReport, String> {
+    if !Path::new(&target_dir).join("pyproject.toml").is_file() {
+        return Err(format!(
+            "{} has no pyproject.toml. Please clone the repository first.",
+            target_dir
+        ));
+    }
+    fs::create_dir_all(&dest).map_err(|e| format!("Failed to create {}: {}", dest, e))?;
+    let requirements = Path::new(&dest).join(REQUIREMENTS_FILE);
+    export_requirements(&target_dir, &requirements).await?;
+
+    // uv has no download-only mode, so pip does the fetching in a throwaway tool env.
+    let mut command = new_command("uvx");
+    command
+        .args(["pip", "download", "--disable-pip-version-check", "-r"])
+        .arg(&requirements)
+        .arg("-d")
+        .arg(&dest)
+        .current_dir(&target_dir);
+    apply_uv_cache_dir(&app_handle, &mut command);
+    if let Some(build) = load_settings(&app_handle).torch_build {
+        command
+            .arg("--extra-index-url")
+            .arg(torch_index_url(&build));
+    }
+    run_command_with_streaming(&app_handle, "prefetch_wheels", command).await?;
+
+    let offline_error = check_offline_resolve(&requirements, &dest).await.err();
+    let (wheel_count, size_bytes) = wheel_dir_stats(Path::new(&dest));
+    Ok(WheelCacheReport {
+        requirements_file: requirements.display().to_string(),
+        dest,
+        wheel_count,
+        size_bytes,
+        offline_resolve_ok: offline_error.is_none(),
+        offline_resolve_error: offline_error,
+    })
+}
@@ -3,11 +3,60 @@
 
 mod commands;
 
+use crate::commands::capabilities;
+use crate::commands::cloud_folder;
+use crate::commands::command_history::{self, CommandHistory};
+use crate::commands::config_migrations;
+use crate::commands::config_store::{self, ConfigStorage};
+use crate::commands::connectivity;
+use crate::commands::deploy;
+use crate::commands::deploy_log::{self, DeployLogBuffer};
+use crate::commands::deployments;
+use crate::commands::disk_bench;
+use crate::commands::download_accel;
+use crate::commands::download_prewarm;
+use crate::commands::driver_check;
+use crate::commands::encoding;
+use crate::commands::engine::{self, EngineManager};
+use crate::commands::factory_reset;
+use crate::commands::fd_limits;
+use crate::commands::git_locks;
+use crate::commands::git_settings;
+use crate::commands::gpu_usage;
 use crate::commands::index_tts;
 use crate::commands::install_tools;
+use crate::commands::jobs::{self, JobRegistry};
+use crate::commands::launch_args;
+use crate::commands::lfs;
+use crate::commands::log_files::{self, LogFollowers, APP_LOG};
+use crate::commands::log_snapshot;
+use crate::commands::model_compat;
+use crate::commands::model_layout;
+use crate::commands::network;
+use crate::commands::notifications;
+use crate::commands::panic_report;
+use crate::commands::platform_defaults;
+use crate::commands::processes;
+use crate::commands::python_env;
+use crate::commands::readiness;
+use crate::commands::reference_audio;
+use crate::commands::repo_remote;
+use crate::commands::repo_shrink;
+use crate::commands::resource_monitor;
 use crate::commands::server::{self, ServerChildProcess};
+use crate::commands::server_config;
+use crate::commands::settings;
+use crate::commands::step_cancel::{self, RunningSteps};
+use crate::commands::storage_media;
 use crate::commands::system_info;
+use crate::commands::tasks::{self, BackgroundTasks};
 use crate::commands::tool_check;
+use crate::commands::torch_build;
+use crate::commands::tuning;
+use crate::commands::update_scheduler::{self, UpdateScheduler};
+use crate::commands::uv_cache;
+use crate::commands::uv_conflict;
+use crate::commands::wheel_cache;
 use std::env;
 use std::path::PathBuf;
 
@@ -117,25 +166,231 @@ fn main() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(ServerChildProcess::new())
+        .manage(EngineManager::new())
+        .manage(DeployLogBuffer::new())
+        .manage(JobRegistry::new())
+        .manage(ConfigStorage::new())
+        .manage(LogFollowers::new())
+        .manage(BackgroundTasks::new())
+        .manage(UpdateScheduler::new())
+        .manage(CommandHistory::new())
+        .manage(RunningSteps::new())
+        .setup(|app| {
+            panic_report::install_panic_hook(app.handle());
+            // Decide where settings live before anything loads or saves them.
+            let status = config_store::ensure_config_writable(app.handle());
+            if let Some(warning) = status.warning {
+                log_files::append_log_line(app.handle(), APP_LOG, "setup", &warning);
+            }
+            match config_migrations::migrate_config(app.handle()) {
+                Ok(status) => {
+                    if let Some(warning) = status.warning {
+                        log_files::append_log_line(app.handle(), APP_LOG, "setup", &warning);
+                    }
+                }
+                Err(e) => log_files::append_log_line(
+                    app.handle(),
+                    APP_LOG,
+                    "setup",
+                    &format!("Config migration failed: {}", e),
+                ),
+            }
+            if let Some(warning) = engine::restore_engine_config(app.handle()) {
+                log_files::append_log_line(app.handle(), APP_LOG, "setup", &warning);
+            }
+            platform_defaults::init_platform_defaults(app.handle());
+            update_scheduler::start_update_scheduler(app.handle());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             system_info::get_system_info,
+            platform_defaults::suggest_defaults,
+            config_store::check_config_writable,
+            config_migrations::get_config_schema_version,
+            disk_bench::benchmark_disk,
+            disk_bench::estimate_deploy_time,
+            storage_media::check_storage_media,
             tool_check::check_tools,
+            encoding::check_encoding,
+            capabilities::check_capabilities,
+            fd_limits::check_file_descriptor_limit,
+            cloud_folder::normalize_path,
+            cloud_folder::check_cloud_folder,
+            tool_check::get_ffmpeg_status,
             install_tools::install_git_and_lfs,
             install_tools::install_uv,
-            install_tools::install_python, // Added this line
+            install_tools::install_python,
+            install_tools::install_ffmpeg,
             index_tts::clone_index_tts_repo,
-            index_tts::init_git_lfs, // New command
+            index_tts::init_git_lfs,
+            index_tts::repair_lfs,
+            lfs::check_lfs_resolved,
             index_tts::check_index_tts_repo,
             index_tts::setup_index_tts_env,
+            wheel_cache::prefetch_wheels,
+            uv_conflict::parse_uv_conflict,
+            uv_cache::get_uv_cache_location,
+            uv_cache::set_uv_cache_dir,
             index_tts::install_hf_or_modelscope_tools,
             index_tts::download_index_tts_model,
+            index_tts::ensure_model,
+            model_layout::check_model_layout,
+            model_layout::repair_model_layout,
+            model_layout::reconcile_model_location,
+            model_compat::check_model_code_compat,
+            download_accel::get_download_acceleration_status,
+            download_accel::set_hf_transfer_enabled,
+            download_prewarm::prewarm_download,
             index_tts::run_gpu_check,
+            driver_check::revalidate_after_driver_update,
+            gpu_usage::get_gpu_usage,
+            gpu_usage::estimate_vram_required,
+            gpu_usage::validate_device,
+            deploy::deploy_index_tts,
+            deploy::get_deploy_progress,
+            deploy::resume_deploy,
+            deploy::deploy_headless,
+            step_cancel::cancel_deploy_step,
+            deploy::get_deploy_timing_summary,
+            deploy_log::get_recent_deploy_logs,
+            command_history::get_last_commands,
+            resource_monitor::get_resource_status,
+            jobs::start_job,
+            jobs::get_job_status,
+            jobs::cancel_job,
+            log_files::read_log_file,
+            log_files::follow_log_file,
+            log_files::stop_following_log_file,
+            log_snapshot::export_server_log_snapshot,
+            panic_report::list_panic_reports,
             server::start_index_tts_server,
             server::stop_index_tts_server,
+            server::cancel_server_start,
             server::get_server_status,
-            server::check_repo_update, // New command
-            server::pull_repo          // New command
+            server::get_server_logs,
+            server::get_server_logs_since,
+            server::verify_server_bind,
+            server::get_last_startup_duration,
+            server::check_repo_update,
+            server::pull_repo,
+            launch_args::validate_launch_args,
+            update_scheduler::get_update_schedule,
+            update_scheduler::set_update_schedule,
+            git_locks::clear_git_locks,
+            git_settings::optimize_git_settings,
+            git_settings::check_git_identity,
+            repo_shrink::shrink_repo,
+            server::get_update_changelog,
+            server::validate_port,
+            server::get_server_url,
+            server::get_server_share_url,
+            processes::list_related_processes,
+            processes::kill_process,
+            network::list_network_interfaces,
+            connectivity::diagnose_connectivity,
+            server_config::list_launch_presets,
+            server_config::save_launch_preset,
+            server_config::delete_launch_preset,
+            server_config::get_cpu_threads,
+            server_config::set_cpu_threads,
+            server_config::get_stop_scan_ports,
+            server_config::set_stop_scan_ports,
+            engine::get_engine_config,
+            engine::update_config,
+            engine::set_active_model,
+            deployments::list_deployments,
+            deployments::import_existing_deployment,
+            deployments::check_isolation,
+            deployments::diff_deployments,
+            python_env::env_freeze,
+            python_env::check_imports,
+            python_env::check_precision_support,
+            python_env::check_interpreter_consistency,
+            readiness::verify_ready_to_launch,
+            reference_audio::validate_reference_audio,
+            repo_remote::get_repo_remote,
+            repo_remote::set_repo_remote,
+            settings::get_settings,
+            settings::set_keep_server_on_exit,
+            settings::set_min_free_disk_gb,
+            factory_reset::factory_reset,
+            notifications::set_notifications_enabled,
+            torch_build::suggest_torch_build,
+            torch_build::set_torch_build,
+            tuning::recommend_settings,
+            tuning::apply_recommended_settings
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                tasks::shutdown_background_tasks(app_handle);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    /// `module::function` for every `#[tauri::command]` in `src/commands`.
+    fn declared_commands() -> Vec<String> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/commands");
+        let mut commands = Vec::new();
+        for entry in fs::read_dir(&dir).expect("src/commands is readable") {
+            let path = entry.expect("directory entry").path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+                continue;
+            }
+            let module = path.file_stem().unwrap().to_string_lossy().to_string();
+            let source = fs::read_to_string(&path).expect("command module is readable");
+            let mut lines = source.lines();
+            while let Some(line) = lines.next() {
+                if !line.trim_start().starts_with("#[tauri::command") {
+                    continue;
+                }
+                let name = lines
+                    .by_ref()
+                    .find_map(|line| line.split_once("fn ").map(|(_, rest)| rest))
+                    .and_then(|rest| rest.split(['(', '<']).next())
+                    .unwrap_or_else(|| panic!("no fn after a command attribute in {}", module));
+                commands.push(format!("{}::{}", module, name.trim()));
+            }
+        }
+        commands.sort();
+        commands
+    }
+
+    /// Entries of the `generate_handler!` list in this file.
+    fn registered_commands() -> Vec<String> {
+        let source = include_str!("main.rs");
+        let start = source
+            .find("generate_handler![")
+            .expect("generate_handler! call")
+            + "generate_handler![".len();
+        let end = start + source[start..].find(']').expect("end of generate_handler!");
+        source[start..end]
+            .lines()
+            .map(|line| line.split("//").next().unwrap_or_default())
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn every_command_is_registered() {
+        let registered = registered_commands();
+        let missing: Vec<String> = declared_commands()
+            .into_iter()
+            .filter(|command| !registered.contains(command))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "commands missing from generate_handler!: {}",
+            missing.join(", ")
+        );
+    }
 }
@@ -1487,7 +1487,8 @@ const ServerControlStep: React.FC<{ onBackToDeploy: () => void }> = ({ onBackToD
                 }
             } catch (err) {
                 setStatus('Stopped');
-                setLogs(l => [...l, `[ERROR] 服务启动失败: ${err}`, "请确认仓库中存在 webui.py 并已完成核心部署。"]);
+                const message = (err as { message?: string } | null)?.message ?? err;
+                setLogs(l => [...l, `[ERROR] 服务启动失败: ${message}`, "请确认仓库中存在 webui.py 并已完成核心部署。"]);
             }
         } else { 
             try {
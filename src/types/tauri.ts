@@ -19,10 +19,14 @@ export enum ModelSource {
   ModelScope = "ModelScope",
 }
 
+export type GpuVendor = "Nvidia" | "Amd" | "Intel" | "Apple";
+
 export interface GpuInfo {
+  vendor?: GpuVendor;
   has_cuda: boolean;
   name?: string | null;
   vram_gb?: number | null;
+  free_vram_gb?: number | null;
   recommended_fp16: boolean;
 }
 